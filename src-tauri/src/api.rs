@@ -1,14 +1,16 @@
 use async_trait::async_trait;
 use json_patch::Patch;
 use log::error;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use crate::document::{Command, Document, DocumentResult};
+use crate::document::{Command, Document, DocumentResult, WorkbenchGuide};
 use crate::dto::{self, StateTrim, ToFileName};
-use crate::export::export_sheet;
-use crate::features::texture_cache;
-use crate::sheet::{Absolute, Sheet};
+use crate::export::{export_sheet, packing_stats, preview_template_context};
+use crate::features::{autosave, texture_cache, thumbnail_cache};
+use crate::import::AtlasRegion;
+use crate::sheet::{Absolute, ExportSettings, Sheet};
 use crate::state::{self, State};
 use crate::TigerApp;
 
@@ -38,28 +40,81 @@ impl state::Handle {
 
 #[async_trait]
 pub trait Api {
+    fn align_keyframe_offsets(&self, align: dto::Align) -> Result<Patch, ()>;
     fn begin_drag_and_drop_frame<P: Into<PathBuf>>(&self, frame: P) -> Result<Patch, ()>;
     fn begin_export_as(&self) -> Result<Patch, ()>;
     fn close_document<P: AsRef<Path>>(&self, path: P) -> Result<Patch, ()>;
     fn copy(&self) -> Result<Patch, ()>;
+    fn copy_keyframe_hitboxes(&self) -> Result<Patch, ()>;
     fn create_animation(&self) -> Result<Patch, ()>;
     fn create_hitbox(&self, position: Option<(i32, i32)>) -> Result<Patch, ()>;
     fn cut(&self) -> Result<Patch, ()>;
     fn delete_frame<P: Into<PathBuf>>(&self, path: P) -> Result<Patch, ()>;
     fn delete_hitbox<S: Into<String>>(&self, name: S) -> Result<Patch, ()>;
+    fn delete_hitbox_from_all_keyframes<S: Into<String>>(&self, name: S) -> Result<Patch, ()>;
+    fn discard_autosave(&self) -> Result<Patch, ()>;
     fn drop_frame_on_timeline(&self, direction: dto::Direction, index: usize) -> Result<Patch, ()>;
+    fn duplicate_selected_hitboxes(&self) -> Result<Patch, ()>;
     fn edit_animation<S: Into<String>>(&self, name: S) -> Result<Patch, ()>;
     async fn export(&self) -> Result<Patch, ()>;
+    async fn export_and_reveal(&self) -> Result<Patch, ()>;
+    fn export_command_history(&self, redact_paths: bool) -> Result<String, String>;
+    async fn export_contact_sheet<P: Into<PathBuf> + Send>(&self, path: P) -> Result<Patch, ()>;
+    fn find_frame_usages<P: AsRef<Path>>(&self, path: P) -> Result<Vec<dto::FrameUsage>, ()>;
+    fn fit_workbench_to_item(&self, viewport_size: (f32, f32)) -> Result<Patch, ()>;
+    fn flip_hitboxes_horizontally(&self) -> Result<Patch, ()>;
     fn focus_document<P: AsRef<Path>>(&self, path: P) -> Result<Patch, ()>;
+    fn get_animation_keyframes<S: Into<String>>(
+        &self,
+        name: S,
+    ) -> Result<Vec<dto::KeyframeSummary>, ()>;
+    fn get_animation_thumbnail<S: Into<String>>(&self, name: S) -> Result<Option<Vec<u8>>, ()>;
+    fn get_current_keyframe_index(&self) -> Result<Option<usize>, ()>;
+    fn get_degenerate_animations(&self) -> Result<Vec<String>, ()>;
+    fn get_hitbox_rect<S: Into<String>>(
+        &self,
+        name: S,
+    ) -> Result<Option<((i32, i32), (u32, u32))>, ()>;
+    fn get_keyframe_offset(&self) -> Result<Option<(i32, i32)>, ()>;
+    fn get_missing_keyframe_frames(&self) -> Result<Vec<(String, dto::Direction, usize)>, ()>;
+    async fn get_packing_stats(&self) -> Result<dto::PackingStats, String>;
+    async fn get_template_context_preview(&self) -> Result<String, String>;
+    fn get_timeline_clock(&self) -> Result<u64, ()>;
+    fn get_unsaved_documents(&self) -> Result<Vec<PathBuf>, ()>;
+    async fn import_aseprite<P: Into<PathBuf> + Send>(&self, path: P) -> Result<Patch, ()>;
+    async fn import_atlas_regions<P: Into<PathBuf> + Send>(
+        &self,
+        path: P,
+        regions: Vec<AtlasRegion>,
+    ) -> Result<Patch, ()>;
     fn import_frames<P: Into<PathBuf>>(&self, paths: Vec<P>) -> Result<Patch, ()>;
+    async fn import_sprite_strip<P: Into<PathBuf> + Send>(
+        &self,
+        path: P,
+        frame_width: u32,
+        frame_height: u32,
+        count: u32,
+    ) -> Result<Patch, ()>;
     fn new_document<P: Into<PathBuf>>(&self, path: P) -> Result<Patch, ()>;
     async fn open_documents<P: Into<PathBuf> + Send + Sync>(
         &self,
         paths: Vec<P>,
     ) -> Result<Patch, ()>;
     fn paste(&self) -> Result<Patch, ()>;
+    fn paste_after_selection(&self) -> Result<Patch, ()>;
+    fn paste_keyframe_hitboxes(&self) -> Result<Patch, ()>;
+    fn paste_properties(&self) -> Result<Patch, ()>;
+    fn recover_autosave(&self) -> Result<Patch, ()>;
+    async fn rename_frame_file<P: Into<PathBuf> + Send, Q: Into<PathBuf> + Send>(
+        &self,
+        from: P,
+        to: Q,
+    ) -> Result<Patch, ()>;
+    async fn reopen_last_closed_document(&self) -> Result<Patch, ()>;
+    fn reorder_animations<S: Into<String>>(&self, names: Vec<S>) -> Result<Patch, ()>;
     fn request_exit(&self) -> Result<Patch, ()>;
     fn reset_timeline_zoom(&self) -> Result<Patch, ()>;
+    fn reset_view(&self) -> Result<Patch, ()>;
     fn reset_workbench_zoom(&self) -> Result<Patch, ()>;
     fn select_animation<S: Into<String>>(
         &self,
@@ -78,17 +133,34 @@ pub trait Api {
         shift: bool,
         ctrl: bool,
     ) -> Result<Patch, ()>;
+    fn set_autosave_interval(&self, interval_secs: u64) -> Result<Patch, ()>;
+    fn set_export_settings(&self, settings: dto::ExportSettings) -> Result<Patch, String>;
     fn set_export_template_file<P: Into<PathBuf>>(&self, file: P) -> Result<Patch, ()>;
     fn set_hitbox_height(&self, height: u32) -> Result<Patch, ()>;
+    fn set_hitbox_kind(&self, kind: dto::HitboxKind) -> Result<Patch, ()>;
     fn set_hitbox_position_x(&self, x: i32) -> Result<Patch, ()>;
     fn set_hitbox_position_y(&self, y: i32) -> Result<Patch, ()>;
     fn set_hitbox_width(&self, width: u32) -> Result<Patch, ()>;
+    fn set_keyframe_colors(&self, colors: dto::KeyframeColors) -> Result<Patch, ()>;
     fn set_keyframe_duration(&self, duration_millies: u64) -> Result<Patch, ()>;
     fn set_keyframe_offset_x(&self, x: i32) -> Result<Patch, ()>;
     fn set_keyframe_offset_y(&self, y: i32) -> Result<Patch, ()>;
+    fn set_restore_session_on_startup(&self, restore: bool) -> Result<Patch, ()>;
+    fn set_save_on_focus_loss(&self, save: bool) -> Result<Patch, ()>;
     fn set_timeline_zoom_amount(&self, amount: f32) -> Result<Patch, ()>;
     fn set_workbench_zoom_factor(&self, zoom_factor: u32) -> Result<Patch, ()>;
+    fn subdivide_hitbox<S: Into<String>>(
+        &self,
+        name: S,
+        columns: u32,
+        rows: u32,
+    ) -> Result<Patch, ()>;
+    fn tick(&self, delta_time_millis: f64) -> Result<Patch, ()>;
+    fn toggle_playback_all_documents(&self) -> Result<Patch, ()>;
+    fn toggle_precise_durations(&self) -> Result<Patch, ()>;
     fn toggle_preserve_aspect_ratio(&self) -> Result<Patch, ()>;
+    fn trim_animation_ends(&self) -> Result<Patch, ()>;
+    async fn was_export_changed(&self) -> Result<bool, String>;
     fn zoom_in_timeline(&self) -> Result<Patch, ()>;
     fn zoom_in_timeline_around(&self, fixed_point: f32) -> Result<Patch, ()>;
     fn zoom_in_workbench(&self) -> Result<Patch, ()>;
@@ -101,6 +173,17 @@ pub trait Api {
 
 #[async_trait]
 impl<T: TigerApp + Sync> Api for T {
+    fn align_keyframe_offsets(&self, align: dto::Align) -> Result<Patch, ()> {
+        let frame_sizes = self.texture_cache().texture_sizes();
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document
+                    .process_command(Command::AlignKeyframeOffsets(align.into(), frame_sizes))
+                    .ok();
+            }
+        }))
+    }
+
     fn begin_drag_and_drop_frame<P: Into<PathBuf>>(&self, frame: P) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
             if let Some(document) = state.current_document_mut() {
@@ -142,6 +225,20 @@ impl<T: TigerApp + Sync> Api for T {
         }))
     }
 
+    fn copy_keyframe_hitboxes(&self) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(data) = state
+                .current_document()
+                .and_then(|d| d.copy_keyframe_hitboxes())
+            {
+                if let Ok(serialized) = serde_json::to_string_pretty(&data) {
+                    self.write_clipboard(serialized);
+                    state.set_clipboard_manifest(Some(data.manifest()));
+                }
+            }
+        }))
+    }
+
     fn create_animation(&self) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
             if let Some(document) = state.current_document_mut() {
@@ -194,6 +291,26 @@ impl<T: TigerApp + Sync> Api for T {
         }))
     }
 
+    fn delete_hitbox_from_all_keyframes<S: Into<String>>(&self, name: S) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document
+                    .process_command(Command::DeleteHitboxFromAllKeyframes(name.into()))
+                    .ok();
+            }
+        }))
+    }
+
+    fn discard_autosave(&self) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                if document.process_command(Command::DiscardAutosave).is_ok() {
+                    autosave::discard(document.path());
+                }
+            }
+        }))
+    }
+
     fn drop_frame_on_timeline(&self, direction: dto::Direction, index: usize) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
             if let Some(document) = state.current_document_mut() {
@@ -204,6 +321,16 @@ impl<T: TigerApp + Sync> Api for T {
         }))
     }
 
+    fn duplicate_selected_hitboxes(&self) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document
+                    .process_command(Command::DuplicateSelectedHitboxes)
+                    .ok();
+            }
+        }))
+    }
+
     fn edit_animation<S: Into<String>>(&self, name: S) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
             if let Some(document) = state.current_document_mut() {
@@ -215,6 +342,35 @@ impl<T: TigerApp + Sync> Api for T {
     }
 
     async fn export(&self) -> Result<Patch, ()> {
+        Ok(match export_current_document(self).await {
+            Ok(_) => Patch(Vec::new()),
+            Err(patch) => patch,
+        })
+    }
+
+    async fn export_and_reveal(&self) -> Result<Patch, ()> {
+        Ok(match export_current_document(self).await {
+            Ok(Some(texture_file)) => {
+                self.reveal_in_explorer(texture_file);
+                Patch(Vec::new())
+            }
+            Ok(None) => Patch(Vec::new()),
+            Err(patch) => patch,
+        })
+    }
+
+    fn export_command_history(&self, redact_paths: bool) -> Result<String, String> {
+        let state_handle = self.state();
+        let state = state_handle.lock();
+        let Some(document) = state.current_document() else {
+            return Err("No document is currently open".to_owned());
+        };
+        serde_json::to_string_pretty(&document.command_history(redact_paths))
+            .map_err(|e| e.to_string())
+    }
+
+    async fn export_contact_sheet<P: Into<PathBuf> + Send>(&self, path: P) -> Result<Patch, ()> {
+        let path = path.into();
         let (sheet, document_name) = {
             let state_handle = self.state();
             let state = state_handle.lock();
@@ -226,7 +382,7 @@ impl<T: TigerApp + Sync> Api for T {
 
         match tauri::async_runtime::spawn_blocking({
             let texture_cache = self.texture_cache();
-            move || export_sheet(&sheet, texture_cache)
+            move || crate::export::export_contact_sheet(&sheet, texture_cache, path)
         })
         .await
         .unwrap()
@@ -236,7 +392,7 @@ impl<T: TigerApp + Sync> Api for T {
                 state.show_error_message(
                     "Export Error".to_owned(),
                     format!(
-                        "An error occured while trying to export `{}`",
+                        "An error occured while trying to export a contact sheet for `{}`",
                         document_name.to_file_name(),
                     ),
                     e.to_string(),
@@ -245,12 +401,256 @@ impl<T: TigerApp + Sync> Api for T {
         }
     }
 
+    fn find_frame_usages<P: AsRef<Path>>(&self, path: P) -> Result<Vec<dto::FrameUsage>, ()> {
+        let state_handle = self.state();
+        let state = state_handle.lock();
+        let Some(document) = state.current_document() else {
+            return Ok(Vec::new());
+        };
+        Ok(document
+            .sheet()
+            .frame_usages(path)
+            .into_iter()
+            .map(
+                |(animation_name, direction, keyframe_index)| dto::FrameUsage {
+                    animation_name,
+                    direction: direction.into(),
+                    keyframe_index,
+                },
+            )
+            .collect())
+    }
+
+    fn fit_workbench_to_item(&self, viewport_size: (f32, f32)) -> Result<Patch, ()> {
+        let frame_sizes = self.texture_cache().texture_sizes();
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document
+                    .process_command(Command::FitWorkbenchToItem(
+                        frame_sizes,
+                        viewport_size.into(),
+                    ))
+                    .ok();
+            }
+        }))
+    }
+
+    fn flip_hitboxes_horizontally(&self) -> Result<Patch, ()> {
+        let frame_sizes = self.texture_cache().texture_sizes();
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document
+                    .process_command(Command::FlipHitboxesHorizontally(frame_sizes))
+                    .ok();
+            }
+        }))
+    }
+
     fn focus_document<P: AsRef<Path>>(&self, path: P) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
             state.focus_document(path.as_ref()).ok();
         }))
     }
 
+    fn get_animation_keyframes<S: Into<String>>(
+        &self,
+        name: S,
+    ) -> Result<Vec<dto::KeyframeSummary>, ()> {
+        let state_handle = self.state();
+        let state = state_handle.lock();
+        let name = name.into();
+        Ok(state
+            .current_document()
+            .and_then(|d| d.sheet().animation(&name))
+            .map(|animation| {
+                animation
+                    .sequences_iter()
+                    .flat_map(|(direction, sequence)| {
+                        sequence
+                            .keyframes_iter()
+                            .map(|keyframe| dto::KeyframeSummary {
+                                direction: (*direction).into(),
+                                frame_path: keyframe.frame().to_owned(),
+                                duration_millis: keyframe.duration_millis(),
+                                offset: keyframe.offset().to_tuple(),
+                                hitbox_count: keyframe.hitboxes_iter().count(),
+                            })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn get_animation_thumbnail<S: Into<String>>(&self, name: S) -> Result<Option<Vec<u8>>, ()> {
+        let key = {
+            let state_handle = self.state();
+            let state = state_handle.lock();
+            let name = name.into();
+            state.current_document().and_then(|document| {
+                let keyframe = document
+                    .sheet()
+                    .animation(&name)?
+                    .sequences_iter()
+                    .find_map(|(_, sequence)| sequence.keyframes_iter().next())?;
+                Some(thumbnail_cache::ThumbnailKey {
+                    document: document.path().to_owned(),
+                    animation: name,
+                    frame: keyframe.frame().to_owned(),
+                    version: document.version(),
+                })
+            })
+        };
+        Ok(key.and_then(|key| self.thumbnail_cache().request(key)))
+    }
+
+    fn get_current_keyframe_index(&self) -> Result<Option<usize>, ()> {
+        let state_handle = self.state();
+        let state = state_handle.lock();
+        Ok(state
+            .current_document()
+            .and_then(|d| d.current_keyframe_index()))
+    }
+
+    fn get_degenerate_animations(&self) -> Result<Vec<String>, ()> {
+        let state_handle = self.state();
+        let state = state_handle.lock();
+        Ok(state
+            .current_document()
+            .map(|d| d.degenerate_animations())
+            .unwrap_or_default())
+    }
+
+    fn get_hitbox_rect<S: Into<String>>(
+        &self,
+        name: S,
+    ) -> Result<Option<((i32, i32), (u32, u32))>, ()> {
+        let state_handle = self.state();
+        let state = state_handle.lock();
+        let name = name.into();
+        Ok(state
+            .current_document()
+            .and_then(|d| d.workbench_keyframe().ok())
+            .and_then(|(_, keyframe)| keyframe.hitbox(&name))
+            .map(|hitbox| (hitbox.position().to_tuple(), hitbox.size().to_tuple())))
+    }
+
+    fn get_keyframe_offset(&self) -> Result<Option<(i32, i32)>, ()> {
+        let state_handle = self.state();
+        let state = state_handle.lock();
+        Ok(state
+            .current_document()
+            .and_then(|d| d.workbench_keyframe().ok())
+            .map(|(_, keyframe)| keyframe.offset().to_tuple()))
+    }
+
+    fn get_missing_keyframe_frames(&self) -> Result<Vec<(String, dto::Direction, usize)>, ()> {
+        let state_handle = self.state();
+        let state = state_handle.lock();
+        Ok(state
+            .current_document()
+            .map(|d| {
+                d.missing_keyframe_frames()
+                    .into_iter()
+                    .map(|(name, direction, index)| (name, direction.into(), index))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn get_packing_stats(&self) -> Result<dto::PackingStats, String> {
+        let sheet = {
+            let state_handle = self.state();
+            let state = state_handle.lock();
+            match state.current_document() {
+                Some(d) => d.sheet().clone(),
+                _ => return Err("No document is currently open".to_owned()),
+            }
+        };
+
+        let stats = tauri::async_runtime::spawn_blocking({
+            let texture_cache = self.texture_cache();
+            move || packing_stats(&sheet, texture_cache)
+        })
+        .await
+        .unwrap()
+        .map_err(|e| e.to_string())?;
+
+        Ok(dto::PackingStats {
+            used_area: stats.used_area,
+            atlas_area: stats.atlas_area,
+            efficiency: stats.efficiency(),
+        })
+    }
+
+    fn get_timeline_clock(&self) -> Result<u64, ()> {
+        let state_handle = self.state();
+        let state = state_handle.lock();
+        Ok(state
+            .current_document()
+            .map(|d| d.timeline_clock().as_millis() as u64)
+            .unwrap_or_default())
+    }
+
+    fn get_unsaved_documents(&self) -> Result<Vec<PathBuf>, ()> {
+        let state_handle = self.state();
+        let state = state_handle.lock();
+        Ok(state
+            .documents_iter()
+            .filter(|d| !d.is_saved())
+            .map(|d| d.path().to_owned())
+            .collect())
+    }
+
+    async fn get_template_context_preview(&self) -> Result<String, String> {
+        let sheet = {
+            let state_handle = self.state();
+            let state = state_handle.lock();
+            match state.current_document() {
+                Some(d) => d.sheet().clone(),
+                _ => return Err("No document is currently open".to_owned()),
+            }
+        };
+
+        tauri::async_runtime::spawn_blocking({
+            let texture_cache = self.texture_cache();
+            move || preview_template_context(&sheet, texture_cache)
+        })
+        .await
+        .unwrap()
+        .map_err(|e| e.to_string())
+    }
+
+    async fn import_aseprite<P: Into<PathBuf> + Send>(&self, path: P) -> Result<Patch, ()> {
+        let path = path.into();
+        let output_directory = path.with_extension("");
+        let imported_sheet = tauri::async_runtime::spawn_blocking({
+            let path = path.clone();
+            move || crate::import::import_aseprite(path, output_directory)
+        })
+        .await
+        .unwrap();
+
+        Ok(self
+            .state()
+            .mutate(StateTrim::Full, |state| match imported_sheet {
+                Ok(imported_sheet) => {
+                    if let Some(document) = state.current_document_mut() {
+                        document
+                            .process_command(Command::ImportAseprite(imported_sheet))
+                            .ok();
+                    }
+                }
+                Err(e) => state.show_error_message(
+                    "Import Error".to_owned(),
+                    format!(
+                        "An error occured while trying to import `{}`",
+                        path.to_file_name(),
+                    ),
+                    e.to_string(),
+                ),
+            }))
+    }
+
     fn import_frames<P: Into<PathBuf>>(&self, paths: Vec<P>) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
             if let Some(document) = state.current_document_mut() {
@@ -263,6 +663,72 @@ impl<T: TigerApp + Sync> Api for T {
         }))
     }
 
+    async fn import_sprite_strip<P: Into<PathBuf> + Send>(
+        &self,
+        path: P,
+        frame_width: u32,
+        frame_height: u32,
+        count: u32,
+    ) -> Result<Patch, ()> {
+        let path = path.into();
+        let frames = tauri::async_runtime::spawn_blocking({
+            let path = path.clone();
+            move || crate::import::import_sprite_strip(path, frame_width, frame_height, count)
+        })
+        .await
+        .unwrap();
+
+        Ok(self.state().mutate(StateTrim::Full, |state| match frames {
+            Ok(frames) => {
+                if let Some(document) = state.current_document_mut() {
+                    document
+                        .process_command(Command::ImportSpriteStrip(frames))
+                        .ok();
+                }
+            }
+            Err(e) => state.show_error_message(
+                "Import Error".to_owned(),
+                format!(
+                    "An error occured while trying to import `{}`",
+                    path.to_file_name(),
+                ),
+                e.to_string(),
+            ),
+        }))
+    }
+
+    async fn import_atlas_regions<P: Into<PathBuf> + Send>(
+        &self,
+        path: P,
+        regions: Vec<AtlasRegion>,
+    ) -> Result<Patch, ()> {
+        let path = path.into();
+        let frames = tauri::async_runtime::spawn_blocking({
+            let path = path.clone();
+            move || crate::import::import_atlas_regions(path, &regions)
+        })
+        .await
+        .unwrap();
+
+        Ok(self.state().mutate(StateTrim::Full, |state| match frames {
+            Ok(frames) => {
+                if let Some(document) = state.current_document_mut() {
+                    document
+                        .process_command(Command::ImportAtlasRegions(frames))
+                        .ok();
+                }
+            }
+            Err(e) => state.show_error_message(
+                "Import Error".to_owned(),
+                format!(
+                    "An error occured while trying to import `{}`",
+                    path.to_file_name(),
+                ),
+                e.to_string(),
+            ),
+        }))
+    }
+
     fn new_document<P: Into<PathBuf>>(&self, path: P) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
             state.new_document(path.into());
@@ -273,24 +739,48 @@ impl<T: TigerApp + Sync> Api for T {
         &self,
         paths: Vec<P>,
     ) -> Result<Patch, ()> {
-        let mut documents: Vec<(PathBuf, DocumentResult<Document>)> = Vec::new();
+        let mut documents: Vec<(PathBuf, DocumentResult<Document>, Option<Sheet<Absolute>>)> =
+            Vec::new();
         for path in paths {
             let open_path: PathBuf = path.into();
-            documents.push((
-                open_path.clone(),
-                tauri::async_runtime::spawn_blocking(move || Document::open(open_path))
-                    .await
-                    .unwrap(),
-            ));
+            let (document, recovered_sheet) = tauri::async_runtime::spawn_blocking({
+                let open_path = open_path.clone();
+                move || (Document::open(&open_path), autosave::recover(&open_path))
+            })
+            .await
+            .unwrap();
+            documents.push((open_path, document, recovered_sheet));
         }
 
         Ok(self.state().mutate(StateTrim::Full, |state| {
             for document in documents {
                 match document {
-                    (_, Ok(d)) => {
+                    (_, Ok(mut d), Some(recovered_sheet)) => {
+                        d.offer_autosave_recovery(recovered_sheet);
+                        state.open_document(d);
+                    }
+                    (path, Ok(d), None) => {
+                        let missing_frames = d.sheet().missing_frames();
                         state.open_document(d);
+                        if !missing_frames.is_empty() {
+                            state.show_error_message(
+                                "Missing Frames".to_owned(),
+                                format!(
+                                    "`{}` has keyframes using frames which are not part of the spritesheet.",
+                                    path.to_file_name()
+                                ),
+                                format!(
+                                    "Use `Repair Spritesheet` from the Edit menu to add the following frames back: {}",
+                                    missing_frames
+                                        .iter()
+                                        .map(|f| f.to_file_name())
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                ),
+                            );
+                        }
                     }
-                    (path, Err(e)) => {
+                    (path, Err(e), _) => {
                         state.show_error_message(
                             "Error".to_owned(),
                             format!(
@@ -317,57 +807,186 @@ impl<T: TigerApp + Sync> Api for T {
         }))
     }
 
-    fn request_exit(&self) -> Result<Patch, ()> {
+    fn recover_autosave(&self) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
-            state.request_exit();
-            if state.should_exit() {
-                self.close_window();
+            if let Some(document) = state.current_document_mut() {
+                document.process_command(Command::RecoverAutosave).ok();
             }
         }))
     }
 
-    fn reset_timeline_zoom(&self) -> Result<Patch, ()> {
-        Ok(self.state().mutate(StateTrim::Full, |state| {
-            if let Some(document) = state.current_document_mut() {
-                document.process_command(Command::ResetTimelineZoom).ok();
+    async fn rename_frame_file<P: Into<PathBuf> + Send, Q: Into<PathBuf> + Send>(
+        &self,
+        from: P,
+        to: Q,
+    ) -> Result<Patch, ()> {
+        let from: PathBuf = from.into();
+        let to: PathBuf = to.into();
+        let Some(document_path) = self
+            .state()
+            .lock()
+            .current_document()
+            .map(|d| d.path().to_owned())
+        else {
+            return Ok(Patch(Vec::new()));
+        };
+
+        let result = tauri::async_runtime::spawn_blocking({
+            let from = from.clone();
+            let to = to.clone();
+            move || {
+                if to.exists() {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::AlreadyExists,
+                        format!("`{}` already exists", to.to_string_lossy()),
+                    ));
+                }
+                std::fs::rename(from, to)
             }
-        }))
-    }
+        })
+        .await
+        .unwrap();
 
-    fn reset_workbench_zoom(&self) -> Result<Patch, ()> {
-        Ok(self.state().mutate(StateTrim::Full, |state| {
-            if let Some(document) = state.current_document_mut() {
-                document.process_command(Command::ResetWorkbenchZoom).ok();
+        Ok(self.state().mutate(StateTrim::Full, |state| match result {
+            Ok(_) => {
+                if let Some(document) = state.document_mut(&document_path) {
+                    document
+                        .process_command(Command::RenameFrameFile(from, to))
+                        .ok();
+                }
             }
+            Err(e) => state.show_error_message(
+                "Error".to_owned(),
+                format!(
+                    "An error occured while trying to rename `{}`",
+                    from.to_file_name()
+                ),
+                e.to_string(),
+            ),
         }))
     }
 
-    fn select_animation<S: Into<String>>(
-        &self,
-        name: S,
-        shift: bool,
-        ctrl: bool,
-    ) -> Result<Patch, ()> {
+    fn paste_after_selection(&self) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
-            if let Some(document) = state.current_document_mut() {
-                document
-                    .process_command(Command::SelectAnimation(name.into(), shift, ctrl))
-                    .ok();
+            if let Some(serialized) = self.read_clipboard() {
+                if let Ok(data) = serde_json::from_str(&serialized) {
+                    if let Some(document) = state.current_document_mut() {
+                        document
+                            .process_command(Command::PasteAfterSelection(data))
+                            .ok();
+                    }
+                }
             }
         }))
     }
 
-    fn select_frame<P: Into<PathBuf>>(
-        &self,
-        path: P,
-        shift: bool,
-        ctrl: bool,
-    ) -> Result<Patch, ()> {
+    fn paste_keyframe_hitboxes(&self) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
-            if let Some(document) = state.current_document_mut() {
-                document
-                    .process_command(Command::SelectFrame(path.into(), shift, ctrl))
-                    .ok();
+            if let Some(serialized) = self.read_clipboard() {
+                if let Ok(data) = serde_json::from_str(&serialized) {
+                    if let Some(document) = state.current_document_mut() {
+                        document
+                            .process_command(Command::PasteKeyframeHitboxes(data))
+                            .ok();
+                    }
+                }
+            }
+        }))
+    }
+
+    fn paste_properties(&self) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(serialized) = self.read_clipboard() {
+                if let Ok(data) = serde_json::from_str(&serialized) {
+                    if let Some(document) = state.current_document_mut() {
+                        document
+                            .process_command(Command::PasteProperties(data))
+                            .ok();
+                    }
+                }
+            }
+        }))
+    }
+
+    async fn reopen_last_closed_document(&self) -> Result<Patch, ()> {
+        let path = self.state().lock().take_last_closed_document();
+        match path {
+            Some(path) => self.open_documents(vec![path]).await,
+            None => Ok(Patch(Vec::new())),
+        }
+    }
+
+    fn reorder_animations<S: Into<String>>(&self, names: Vec<S>) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document
+                    .process_command(Command::ReorderAnimations(
+                        names.into_iter().map(|n| n.into()).collect(),
+                    ))
+                    .ok();
+            }
+        }))
+    }
+
+    fn request_exit(&self) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            state.request_exit();
+            if state.should_exit() {
+                self.close_window();
+            }
+        }))
+    }
+
+    fn reset_timeline_zoom(&self) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document.process_command(Command::ResetTimelineZoom).ok();
+            }
+        }))
+    }
+
+    fn reset_view(&self) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document.process_command(Command::ResetView).ok();
+            }
+        }))
+    }
+
+    fn reset_workbench_zoom(&self) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document.process_command(Command::ResetWorkbenchZoom).ok();
+            }
+        }))
+    }
+
+    fn select_animation<S: Into<String>>(
+        &self,
+        name: S,
+        shift: bool,
+        ctrl: bool,
+    ) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document
+                    .process_command(Command::SelectAnimation(name.into(), shift, ctrl))
+                    .ok();
+            }
+        }))
+    }
+
+    fn select_frame<P: Into<PathBuf>>(
+        &self,
+        path: P,
+        shift: bool,
+        ctrl: bool,
+    ) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document
+                    .process_command(Command::SelectFrame(path.into(), shift, ctrl))
+                    .ok();
             }
         }))
     }
@@ -408,6 +1027,24 @@ impl<T: TigerApp + Sync> Api for T {
         }))
     }
 
+    fn set_autosave_interval(&self, interval_secs: u64) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            state.set_autosave_interval(Duration::from_secs(interval_secs));
+        }))
+    }
+
+    fn set_export_settings(&self, settings: dto::ExportSettings) -> Result<Patch, String> {
+        let mut result = Err("No document is currently open".to_owned());
+        let patch = self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                result = document
+                    .process_command(Command::SetExportSettings(settings.into()))
+                    .map_err(|e| e.to_string());
+            }
+        });
+        result.map(|_| patch)
+    }
+
     fn set_export_template_file<P: Into<PathBuf>>(&self, path: P) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
             if let Some(document) = state.current_document_mut() {
@@ -428,6 +1065,16 @@ impl<T: TigerApp + Sync> Api for T {
         }))
     }
 
+    fn set_hitbox_kind(&self, kind: dto::HitboxKind) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document
+                    .process_command(Command::SetHitboxKind(kind.into()))
+                    .ok();
+            }
+        }))
+    }
+
     fn set_hitbox_position_x(&self, x: i32) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
             if let Some(document) = state.current_document_mut() {
@@ -468,6 +1115,12 @@ impl<T: TigerApp + Sync> Api for T {
         }))
     }
 
+    fn set_keyframe_colors(&self, colors: dto::KeyframeColors) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            state.set_keyframe_colors(colors.into());
+        }))
+    }
+
     fn set_keyframe_duration(&self, duration_millis: u64) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
             if let Some(document) = state.current_document_mut() {
@@ -490,6 +1143,18 @@ impl<T: TigerApp + Sync> Api for T {
         }))
     }
 
+    fn set_restore_session_on_startup(&self, restore: bool) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            state.set_restore_session_on_startup(restore);
+        }))
+    }
+
+    fn set_save_on_focus_loss(&self, save: bool) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            state.set_save_on_focus_loss(save);
+        }))
+    }
+
     fn set_timeline_zoom_amount(&self, amount: f32) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
             if let Some(document) = state.current_document_mut() {
@@ -510,6 +1175,60 @@ impl<T: TigerApp + Sync> Api for T {
         }))
     }
 
+    fn subdivide_hitbox<S: Into<String>>(
+        &self,
+        name: S,
+        columns: u32,
+        rows: u32,
+    ) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document
+                    .process_command(Command::SubdivideHitbox(name.into(), columns, rows))
+                    .ok();
+            }
+        }))
+    }
+
+    // Every playing document advances, so documents keep playing in sync while
+    // another one has focus. Only the current document is displayed, which is
+    // why the others are left out of the patch.
+    fn tick(&self, delta_time_millis: f64) -> Result<Patch, ()> {
+        let delta = Duration::from_nanos((delta_time_millis * 1_000_000.0) as u64);
+        Ok(self
+            .state()
+            .mutate(StateTrim::OnlyCurrentDocument, |state| {
+                for document in state.documents_iter_mut() {
+                    if document.is_timeline_playing() {
+                        document.process_command(Command::Tick(delta)).ok();
+                    }
+                }
+            }))
+    }
+
+    fn toggle_playback_all_documents(&self) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            let any_playing = state.documents_iter().any(|d| d.is_timeline_playing());
+            for document in state.documents_iter_mut() {
+                if any_playing && document.is_timeline_playing() {
+                    document.process_command(Command::Pause).ok();
+                } else if !any_playing {
+                    document.process_command(Command::Play).ok();
+                }
+            }
+        }))
+    }
+
+    fn toggle_precise_durations(&self) -> Result<Patch, ()> {
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document
+                    .process_command(Command::TogglePreciseDurations)
+                    .ok();
+            }
+        }))
+    }
+
     fn toggle_preserve_aspect_ratio(&self) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
             if let Some(document) = state.current_document_mut() {
@@ -520,6 +1239,36 @@ impl<T: TigerApp + Sync> Api for T {
         }))
     }
 
+    fn trim_animation_ends(&self) -> Result<Patch, ()> {
+        let empty_frames = self.texture_cache().transparent_textures();
+        Ok(self.state().mutate(StateTrim::Full, |state| {
+            if let Some(document) = state.current_document_mut() {
+                document
+                    .process_command(Command::TrimAnimationEnds(empty_frames))
+                    .ok();
+            }
+        }))
+    }
+
+    async fn was_export_changed(&self) -> Result<bool, String> {
+        let sheet = {
+            let state_handle = self.state();
+            let state = state_handle.lock();
+            match state.current_document() {
+                Some(d) => d.sheet().clone(),
+                _ => return Err("No document is currently open".to_owned()),
+            }
+        };
+
+        tauri::async_runtime::spawn_blocking({
+            let texture_cache = self.texture_cache();
+            move || crate::export::was_export_changed(&sheet, texture_cache)
+        })
+        .await
+        .unwrap()
+        .map_err(|e| e.to_string())
+    }
+
     fn zoom_in_timeline(&self) -> Result<Patch, ()> {
         Ok(self.state().mutate(StateTrim::Full, |state| {
             if let Some(document) = state.current_document_mut() {
@@ -597,6 +1346,41 @@ impl<T: TigerApp + Sync> Api for T {
     }
 }
 
+async fn export_current_document<T: TigerApp + Sync>(app: &T) -> Result<Option<PathBuf>, Patch> {
+    let (sheet, document_name) = {
+        let state_handle = app.state();
+        let state = state_handle.lock();
+        match state.current_document() {
+            Some(d) => (d.sheet().clone(), d.path().to_file_name()),
+            _ => return Ok(None),
+        }
+    };
+
+    let texture_file = sheet.export_settings().as_ref().map(|s| match s {
+        ExportSettings::Template(t) => t.texture_file().to_owned(),
+    });
+
+    match tauri::async_runtime::spawn_blocking({
+        let texture_cache = app.texture_cache();
+        move || export_sheet(&sheet, texture_cache)
+    })
+    .await
+    .unwrap()
+    {
+        Ok(_) => Ok(texture_file),
+        Err(e) => Err(app.state().mutate(StateTrim::Full, |state| {
+            state.show_error_message(
+                "Export Error".to_owned(),
+                format!(
+                    "An error occured while trying to export `{}`",
+                    document_name.to_file_name(),
+                ),
+                e.to_string(),
+            )
+        })),
+    }
+}
+
 #[tauri::command]
 pub fn get_state(state_handle: tauri::State<'_, state::Handle>) -> Result<dto::State, ()> {
     let state = state_handle.lock();
@@ -642,6 +1426,11 @@ pub fn close_document(app: tauri::AppHandle, path: PathBuf) -> Result<Patch, ()>
     app.close_document(path)
 }
 
+#[tauri::command]
+pub async fn reopen_last_closed_document(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.reopen_last_closed_document().await
+}
+
 #[tauri::command]
 pub fn close_current_document(
     window: tauri::Window,
@@ -674,6 +1463,11 @@ pub fn close_all_documents(
     }))
 }
 
+#[tauri::command]
+pub fn reorder_animations(app: tauri::AppHandle, names: Vec<String>) -> Result<Patch, ()> {
+    app.reorder_animations(names)
+}
+
 #[tauri::command]
 pub fn request_exit(app: tauri::AppHandle) -> Result<Patch, ()> {
     app.request_exit()
@@ -687,13 +1481,8 @@ pub fn cancel_exit(state_handle: tauri::State<'_, state::Handle>) -> Result<Patc
 }
 
 #[tauri::command]
-pub fn reveal_in_explorer(path: PathBuf) {
-    // For future improvements, see https://github.com/tauri-apps/tauri/issues/4062
-    #[cfg(windows)]
-    std::process::Command::new("explorer")
-        .args(["/select,", path.to_string_lossy().as_ref()]) // The comma after select is not a typo
-        .spawn()
-        .unwrap();
+pub fn reveal_in_explorer(app: tauri::AppHandle, path: PathBuf) {
+    app.reveal_in_explorer(path);
 }
 
 #[tauri::command]
@@ -704,6 +1493,7 @@ pub fn close_without_saving(
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         let path = state.current_document().map(|d| d.path().to_owned());
         if let Some(path) = path {
+            autosave::discard(&path);
             state.close_document(path);
             state.advance_exit();
             if state.should_exit() {
@@ -742,6 +1532,7 @@ async fn save_documents(
         for (document, result) in documents.iter().zip(results) {
             match result {
                 Ok(_) => {
+                    autosave::discard(&document.destination);
                     state.relocate_document(&document.source, &document.destination);
                     if let Some(d) = state.document_mut(&document.destination) {
                         d.mark_as_saved(document.version);
@@ -844,11 +1635,25 @@ pub fn redo(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()>
     }))
 }
 
+#[tauri::command]
+pub fn clear_history(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::ClearHistory).ok();
+        }
+    }))
+}
+
 #[tauri::command]
 pub fn copy(app: tauri::AppHandle) -> Result<Patch, ()> {
     app.copy()
 }
 
+#[tauri::command]
+pub fn copy_keyframe_hitboxes(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.copy_keyframe_hitboxes()
+}
+
 #[tauri::command]
 pub fn cut(app: tauri::AppHandle) -> Result<Patch, ()> {
     app.cut()
@@ -859,6 +1664,21 @@ pub fn paste(app: tauri::AppHandle) -> Result<Patch, ()> {
     app.paste()
 }
 
+#[tauri::command]
+pub fn paste_after_selection(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.paste_after_selection()
+}
+
+#[tauri::command]
+pub fn paste_keyframe_hitboxes(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.paste_keyframe_hitboxes()
+}
+
+#[tauri::command]
+pub fn paste_properties(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.paste_properties()
+}
+
 #[tauri::command]
 pub fn set_frames_list_mode(
     state_handle: tauri::State<'_, state::Handle>,
@@ -944,70 +1764,236 @@ pub fn set_animations_list_offset(
 }
 
 #[tauri::command]
-pub fn import_frames(app: tauri::AppHandle, paths: Vec<PathBuf>) -> Result<Patch, ()> {
-    app.import_frames(paths)
+pub async fn import_aseprite(app: tauri::AppHandle, path: PathBuf) -> Result<Patch, ()> {
+    app.import_aseprite(path).await
 }
 
 #[tauri::command]
-pub fn begin_relocate_frames(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
-    Ok(state_handle.mutate(StateTrim::Full, |state| {
-        if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::BeginRelocateFrames).ok();
-        }
-    }))
+pub fn find_frame_usages(app: tauri::AppHandle, path: PathBuf) -> Result<Vec<dto::FrameUsage>, ()> {
+    app.find_frame_usages(path)
 }
 
 #[tauri::command]
-pub fn relocate_frame(
-    state_handle: tauri::State<'_, state::Handle>,
-    from: PathBuf,
-    to: PathBuf,
-) -> Result<Patch, ()> {
-    Ok(state_handle.mutate(StateTrim::Full, |state| {
-        if let Some(document) = state.current_document_mut() {
-            document
-                .process_command(Command::RelocateFrame(from, to))
-                .ok();
-        }
-    }))
+pub fn get_animation_keyframes(
+    app: tauri::AppHandle,
+    name: &str,
+) -> Result<Vec<dto::KeyframeSummary>, ()> {
+    app.get_animation_keyframes(name)
 }
 
 #[tauri::command]
-pub fn cancel_relocate_frames(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
-    Ok(state_handle.mutate(StateTrim::Full, |state| {
-        if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::CancelRelocateFrames).ok();
-        }
-    }))
+pub fn get_animation_thumbnail(app: tauri::AppHandle, name: &str) -> Result<Option<Vec<u8>>, ()> {
+    app.get_animation_thumbnail(name)
 }
 
 #[tauri::command]
-pub fn end_relocate_frames(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
-    Ok(state_handle.mutate(StateTrim::Full, |state| {
-        if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::EndRelocateFrames).ok();
-        }
-    }))
+pub fn get_current_keyframe_index(app: tauri::AppHandle) -> Result<Option<usize>, ()> {
+    app.get_current_keyframe_index()
 }
 
 #[tauri::command]
-pub fn delete_frame(app: tauri::AppHandle, path: PathBuf) -> Result<Patch, ()> {
-    app.delete_frame(path)
+pub fn get_degenerate_animations(app: tauri::AppHandle) -> Result<Vec<String>, ()> {
+    app.get_degenerate_animations()
 }
 
 #[tauri::command]
-pub fn delete_selected_frames(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
-    Ok(state_handle.mutate(StateTrim::Full, |state| {
-        if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::DeleteSelectedFrames).ok();
-        }
-    }))
+pub fn get_hitbox_rect(
+    app: tauri::AppHandle,
+    name: &str,
+) -> Result<Option<((i32, i32), (u32, u32))>, ()> {
+    app.get_hitbox_rect(name)
 }
 
 #[tauri::command]
-pub fn delete_selection(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
-    Ok(state_handle.mutate(StateTrim::Full, |state| {
-        if let Some(document) = state.current_document_mut() {
+pub fn get_keyframe_offset(app: tauri::AppHandle) -> Result<Option<(i32, i32)>, ()> {
+    app.get_keyframe_offset()
+}
+
+#[tauri::command]
+pub fn get_missing_keyframe_frames(
+    app: tauri::AppHandle,
+) -> Result<Vec<(String, dto::Direction, usize)>, ()> {
+    app.get_missing_keyframe_frames()
+}
+
+#[tauri::command]
+pub async fn get_packing_stats(app: tauri::AppHandle) -> Result<dto::PackingStats, String> {
+    app.get_packing_stats().await
+}
+
+#[tauri::command]
+pub fn get_timeline_clock(app: tauri::AppHandle) -> Result<u64, ()> {
+    app.get_timeline_clock()
+}
+
+#[tauri::command]
+pub fn get_unsaved_documents(app: tauri::AppHandle) -> Result<Vec<PathBuf>, ()> {
+    app.get_unsaved_documents()
+}
+
+#[tauri::command]
+pub fn import_frames(app: tauri::AppHandle, paths: Vec<PathBuf>) -> Result<Patch, ()> {
+    app.import_frames(paths)
+}
+
+#[tauri::command]
+pub fn import_frames_into_animation(
+    state_handle: tauri::State<'_, state::Handle>,
+    paths: Vec<PathBuf>,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::ImportFramesIntoAnimation(paths))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub async fn import_atlas_regions(
+    app: tauri::AppHandle,
+    path: PathBuf,
+    regions: Vec<AtlasRegion>,
+) -> Result<Patch, ()> {
+    app.import_atlas_regions(path, regions).await
+}
+
+#[tauri::command]
+pub async fn import_sprite_strip(
+    app: tauri::AppHandle,
+    path: PathBuf,
+    frame_width: u32,
+    frame_height: u32,
+    count: u32,
+) -> Result<Patch, ()> {
+    app.import_sprite_strip(path, frame_width, frame_height, count)
+        .await
+}
+
+#[tauri::command]
+pub fn begin_relocate_frames(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::BeginRelocateFrames).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn relocate_frame(
+    state_handle: tauri::State<'_, state::Handle>,
+    from: PathBuf,
+    to: PathBuf,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::RelocateFrame(from, to))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn cancel_relocate_frames(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::CancelRelocateFrames).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn end_relocate_frames(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::EndRelocateFrames).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn relocate_frames_root(
+    state_handle: tauri::State<'_, state::Handle>,
+    old_root: PathBuf,
+    new_root: PathBuf,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::RelocateFramesRoot(old_root, new_root))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn replace_frame_source(
+    state_handle: tauri::State<'_, state::Handle>,
+    old: PathBuf,
+    new: PathBuf,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::ReplaceFrameSource(old, new))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub async fn rename_frame_file(
+    app: tauri::AppHandle,
+    from: PathBuf,
+    to: PathBuf,
+) -> Result<Patch, ()> {
+    app.rename_frame_file(from, to).await
+}
+
+#[tauri::command]
+pub fn delete_frame(app: tauri::AppHandle, path: PathBuf) -> Result<Patch, ()> {
+    app.delete_frame(path)
+}
+
+#[tauri::command]
+pub fn set_frame_normal_map(
+    state_handle: tauri::State<'_, state::Handle>,
+    frame: PathBuf,
+    normal_map: Option<PathBuf>,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetFrameNormalMap(frame, normal_map))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn repair_sheet(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::RepairSheet).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn delete_selected_frames(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::DeleteSelectedFrames).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn delete_selection(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
             document.process_command(Command::DeleteSelection).ok();
         }
     }))
@@ -1085,6 +2071,55 @@ pub fn select_all(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch
     }))
 }
 
+#[tauri::command]
+pub fn select_next_animation(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::SelectNextAnimation).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn select_previous_animation(
+    state_handle: tauri::State<'_, state::Handle>,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::SelectPreviousAnimation).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn invert_hitbox_selection(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::InvertHitboxSelection)
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn select_keyframes_in_range(
+    state_handle: tauri::State<'_, state::Handle>,
+    start_millis: u64,
+    end_millis: u64,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SelectKeyframesInRange(
+                    Duration::from_millis(start_millis),
+                    Duration::from_millis(end_millis),
+                ))
+                .ok();
+        }
+    }))
+}
+
 #[tauri::command]
 pub fn select_animation(
     app: tauri::AppHandle,
@@ -1144,6 +2179,14 @@ pub fn center_workbench(state_handle: tauri::State<'_, state::Handle>) -> Result
     }))
 }
 
+#[tauri::command]
+pub fn fit_workbench_to_item(
+    app: tauri::AppHandle,
+    viewport_size: (f32, f32),
+) -> Result<Patch, ()> {
+    app.fit_workbench_to_item(viewport_size)
+}
+
 #[tauri::command]
 pub fn zoom_in_workbench(app: tauri::AppHandle) -> Result<Patch, ()> {
     app.zoom_in_workbench()
@@ -1181,109 +2224,262 @@ pub fn reset_workbench_zoom(app: tauri::AppHandle) -> Result<Patch, ()> {
 }
 
 #[tauri::command]
-pub fn enable_sprite_darkening(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+pub fn reset_view(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.reset_view()
+}
+
+#[tauri::command]
+pub fn add_workbench_guide(
+    state_handle: tauri::State<'_, state::Handle>,
+    orientation: dto::GuideOrientation,
+    position: i32,
+) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::EnableSpriteDarkening)
+                .process_command(Command::AddWorkbenchGuide(WorkbenchGuide {
+                    orientation: orientation.into(),
+                    position,
+                }))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn disable_sprite_darkening(
+pub fn remove_workbench_guide(
     state_handle: tauri::State<'_, state::Handle>,
+    index: usize,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::DisableSpriteDarkening)
+                .process_command(Command::RemoveWorkbenchGuide(index))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn hide_sprite(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
-    Ok(state_handle.mutate(StateTrim::Full, |state| {
+pub fn move_workbench_guide(
+    state_handle: tauri::State<'_, state::Handle>,
+    index: usize,
+    position: i32,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::OnlyWorkbench, |state| {
         if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::HideSprite).ok();
+            document
+                .process_command(Command::MoveWorkbenchGuide(index, position))
+                .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn show_sprite(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+pub fn clear_workbench_guides(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::ShowSprite).ok();
+            document.process_command(Command::ClearWorkbenchGuides).ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn hide_hitboxes(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+pub fn set_pan_button(
+    state_handle: tauri::State<'_, state::Handle>,
+    pan_button: dto::PanButton,
+) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::HideHitboxes).ok();
+            document
+                .process_command(Command::SetPanButton(pan_button.into()))
+                .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn show_hitboxes(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+pub fn set_grid_spacing(
+    state_handle: tauri::State<'_, state::Handle>,
+    spacing: u32,
+) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::ShowHitboxes).ok();
+            document
+                .process_command(Command::SetGridSpacing(spacing))
+                .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn hide_origin(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+pub fn set_grid_subdivisions(
+    state_handle: tauri::State<'_, state::Handle>,
+    subdivisions: u32,
+) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::HideOrigin).ok();
+            document
+                .process_command(Command::SetGridSubdivisions(subdivisions))
+                .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn show_origin(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+pub fn set_origin_offset(
+    state_handle: tauri::State<'_, state::Handle>,
+    offset: (i32, i32),
+) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::ShowOrigin).ok();
+            document
+                .process_command(Command::SetOriginOffset(offset.into()))
+                .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn create_animation(app: tauri::AppHandle) -> Result<Patch, ()> {
-    app.create_animation()
-}
-
-#[tauri::command]
-pub fn edit_animation(app: tauri::AppHandle, name: &str) -> Result<Patch, ()> {
-    app.edit_animation(name)
-}
-
-#[tauri::command]
-pub fn begin_rename_animation(
-    state_handle: tauri::State<'_, state::Handle>,
-    animation_name: String,
-) -> Result<Patch, ()> {
+pub fn enable_sprite_darkening(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::BeginRenameAnimation(animation_name))
+                .process_command(Command::EnableSpriteDarkening)
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn begin_rename_hitbox(
+pub fn disable_sprite_darkening(
+    state_handle: tauri::State<'_, state::Handle>,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::DisableSpriteDarkening)
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn hide_sprite(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::HideSprite).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn show_sprite(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::ShowSprite).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn hide_hitboxes(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::HideHitboxes).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn show_hitboxes(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::ShowHitboxes).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn toggle_hitbox_visibility(
+    state_handle: tauri::State<'_, state::Handle>,
+    name: String,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::ToggleHitboxVisibility(name))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn show_all_hitboxes(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::ShowAllHitboxes).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn hide_origin(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::HideOrigin).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn show_origin(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::ShowOrigin).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn create_animation(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.create_animation()
+}
+
+#[tauri::command]
+pub fn create_animation_from_selected_frames(
+    state_handle: tauri::State<'_, state::Handle>,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::CreateAnimationFromSelectedFrames)
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn edit_animation(app: tauri::AppHandle, name: &str) -> Result<Patch, ()> {
+    app.edit_animation(name)
+}
+
+#[tauri::command]
+pub fn begin_rename_animation(
+    state_handle: tauri::State<'_, state::Handle>,
+    animation_name: String,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::BeginRenameAnimation(animation_name))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn begin_rename_hitbox(
     state_handle: tauri::State<'_, state::Handle>,
     hitbox_name: String,
 ) -> Result<Patch, ()> {
@@ -1328,6 +2524,44 @@ pub fn end_rename_animation(
     }))
 }
 
+#[tauri::command]
+pub fn normalize_animation_names(
+    state_handle: tauri::State<'_, state::Handle>,
+    case: dto::Case,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            let document_name = document.path().to_file_name();
+            let result = document.process_command(Command::NormalizeAnimationNames(case.into()));
+            if let Err(e) = result {
+                state.show_error_message(
+                    "Rename Error".to_owned(),
+                    format!(
+                        "An error occured while trying to rename animations in `{}`",
+                        document_name
+                    ),
+                    e.to_string(),
+                );
+            }
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn swap_animation_names(
+    state_handle: tauri::State<'_, state::Handle>,
+    name_a: String,
+    name_b: String,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SwapAnimationNames(name_a, name_b))
+                .ok();
+        }
+    }))
+}
+
 #[tauri::command]
 pub fn end_rename_hitbox(
     state_handle: tauri::State<'_, state::Handle>,
@@ -1356,6 +2590,20 @@ pub fn delete_animation(
     }))
 }
 
+#[tauri::command]
+pub fn delete_animations_not_matching(
+    state_handle: tauri::State<'_, state::Handle>,
+    query: String,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::DeleteAnimationsNotMatching(query))
+                .ok();
+        }
+    }))
+}
+
 #[tauri::command]
 pub fn delete_selected_animations(
     state_handle: tauri::State<'_, state::Handle>,
@@ -1370,21 +2618,27 @@ pub fn delete_selected_animations(
 }
 
 #[tauri::command]
-pub fn tick(
+pub fn set_animation_frame(
     state_handle: tauri::State<'_, state::Handle>,
-    delta_time_millis: f64,
+    path: PathBuf,
 ) -> Result<Patch, ()> {
-    Ok(
-        state_handle.mutate(StateTrim::OnlyCurrentDocument, |state| {
-            if let Some(document) = state.current_document_mut() {
-                document
-                    .process_command(Command::Tick(Duration::from_nanos(
-                        (delta_time_millis * 1_000_000.0) as u64,
-                    )))
-                    .ok();
-            }
-        }),
-    )
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetAnimationFrame(path))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn tick(app: tauri::AppHandle, delta_time_millis: f64) -> Result<Patch, ()> {
+    app.tick(delta_time_millis)
+}
+
+#[tauri::command]
+pub fn toggle_playback_all_documents(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.toggle_playback_all_documents()
 }
 
 #[tauri::command]
@@ -1408,13 +2662,12 @@ pub fn pause(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()>
 #[tauri::command]
 pub fn scrub_timeline(
     state_handle: tauri::State<'_, state::Handle>,
-    time_millis: u64,
+    time_millis: f64,
 ) -> Result<Patch, ()> {
+    let time = Duration::from_secs_f64(time_millis.max(0.0) / 1_000.0);
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document
-                .process_command(Command::ScrubTimeline(Duration::from_millis(time_millis)))
-                .ok();
+            document.process_command(Command::ScrubTimeline(time)).ok();
         }
     }))
 }
@@ -1455,6 +2708,20 @@ pub fn jump_to_next_frame(state_handle: tauri::State<'_, state::Handle>) -> Resu
     }))
 }
 
+#[tauri::command]
+pub fn jump_to_keyframe(
+    state_handle: tauri::State<'_, state::Handle>,
+    index: usize,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::JumpToKeyframe(index))
+                .ok();
+        }
+    }))
+}
+
 #[tauri::command]
 pub fn set_snap_keyframe_durations(
     state_handle: tauri::State<'_, state::Handle>,
@@ -1513,6 +2780,22 @@ pub fn set_keyframe_snapping_base_duration(
     }))
 }
 
+#[tauri::command]
+pub fn set_min_keyframe_duration(
+    state_handle: tauri::State<'_, state::Handle>,
+    duration_millis: u64,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetMinKeyframeDuration(Duration::from_millis(
+                    duration_millis,
+                )))
+                .ok();
+        }
+    }))
+}
+
 #[tauri::command]
 pub fn zoom_in_timeline(app: tauri::AppHandle) -> Result<Patch, ()> {
     app.zoom_in_timeline()
@@ -1559,6 +2842,34 @@ pub fn set_timeline_offset(
     }))
 }
 
+#[tauri::command]
+pub fn set_follow_playhead(
+    state_handle: tauri::State<'_, state::Handle>,
+    follow: bool,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::OnlyWorkbench, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetFollowPlayhead(follow))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn set_timeline_viewport_width(
+    state_handle: tauri::State<'_, state::Handle>,
+    width: f32,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::OnlyWorkbench, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetTimelineViewportWidth(width))
+                .ok();
+        }
+    }))
+}
+
 #[tauri::command]
 pub fn pan_timeline(
     state_handle: tauri::State<'_, state::Handle>,
@@ -1586,406 +2897,1118 @@ pub fn set_animation_looping(
 }
 
 #[tauri::command]
-pub fn apply_direction_preset(
+pub fn set_animation_start_delay(
     state_handle: tauri::State<'_, state::Handle>,
-    preset: dto::DirectionPreset,
+    start_delay_millis: u64,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::ApplyDirectionPreset(preset.into()))
+                .process_command(Command::SetAnimationStartDelay(Duration::from_millis(
+                    start_delay_millis,
+                )))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn select_direction(
+pub fn set_animation_origin(
     state_handle: tauri::State<'_, state::Handle>,
-    direction: dto::Direction,
+    origin: (i32, i32),
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetAnimationOrigin(origin.into()))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn nudge_animation_origin(
+    state_handle: tauri::State<'_, state::Handle>,
+    direction: dto::NudgeDirection,
+    large_nudge: bool,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::NudgeAnimationOrigin(direction.into(), large_nudge))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn extract_common_offset(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::ExtractCommonOffset).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn set_selected_animations_looping(
+    state_handle: tauri::State<'_, state::Handle>,
+    is_looping: bool,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetSelectedAnimationsLooping(is_looping))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn apply_direction_preset(
+    state_handle: tauri::State<'_, state::Handle>,
+    preset: dto::DirectionPreset,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::ApplyDirectionPreset(preset.into()))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn select_direction(
+    state_handle: tauri::State<'_, state::Handle>,
+    direction: dto::Direction,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SelectDirection(direction.into()))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn begin_drag_and_drop_frame(app: tauri::AppHandle, frame: PathBuf) -> Result<Patch, ()> {
+    app.begin_drag_and_drop_frame(frame)
+}
+
+#[tauri::command]
+pub fn drop_frame_on_timeline(
+    app: tauri::AppHandle,
+    direction: dto::Direction,
+    index: usize,
+) -> Result<Patch, ()> {
+    app.drop_frame_on_timeline(direction, index)
+}
+
+#[tauri::command]
+pub fn duplicate_selected_hitboxes(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.duplicate_selected_hitboxes()
+}
+
+#[tauri::command]
+pub fn subdivide_hitbox(
+    app: tauri::AppHandle,
+    name: String,
+    columns: u32,
+    rows: u32,
+) -> Result<Patch, ()> {
+    app.subdivide_hitbox(name, columns, rows)
+}
+
+#[tauri::command]
+pub fn prefix_hitbox_names(
+    state_handle: tauri::State<'_, state::Handle>,
+    prefix: String,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::PrefixHitboxNames(prefix))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn suffix_hitbox_names(
+    state_handle: tauri::State<'_, state::Handle>,
+    suffix: String,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SuffixHitboxNames(suffix))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn end_drag_and_drop_frame(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::EndDragAndDropFrame).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn delete_selected_keyframes(
+    state_handle: tauri::State<'_, state::Handle>,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::DeleteSelectedKeyframes)
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn move_keyframes_earlier(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::MoveSelectedKeyframesEarlier)
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn move_keyframes_later(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::MoveSelectedKeyframesLater)
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn set_keyframe_duration(app: tauri::AppHandle, duration_millis: u64) -> Result<Patch, ()> {
+    app.set_keyframe_duration(duration_millis)
+}
+
+#[tauri::command]
+pub fn set_keyframe_duration_in_frames(
+    state_handle: tauri::State<'_, state::Handle>,
+    frames: u32,
+    fps: f64,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetKeyframeDurationInFrames(frames, fps))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn set_keyframe_hold(
+    state_handle: tauri::State<'_, state::Handle>,
+    hold: u8,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetKeyframeHold(hold))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn merge_adjacent_duplicate_keyframes(
+    state_handle: tauri::State<'_, state::Handle>,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::MergeAdjacentDuplicateKeyframes)
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn quantize_keyframe_durations(
+    state_handle: tauri::State<'_, state::Handle>,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::QuantizeKeyframeDurations)
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn set_uniform_keyframe_duration(
+    state_handle: tauri::State<'_, state::Handle>,
+    duration_millis: u64,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetUniformKeyframeDuration(Duration::from_millis(
+                    duration_millis,
+                )))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn get_keyframe_durations_in_frames(
+    state_handle: tauri::State<'_, state::Handle>,
+    fps: f64,
+) -> Result<HashMap<dto::Direction, Vec<f64>>, ()> {
+    let state = state_handle.lock();
+    let document = state.current_document().ok_or(())?;
+    let durations = document.keyframe_durations_in_frames(fps).map_err(|_| ())?;
+    Ok(durations
+        .into_iter()
+        .map(|(direction, frames)| (direction.into(), frames))
+        .collect())
+}
+
+#[tauri::command]
+pub fn set_keyframe_offset_x(app: tauri::AppHandle, x: i32) -> Result<Patch, ()> {
+    app.set_keyframe_offset_x(x)
+}
+
+#[tauri::command]
+pub fn set_keyframe_offset_y(app: tauri::AppHandle, y: i32) -> Result<Patch, ()> {
+    app.set_keyframe_offset_y(y)
+}
+
+#[tauri::command]
+pub fn set_restore_session_on_startup(app: tauri::AppHandle, restore: bool) -> Result<Patch, ()> {
+    app.set_restore_session_on_startup(restore)
+}
+
+#[tauri::command]
+pub fn reset_keyframe_colors(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        state.set_keyframe_colors(Default::default());
+    }))
+}
+
+#[tauri::command]
+pub fn set_keyframe_colors(
+    app: tauri::AppHandle,
+    colors: dto::KeyframeColors,
+) -> Result<Patch, ()> {
+    app.set_keyframe_colors(colors)
+}
+
+#[tauri::command]
+pub fn set_save_on_focus_loss(app: tauri::AppHandle, save: bool) -> Result<Patch, ()> {
+    app.set_save_on_focus_loss(save)
+}
+
+#[tauri::command]
+pub fn set_autosave_interval(app: tauri::AppHandle, interval_secs: u64) -> Result<Patch, ()> {
+    app.set_autosave_interval(interval_secs)
+}
+
+#[tauri::command]
+pub fn recover_autosave(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.recover_autosave()
+}
+
+#[tauri::command]
+pub fn discard_autosave(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.discard_autosave()
+}
+
+#[tauri::command]
+pub fn add_keyframe_event(
+    state_handle: tauri::State<'_, state::Handle>,
+    name: String,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::AddKeyframeEvent(name))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn remove_keyframe_event(
+    state_handle: tauri::State<'_, state::Handle>,
+    name: String,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::RemoveKeyframeEvent(name))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn begin_drag_and_drop_keyframe(
+    state_handle: tauri::State<'_, state::Handle>,
+    direction: dto::Direction,
+    index: usize,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::BeginDragAndDropKeyframe(direction.into(), index))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn drop_keyframe_on_timeline(
+    state_handle: tauri::State<'_, state::Handle>,
+    direction: dto::Direction,
+    index: usize,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::DropKeyframeOnTimeline(direction.into(), index))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn end_drag_and_drop_keyframe(
+    state_handle: tauri::State<'_, state::Handle>,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::EndDragAndDropKeyframe)
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn begin_drag_keyframe_duration(
+    state_handle: tauri::State<'_, state::Handle>,
+    direction: dto::Direction,
+    index: usize,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::BeginDragKeyframeDuration(direction.into(), index))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn update_drag_keyframe_duration(
+    state_handle: tauri::State<'_, state::Handle>,
+    delta_millis: i64,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::OnlyWorkbench, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::UpdateDragKeyframeDuration(delta_millis))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn end_drag_keyframe_duration(
+    state_handle: tauri::State<'_, state::Handle>,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::EndDragKeyframeDuration())
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn begin_nudge_keyframe(
+    state_handle: tauri::State<'_, state::Handle>,
+    direction: dto::Direction,
+    index: usize,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::BeginNudgeKeyframe(direction.into(), index))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn update_nudge_keyframe(
+    state_handle: tauri::State<'_, state::Handle>,
+    displacement: (i32, i32),
+    both_axis: bool,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::OnlyWorkbench, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::UpdateNudgeKeyframe(displacement.into(), both_axis))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn end_nudge_keyframe(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::EndNudgeKeyframe()).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn create_hitbox(app: tauri::AppHandle, position: Option<(i32, i32)>) -> Result<Patch, ()> {
+    app.create_hitbox(position)
+}
+
+#[tauri::command]
+pub fn delete_hitbox(app: tauri::AppHandle, name: String) -> Result<Patch, ()> {
+    app.delete_hitbox(name)
+}
+
+#[tauri::command]
+pub fn delete_hitbox_from_all_keyframes(
+    app: tauri::AppHandle,
+    name: String,
+) -> Result<Patch, ()> {
+    app.delete_hitbox_from_all_keyframes(name)
+}
+
+#[tauri::command]
+pub fn delete_all_hitboxes_on_keyframe(
+    state_handle: tauri::State<'_, state::Handle>,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::DeleteAllHitboxesOnKeyframe)
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn delete_selected_hitboxes(
+    state_handle: tauri::State<'_, state::Handle>,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::DeleteSelectedHitboxes)
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn lock_hitboxes(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::LockHitboxes).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn unlock_hitboxes(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::UnlockHitboxes).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn set_hitbox_height(app: tauri::AppHandle, height: u32) -> Result<Patch, ()> {
+    app.set_hitbox_height(height)
+}
+
+#[tauri::command]
+pub fn match_hitbox_sizes(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::MatchHitboxSizes).ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn flip_hitboxes_horizontally(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.flip_hitboxes_horizontally()
+}
+
+#[tauri::command]
+pub fn set_hitbox_kind(app: tauri::AppHandle, kind: dto::HitboxKind) -> Result<Patch, ()> {
+    app.set_hitbox_kind(kind)
+}
+
+#[tauri::command]
+pub fn set_hitbox_width(app: tauri::AppHandle, width: u32) -> Result<Patch, ()> {
+    app.set_hitbox_width(width)
+}
+
+#[tauri::command]
+pub fn set_hitbox_position_x(app: tauri::AppHandle, x: i32) -> Result<Patch, ()> {
+    app.set_hitbox_position_x(x)
+}
+
+#[tauri::command]
+pub fn set_hitbox_position_y(app: tauri::AppHandle, y: i32) -> Result<Patch, ()> {
+    app.set_hitbox_position_y(y)
+}
+
+#[tauri::command]
+pub fn toggle_precise_durations(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.toggle_precise_durations()
+}
+
+#[tauri::command]
+pub fn toggle_preserve_aspect_ratio(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.toggle_preserve_aspect_ratio()
+}
+
+#[tauri::command]
+pub fn set_default_preserve_aspect_ratio(
+    state_handle: tauri::State<'_, state::Handle>,
+    preserve: bool,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetDefaultPreserveAspectRatio(preserve))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn trim_animation_ends(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.trim_animation_ends()
+}
+
+#[tauri::command]
+pub fn reset_keyframe_duration(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::ResetKeyframeDuration)
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn reset_keyframe_offsets(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::ResetSelectedKeyframeOffsets)
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn translate_selected_keyframes(
+    state_handle: tauri::State<'_, state::Handle>,
+    dx: i32,
+    dy: i32,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::TranslateSelectedKeyframes((dx, dy).into()))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn align_keyframe_offsets(app: tauri::AppHandle, align: dto::Align) -> Result<Patch, ()> {
+    app.align_keyframe_offsets(align)
+}
+
+#[tauri::command]
+pub fn begin_nudge_hitbox(
+    state_handle: tauri::State<'_, state::Handle>,
+    name: String,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::BeginNudgeHitbox(name))
+                .ok();
+        }
+    }))
+}
+
+#[tauri::command]
+pub fn update_nudge_hitbox(
+    state_handle: tauri::State<'_, state::Handle>,
+    displacement: (i32, i32),
+    both_axis: bool,
 ) -> Result<Patch, ()> {
-    Ok(state_handle.mutate(StateTrim::Full, |state| {
+    Ok(state_handle.mutate(StateTrim::OnlyWorkbench, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::SelectDirection(direction.into()))
+                .process_command(Command::UpdateNudgeHitbox(displacement.into(), both_axis))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn begin_drag_and_drop_frame(app: tauri::AppHandle, frame: PathBuf) -> Result<Patch, ()> {
-    app.begin_drag_and_drop_frame(frame)
-}
-
-#[tauri::command]
-pub fn drop_frame_on_timeline(
-    app: tauri::AppHandle,
-    direction: dto::Direction,
-    index: usize,
-) -> Result<Patch, ()> {
-    app.drop_frame_on_timeline(direction, index)
-}
-
-#[tauri::command]
-pub fn end_drag_and_drop_frame(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+pub fn end_nudge_hitbox(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::EndDragAndDropFrame).ok();
+            document.process_command(Command::EndNudgeHitbox).ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn delete_selected_keyframes(
+pub fn create_anchor(
     state_handle: tauri::State<'_, state::Handle>,
+    position: Option<(i32, i32)>,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::DeleteSelectedKeyframes)
+                .process_command(Command::CreateAnchor(position.map(|p| p.into())))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn set_keyframe_duration(app: tauri::AppHandle, duration_millis: u64) -> Result<Patch, ()> {
-    app.set_keyframe_duration(duration_millis)
-}
-
-#[tauri::command]
-pub fn set_keyframe_offset_x(app: tauri::AppHandle, x: i32) -> Result<Patch, ()> {
-    app.set_keyframe_offset_x(x)
-}
-
-#[tauri::command]
-pub fn set_keyframe_offset_y(app: tauri::AppHandle, y: i32) -> Result<Patch, ()> {
-    app.set_keyframe_offset_x(y)
-}
-
-#[tauri::command]
-pub fn begin_drag_and_drop_keyframe(
+pub fn set_anchor_position(
     state_handle: tauri::State<'_, state::Handle>,
-    direction: dto::Direction,
-    index: usize,
+    name: String,
+    position: (i32, i32),
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::BeginDragAndDropKeyframe(direction.into(), index))
+                .process_command(Command::SetAnchorPosition(name, position.into()))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn drop_keyframe_on_timeline(
+pub fn rename_anchor(
     state_handle: tauri::State<'_, state::Handle>,
-    direction: dto::Direction,
-    index: usize,
+    old_name: String,
+    new_name: String,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::DropKeyframeOnTimeline(direction.into(), index))
+                .process_command(Command::RenameAnchor(old_name, new_name))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn end_drag_and_drop_keyframe(
+pub fn delete_anchor(
     state_handle: tauri::State<'_, state::Handle>,
+    name: String,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document
-                .process_command(Command::EndDragAndDropKeyframe)
-                .ok();
+            document.process_command(Command::DeleteAnchor(name)).ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn begin_drag_keyframe_duration(
+pub fn begin_nudge_anchor(
     state_handle: tauri::State<'_, state::Handle>,
-    direction: dto::Direction,
-    index: usize,
+    name: String,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::BeginDragKeyframeDuration(direction.into(), index))
+                .process_command(Command::BeginNudgeAnchor(name))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn update_drag_keyframe_duration(
+pub fn update_nudge_anchor(
     state_handle: tauri::State<'_, state::Handle>,
-    delta_millis: i64,
+    displacement: (i32, i32),
+    both_axis: bool,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::OnlyWorkbench, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::UpdateDragKeyframeDuration(delta_millis))
+                .process_command(Command::UpdateNudgeAnchor(displacement.into(), both_axis))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn end_drag_keyframe_duration(
-    state_handle: tauri::State<'_, state::Handle>,
-) -> Result<Patch, ()> {
+pub fn end_nudge_anchor(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document
-                .process_command(Command::EndDragKeyframeDuration())
-                .ok();
+            document.process_command(Command::EndNudgeAnchor).ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn begin_nudge_keyframe(
+pub fn begin_resize_hitbox(
     state_handle: tauri::State<'_, state::Handle>,
-    direction: dto::Direction,
-    index: usize,
+    name: String,
+    axis: dto::ResizeAxis,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::BeginNudgeKeyframe(direction.into(), index))
+                .process_command(Command::BeginResizeHitbox(name, axis.into()))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn update_nudge_keyframe(
+pub fn update_resize_hitbox(
     state_handle: tauri::State<'_, state::Handle>,
     displacement: (i32, i32),
-    both_axis: bool,
+    preserve_aspect_ratio: bool,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::OnlyWorkbench, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::UpdateNudgeKeyframe(displacement.into(), both_axis))
+                .process_command(Command::UpdateResizeHitbox(
+                    displacement.into(),
+                    preserve_aspect_ratio,
+                ))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn end_nudge_keyframe(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+pub fn end_resize_hitbox(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::EndNudgeKeyframe()).ok();
+            document.process_command(Command::EndResizeHitbox).ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn create_hitbox(app: tauri::AppHandle, position: Option<(i32, i32)>) -> Result<Patch, ()> {
-    app.create_hitbox(position)
+pub async fn export(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.export().await
 }
 
 #[tauri::command]
-pub fn delete_hitbox(app: tauri::AppHandle, name: String) -> Result<Patch, ()> {
-    app.delete_hitbox(name)
+pub async fn export_and_reveal(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.export_and_reveal().await
 }
 
 #[tauri::command]
-pub fn delete_selected_hitboxes(
+pub fn export_command_history(app: tauri::AppHandle, redact_paths: bool) -> Result<String, String> {
+    app.export_command_history(redact_paths)
+}
+
+#[tauri::command]
+pub async fn export_contact_sheet(app: tauri::AppHandle, path: PathBuf) -> Result<Patch, ()> {
+    app.export_contact_sheet(path).await
+}
+
+#[tauri::command]
+pub async fn get_template_context_preview(app: tauri::AppHandle) -> Result<String, String> {
+    app.get_template_context_preview().await
+}
+
+#[tauri::command]
+pub async fn was_export_changed(app: tauri::AppHandle) -> Result<bool, String> {
+    app.was_export_changed().await
+}
+
+#[tauri::command]
+pub fn begin_export_as(app: tauri::AppHandle) -> Result<Patch, ()> {
+    app.begin_export_as()
+}
+
+#[tauri::command]
+pub fn set_export_settings(
+    app: tauri::AppHandle,
+    settings: dto::ExportSettings,
+) -> Result<Patch, String> {
+    app.set_export_settings(settings)
+}
+
+#[tauri::command]
+pub fn set_export_template_file(app: tauri::AppHandle, file: PathBuf) -> Result<Patch, ()> {
+    app.set_export_template_file(file)
+}
+
+#[tauri::command]
+pub fn set_export_texture_file(
     state_handle: tauri::State<'_, state::Handle>,
+    file: PathBuf,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::DeleteSelectedHitboxes)
+                .process_command(Command::SetExportTextureFile(file))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn lock_hitboxes(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+pub fn set_export_metadata_file(
+    state_handle: tauri::State<'_, state::Handle>,
+    file: PathBuf,
+) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::LockHitboxes).ok();
+            document
+                .process_command(Command::SetExportMetadataFile(file))
+                .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn unlock_hitboxes(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+pub fn set_export_metadata_paths_root(
+    state_handle: tauri::State<'_, state::Handle>,
+    directory: PathBuf,
+) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::UnlockHitboxes).ok();
+            document
+                .process_command(Command::SetExportMetadataPathsRoot(directory))
+                .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn set_hitbox_height(app: tauri::AppHandle, height: u32) -> Result<Patch, ()> {
-    app.set_hitbox_height(height)
-}
-
-#[tauri::command]
-pub fn set_hitbox_width(app: tauri::AppHandle, width: u32) -> Result<Patch, ()> {
-    app.set_hitbox_width(width)
+pub fn set_export_format(
+    state_handle: tauri::State<'_, state::Handle>,
+    format: dto::ExportFormat,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetExportFormat(format.into()))
+                .ok();
+        }
+    }))
 }
 
 #[tauri::command]
-pub fn set_hitbox_position_x(app: tauri::AppHandle, x: i32) -> Result<Patch, ()> {
-    app.set_hitbox_position_x(x)
+pub fn set_export_texture_format(
+    state_handle: tauri::State<'_, state::Handle>,
+    format: dto::TextureFormat,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetExportTextureFormat(format.into()))
+                .ok();
+        }
+    }))
 }
 
 #[tauri::command]
-pub fn set_hitbox_position_y(app: tauri::AppHandle, y: i32) -> Result<Patch, ()> {
-    app.set_hitbox_position_y(y)
+pub fn set_export_texture_scale(
+    state_handle: tauri::State<'_, state::Handle>,
+    scale: u32,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetExportTextureScale(scale))
+                .ok();
+        }
+    }))
 }
 
 #[tauri::command]
-pub fn toggle_preserve_aspect_ratio(app: tauri::AppHandle) -> Result<Patch, ()> {
-    app.toggle_preserve_aspect_ratio()
+pub fn add_export_target(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document.process_command(Command::AddExportTarget).ok();
+        }
+    }))
 }
 
 #[tauri::command]
-pub fn begin_nudge_hitbox(
+pub fn remove_export_target(
     state_handle: tauri::State<'_, state::Handle>,
-    name: String,
+    index: usize,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::BeginNudgeHitbox(name))
+                .process_command(Command::RemoveExportTarget(index))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn update_nudge_hitbox(
+pub fn set_export_target_template_file(
     state_handle: tauri::State<'_, state::Handle>,
-    displacement: (i32, i32),
-    both_axis: bool,
+    index: usize,
+    file: PathBuf,
 ) -> Result<Patch, ()> {
-    Ok(state_handle.mutate(StateTrim::OnlyWorkbench, |state| {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::UpdateNudgeHitbox(displacement.into(), both_axis))
+                .process_command(Command::SetExportTargetTemplateFile(index, file))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn end_nudge_hitbox(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+pub fn set_export_target_metadata_file(
+    state_handle: tauri::State<'_, state::Handle>,
+    index: usize,
+    file: PathBuf,
+) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::EndNudgeHitbox).ok();
+            document
+                .process_command(Command::SetExportTargetMetadataFile(index, file))
+                .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn begin_resize_hitbox(
+pub fn set_packing_algorithm(
     state_handle: tauri::State<'_, state::Handle>,
-    name: String,
-    axis: dto::ResizeAxis,
+    algorithm: dto::PackingAlgorithm,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::BeginResizeHitbox(name, axis.into()))
+                .process_command(Command::SetPackingAlgorithm(algorithm.into()))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn update_resize_hitbox(
+pub fn set_packing_order(
     state_handle: tauri::State<'_, state::Handle>,
-    displacement: (i32, i32),
-    preserve_aspect_ratio: bool,
+    order: dto::PackingOrder,
 ) -> Result<Patch, ()> {
-    Ok(state_handle.mutate(StateTrim::OnlyWorkbench, |state| {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::UpdateResizeHitbox(
-                    displacement.into(),
-                    preserve_aspect_ratio,
-                ))
+                .process_command(Command::SetPackingOrder(order.into()))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn end_resize_hitbox(state_handle: tauri::State<'_, state::Handle>) -> Result<Patch, ()> {
+pub fn set_export_path_style(
+    state_handle: tauri::State<'_, state::Handle>,
+    style: dto::PathStyle,
+) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
-            document.process_command(Command::EndResizeHitbox).ok();
+            document
+                .process_command(Command::SetExportPathStyle(style.into()))
+                .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub async fn export(app: tauri::AppHandle) -> Result<Patch, ()> {
-    app.export().await
-}
-
-#[tauri::command]
-pub fn begin_export_as(app: tauri::AppHandle) -> Result<Patch, ()> {
-    app.begin_export_as()
-}
-
-#[tauri::command]
-pub fn set_export_template_file(app: tauri::AppHandle, file: PathBuf) -> Result<Patch, ()> {
-    app.set_export_template_file(file)
+pub fn set_export_allow_rotation(
+    state_handle: tauri::State<'_, state::Handle>,
+    allow_rotation: bool,
+) -> Result<Patch, ()> {
+    Ok(state_handle.mutate(StateTrim::Full, |state| {
+        if let Some(document) = state.current_document_mut() {
+            document
+                .process_command(Command::SetExportAllowRotation(allow_rotation))
+                .ok();
+        }
+    }))
 }
 
 #[tauri::command]
-pub fn set_export_texture_file(
+pub fn set_export_mip_safe_padding(
     state_handle: tauri::State<'_, state::Handle>,
-    file: PathBuf,
+    padding: u32,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::SetExportTextureFile(file))
+                .process_command(Command::SetExportMipSafePadding(padding))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn set_export_metadata_file(
+pub fn set_export_deduplicate_frames(
     state_handle: tauri::State<'_, state::Handle>,
-    file: PathBuf,
+    deduplicate: bool,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::SetExportMetadataFile(file))
+                .process_command(Command::SetExportDeduplicateFrames(deduplicate))
                 .ok();
         }
     }))
 }
 
 #[tauri::command]
-pub fn set_export_metadata_paths_root(
+pub fn set_export_write_manifest(
     state_handle: tauri::State<'_, state::Handle>,
-    directory: PathBuf,
+    write_manifest: bool,
 ) -> Result<Patch, ()> {
     Ok(state_handle.mutate(StateTrim::Full, |state| {
         if let Some(document) = state.current_document_mut() {
             document
-                .process_command(Command::SetExportMetadataPathsRoot(directory))
+                .process_command(Command::SetExportWriteManifest(write_manifest))
                 .ok();
         }
     }))
@@ -0,0 +1,121 @@
+use log::{error, info};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::document::{Document, DocumentError};
+use crate::export::{export_sheet, ExportError};
+use crate::features::texture_cache;
+use crate::sheet::{Absolute, Sheet};
+
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("Missing value after `{0}`")]
+    MissingArgumentValue(String),
+    #[error("{0}")]
+    DocumentError(#[from] DocumentError),
+    #[error("{0}")]
+    ExportError(#[from] ExportError),
+}
+
+// Returns an exit code when the arguments request a headless operation, in which case
+// the GUI should not be launched.
+pub fn run<I: IntoIterator<Item = String>>(args: I) -> Option<i32> {
+    let documents = match parse_export_arguments(args) {
+        Ok(documents) => documents,
+        Err(e) => {
+            error!("{e}");
+            return Some(1);
+        }
+    };
+
+    if documents.is_empty() {
+        return None;
+    }
+
+    let mut exit_code = 0;
+    for document in documents {
+        match export_document(&document) {
+            Ok(_) => info!("Exported `{}`", document.to_string_lossy()),
+            Err(e) => {
+                error!(
+                    "Error while exporting `{}`: {e}",
+                    document.to_string_lossy()
+                );
+                exit_code = 1;
+            }
+        }
+    }
+    Some(exit_code)
+}
+
+pub fn export_document<P: AsRef<Path>>(path: P) -> Result<(), CliError> {
+    let document = Document::open(path)?;
+    Ok(export_sheet_headless(document.sheet())?)
+}
+
+fn export_sheet_headless(sheet: &Sheet<Absolute>) -> Result<(), ExportError> {
+    let texture_cache = texture_cache::Handle::default();
+    texture_cache.preload(
+        &sheet
+            .frames_iter()
+            .map(|f| f.source().to_owned())
+            .collect::<HashSet<_>>(),
+    );
+    export_sheet(sheet, texture_cache)
+}
+
+fn parse_export_arguments<I: IntoIterator<Item = String>>(
+    args: I,
+) -> Result<Vec<PathBuf>, CliError> {
+    let mut documents = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--export" {
+            let document = args
+                .next()
+                .ok_or_else(|| CliError::MissingArgumentValue(arg.clone()))?;
+            documents.push(PathBuf::from(document));
+        }
+    }
+    Ok(documents)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::sheet::ExportSettings;
+
+    #[test]
+    fn can_parse_export_arguments() {
+        let args = ["--export", "a.tiger", "--export", "b.tiger"].map(String::from);
+        assert_eq!(
+            parse_export_arguments(args).unwrap(),
+            vec![PathBuf::from("a.tiger"), PathBuf::from("b.tiger")]
+        );
+        assert!(parse_export_arguments(["--export".to_owned()]).is_err());
+        assert!(parse_export_arguments(Vec::<String>::new())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn can_export_without_gui() {
+        let document = Document::open("test-data/samurai.tiger").unwrap();
+        let mut sheet = document.sheet().clone();
+        let Some(ExportSettings::Template(mut export_settings)) = sheet.export_settings().clone()
+        else {
+            panic!("Missing export settings");
+        };
+        let output_directory = PathBuf::from("test-output/can_export_without_gui");
+        std::fs::remove_dir_all(&output_directory).ok();
+        export_settings.set_texture_file(output_directory.join("samurai.png"));
+        export_settings.set_metadata_file(output_directory.join("samurai.export"));
+        sheet.set_export_settings(ExportSettings::Template(export_settings));
+
+        export_sheet_headless(&sheet).unwrap();
+        assert!(output_directory.join("samurai.png").exists());
+        assert!(output_directory.join("samurai.export").exists());
+    }
+}
@@ -45,10 +45,14 @@ pub struct Persistent {
     pub(super) disk_version: Option<i32>,
     pub(super) close_requested: bool,
     pub(super) timeline_is_playing: bool,
+    pub(super) timeline_viewport_width: f32,
     pub(super) export_settings_edit: Option<ExportSettings<Any>>,
     pub(super) relocate_frames_edit: Option<HashMap<PathBuf, PathBuf>>,
     pub(super) preserve_aspect_ratio: bool,
+    pub(super) default_preserve_aspect_ratio: bool,
+    pub(super) precise_durations: bool,
     pub(super) missing_textures: HashSet<PathBuf>,
+    pub(super) recoverable_autosave: Option<Sheet<Absolute>>,
 }
 
 #[derive(Error, Debug)]
@@ -83,14 +87,34 @@ pub enum DocumentError {
     MissingHitboxPositionData,
     #[error("Not currently nudging a hitbox")]
     NotNudgingHitbox,
+    #[error("Not currently nudging an anchor")]
+    NotNudgingAnchor,
     #[error("Not currently resizing a hitbox")]
     NotResizingHitbox,
     #[error("Not currently adjusting export settings")]
     NotEditingExportSettings,
+    #[error("Export settings are not valid")]
+    InvalidExportSettings,
     #[error("Not currently relocating frames")]
     NotRelocatingFrames,
+    #[error("There is no autosave to recover")]
+    NoAutosaveToRecover,
+    #[error("Frame `{0}` does not exist")]
+    FrameNotInDocument(PathBuf),
+    #[error("Frame rate must be greater than zero")]
+    InvalidFrameRate,
+    #[error("Clipboard does not contain keyframes")]
+    ClipboardHasNoKeyframes,
+    #[error("Clipboard does not contain hitboxes")]
+    ClipboardHasNoHitboxes,
     #[error("Sequence in animation has no keyframes")]
     SequenceHasNoKeyframes,
+    #[error("Cannot change history while an edit is in progress")]
+    EditInProgress,
+    #[error("Hitbox is too small to be split into {0} columns and {1} rows")]
+    InvalidHitboxSubdivision(u32, u32),
+    #[error("Some animations could not be renamed because their new name is already taken: {}", .0.join(", "))]
+    AnimationNameCollisions(Vec<String>),
 }
 
 pub type DocumentResult<T> = Result<T, DocumentError>;
@@ -163,6 +187,15 @@ impl Document {
         self.persistent.close_requested
     }
 
+    // Recovering the autosave is left to the user, who is prompted after opening the document
+    pub fn offer_autosave_recovery(&mut self, sheet: Sheet<Absolute>) {
+        self.persistent.recoverable_autosave = Some(sheet);
+    }
+
+    pub fn has_recoverable_autosave(&self) -> bool {
+        self.persistent.recoverable_autosave.is_some()
+    }
+
     pub fn should_close(&self) -> bool {
         self.close_requested() && self.is_saved()
     }
@@ -175,6 +208,17 @@ impl Document {
         self.persistent.preserve_aspect_ratio
     }
 
+    // When enabled, dragging hitbox corners preserves their aspect ratio unless shift is held
+    pub fn preserves_aspect_ratio_by_default(&self) -> bool {
+        self.persistent.default_preserve_aspect_ratio
+    }
+
+    // When enabled, keyframe durations are edited with microsecond precision
+    // instead of being rounded to whole milliseconds.
+    pub fn uses_precise_durations(&self) -> bool {
+        self.persistent.precise_durations
+    }
+
     fn sanitize_view(&mut self) {
         match self.workbench_animation() {
             Ok((_, animation)) => {
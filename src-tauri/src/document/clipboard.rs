@@ -71,6 +71,40 @@ impl Document {
         Some(Clipboard::Keyframes(keyframes))
     }
 
+    pub(super) fn paste_after_selection(&mut self, clipboard: Clipboard) -> DocumentResult<()> {
+        match (clipboard.manifest(), clipboard) {
+            (ClipboardManifest::Keyframes, Clipboard::Keyframes(keyframes)) => {
+                let (direction, sequence) = self.workbench_sequence()?;
+                let (animation_name, _) = self.workbench_animation()?;
+                let index = self
+                    .view
+                    .selection
+                    .keyframes()
+                    .filter(|(a, d, _)| a == animation_name && *d == direction)
+                    .map(|(_, _, i)| i + 1)
+                    .max()
+                    .unwrap_or_else(|| sequence.num_keyframes())
+                    .min(sequence.num_keyframes());
+                self.insert_keyframes(keyframes, index)
+            }
+            (_, clipboard) => self.paste(clipboard),
+        }
+    }
+
+    pub(super) fn paste_properties(&mut self, clipboard: Clipboard) -> DocumentResult<()> {
+        let source = match (clipboard.manifest(), clipboard) {
+            (ClipboardManifest::Keyframes, Clipboard::Keyframes(keyframes)) => keyframes
+                .into_iter()
+                .next()
+                .ok_or(DocumentError::ClipboardHasNoKeyframes)?,
+            _ => return Err(DocumentError::ClipboardHasNoKeyframes),
+        };
+        for (_, _, keyframe) in self.selected_keyframes_mut()? {
+            keyframe.copy_properties_from(&source);
+        }
+        Ok(())
+    }
+
     fn paste_keyframes(&mut self, keyframes: Vec<Keyframe<Absolute>>) -> DocumentResult<()> {
         let (_, sequence) = self.workbench_sequence()?;
         let at_sequence_end = self.timeline_clock() >= sequence.duration().unwrap_or_default();
@@ -81,6 +115,14 @@ impl Document {
                 .map(|((_, index), _)| index)
                 .unwrap_or_default()
         };
+        self.insert_keyframes(keyframes, index)
+    }
+
+    fn insert_keyframes(
+        &mut self,
+        keyframes: Vec<Keyframe<Absolute>>,
+        index: usize,
+    ) -> DocumentResult<()> {
         let (animation_name, _) = self.workbench_animation_mut()?;
         let num_keyframes = keyframes.len();
         let (direction, sequence) = self.workbench_sequence_mut()?;
@@ -104,6 +146,32 @@ impl Document {
         Some(Clipboard::Hitboxes(hitboxes))
     }
 
+    // Unlike `copy`, this ignores the selection and always copies every hitbox
+    // of the keyframe displayed in the workbench.
+    pub fn copy_keyframe_hitboxes(&self) -> Option<Clipboard> {
+        let (_, keyframe) = self.workbench_keyframe().ok()?;
+        let hitboxes = keyframe
+            .hitboxes_iter()
+            .map(|(name, hitbox)| (name.clone(), hitbox.clone()))
+            .collect::<HashMap<String, Hitbox>>();
+        Some(Clipboard::Hitboxes(hitboxes))
+    }
+
+    pub(super) fn paste_keyframe_hitboxes(&mut self, clipboard: Clipboard) -> DocumentResult<()> {
+        let hitboxes = match clipboard {
+            Clipboard::Hitboxes(hitboxes) => hitboxes,
+            _ => return Err(DocumentError::ClipboardHasNoHitboxes),
+        };
+        for (_, _, keyframe) in self.selected_keyframes_mut()? {
+            keyframe.delete_all_hitboxes();
+            for (name, hitbox) in &hitboxes {
+                let (_, new_hitbox) = keyframe.create_hitbox(name);
+                *new_hitbox = hitbox.duplicate();
+            }
+        }
+        Ok(())
+    }
+
     fn paste_hitboxes(&mut self, hitboxes: HashMap<String, Hitbox>) -> DocumentResult<()> {
         let (animation_name, _) = self.workbench_animation_mut()?;
         let ((direction, index), keyframe) = self.workbench_keyframe_mut()?;
@@ -141,6 +209,8 @@ pub fn clipboard_manifest<S: AsRef<str>>(clipboard_content: S) -> Option<Clipboa
 #[cfg(test)]
 mod test {
 
+    use euclid::vec2;
+
     use super::*;
 
     #[test]
@@ -188,6 +258,105 @@ mod test {
             .is_some());
     }
 
+    #[test]
+    fn can_paste_keyframes_after_selection() {
+        let mut document = Document::new("tmp");
+
+        document.sheet.add_test_animation(
+            "animation",
+            HashMap::from([(Direction::East, vec!["frame_0.png", "frame_1.png"])]),
+        );
+
+        document.edit_animation("animation").unwrap();
+        document.select_keyframes_only([
+            ("animation".to_owned(), Direction::East, 0),
+            ("animation".to_owned(), Direction::East, 1),
+        ]);
+        let clipboard = document.copy().unwrap();
+
+        document.paste_after_selection(clipboard).unwrap();
+
+        let frames = document
+            .sheet
+            .sequence("animation", Direction::East)
+            .keyframes_iter()
+            .map(|k| k.frame().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            frames,
+            vec![
+                PathBuf::from("frame_0.png"),
+                PathBuf::from("frame_1.png"),
+                PathBuf::from("frame_0.png"),
+                PathBuf::from("frame_1.png"),
+            ]
+        );
+        assert!(document
+            .view
+            .selection
+            .is_keyframe_selected("animation", Direction::East, 2));
+        assert!(document
+            .view
+            .selection
+            .is_keyframe_selected("animation", Direction::East, 3));
+    }
+
+    #[test]
+    fn can_paste_keyframe_properties() {
+        let mut document = Document::new("tmp");
+
+        document.sheet.add_test_animation(
+            "animation",
+            HashMap::from([(
+                Direction::East,
+                vec!["frame_0.png", "frame_1.png", "frame_2.png"],
+            )]),
+        );
+
+        let keyframe = document.sheet.keyframe_mut("animation", Direction::East, 0);
+        keyframe.set_duration_millis(250);
+        keyframe.set_offset(vec2(4, -2));
+        keyframe.create_hitbox("hitbox");
+
+        document.edit_animation("animation").unwrap();
+        document.select_keyframe_only("animation".to_owned(), Direction::East, 0);
+        let clipboard = document.copy().unwrap();
+
+        document
+            .process_command(Command::SelectKeyframe(Direction::East, 1, false, false))
+            .unwrap();
+        document
+            .process_command(Command::SelectKeyframe(Direction::East, 2, false, true))
+            .unwrap();
+        document
+            .process_command(Command::PasteProperties(clipboard))
+            .unwrap();
+
+        for (index, frame) in [(1, "frame_1.png"), (2, "frame_2.png")] {
+            let keyframe = document.sheet.keyframe("animation", Direction::East, index);
+            assert_eq!(keyframe.duration_millis(), 250);
+            assert_eq!(keyframe.offset(), vec2(4, -2));
+            assert!(keyframe.has_hitbox("hitbox"));
+            assert_eq!(keyframe.frame(), Path::new(frame));
+        }
+
+        document.process_command(Command::Undo).unwrap();
+        assert_eq!(
+            document
+                .sheet
+                .keyframe("animation", Direction::East, 1)
+                .duration_millis(),
+            100
+        );
+        assert_eq!(
+            document
+                .sheet
+                .keyframe("animation", Direction::East, 2)
+                .duration_millis(),
+            100
+        );
+    }
+
     #[test]
     fn can_copy_paste_hitbox() {
         let mut document = Document::new("tmp");
@@ -225,4 +394,44 @@ mod test {
             "hitbox"
         ));
     }
+
+    #[test]
+    fn can_copy_paste_keyframe_hitboxes() {
+        let mut document = Document::new("tmp");
+
+        document.sheet.add_test_animation(
+            "animation",
+            HashMap::from([
+                (Direction::East, vec!["frame_0.png"]),
+                (Direction::West, vec!["frame_1.png"]),
+            ]),
+        );
+
+        let keyframe = document.sheet.keyframe_mut("animation", Direction::East, 0);
+        keyframe
+            .create_hitbox("hurtbox")
+            .1
+            .set_position(vec2(-4, 8));
+        keyframe.create_hitbox("hitbox").1.set_size(vec2(12, 6));
+        document
+            .sheet
+            .keyframe_mut("animation", Direction::West, 0)
+            .create_hitbox("stale");
+
+        document.edit_animation("animation").unwrap();
+        document.select_direction(Direction::East).unwrap();
+        let clipboard = document.copy_keyframe_hitboxes().unwrap();
+        let serialized = serde_json::to_string_pretty(&clipboard).unwrap();
+        let clipboard = serde_json::from_str::<Clipboard>(&serialized).unwrap();
+
+        document.select_keyframe_only("animation".to_owned(), Direction::West, 0);
+        document
+            .process_command(Command::PasteKeyframeHitboxes(clipboard))
+            .unwrap();
+
+        let source = document.sheet.keyframe("animation", Direction::East, 0);
+        let destination = document.sheet.keyframe("animation", Direction::West, 0);
+        assert_eq!(source.sorted_hitboxes(), destination.sorted_hitboxes());
+        assert!(!destination.has_hitbox("stale"));
+    }
 }
@@ -1,17 +1,27 @@
 use euclid::default::Vector2D;
 use log::error;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 use std::{path::PathBuf, time::Duration};
 
 use crate::document::*;
-use crate::sheet::{Direction, DirectionPreset, Sheet};
+use crate::import::ImportedSheet;
+use crate::sheet::{
+    Direction, DirectionPreset, ExportFormat, HitboxKind, PackingAlgorithm, PackingOrder,
+    PathStyle, Sheet, TextureFormat,
+};
 
 #[derive(Clone, Debug)]
 pub enum Command {
     Undo,
     Redo,
+    ClearHistory,
     DetachedNavigation,
     Paste(Clipboard),
+    PasteAfterSelection(Clipboard),
+    PasteProperties(Clipboard),
+    PasteKeyframeHitboxes(Clipboard),
     SetFramesListMode(ListMode),
     SetFramesListOffset(u32),
     FilterFrames(String),
@@ -19,10 +29,21 @@ pub enum Command {
     SetAnimationsListOffset(u32),
     SetHitboxesListOffset(u32),
     ImportFrames(Vec<PathBuf>),
+    ImportFramesIntoAnimation(Vec<PathBuf>),
+    ImportAseprite(ImportedSheet),
+    ImportAtlasRegions(Vec<PathBuf>),
+    ImportSpriteStrip(Vec<PathBuf>),
+    RecoverAutosave,
+    DiscardAutosave,
+    RepairSheet,
     BeginRelocateFrames,
     RelocateFrame(PathBuf, PathBuf),
     CancelRelocateFrames,
     EndRelocateFrames,
+    RelocateFramesRoot(PathBuf, PathBuf),
+    RenameFrameFile(PathBuf, PathBuf),
+    ReplaceFrameSource(PathBuf, PathBuf),
+    SetFrameNormalMap(PathBuf, Option<PathBuf>),
     DeleteFrame(PathBuf),
     DeleteSelectedFrames,
     DeleteSelection,
@@ -32,27 +53,43 @@ pub enum Command {
     BrowseToStart(bool),
     ClearSelection,
     SelectAll,
+    SelectNextAnimation,
+    SelectPreviousAnimation,
+    InvertHitboxSelection,
+    SelectKeyframesInRange(Duration, Duration),
     SelectFrame(PathBuf, bool, bool),
     SelectAnimation(String, bool, bool),
     SelectKeyframe(Direction, usize, bool, bool),
     SelectHitbox(String, bool, bool),
     Pan(Vector2D<f32>),
     CenterWorkbench,
+    FitWorkbenchToItem(HashMap<PathBuf, Vector2D<u32>>, Vector2D<f32>),
     ZoomInWorkbench,
     ZoomInWorkbenchAround(Vector2D<f32>),
     ZoomOutWorkbench,
     ZoomOutWorkbenchAround(Vector2D<f32>),
     SetWorkbenchZoomFactor(u32),
     ResetWorkbenchZoom,
+    AddWorkbenchGuide(WorkbenchGuide),
+    RemoveWorkbenchGuide(usize),
+    MoveWorkbenchGuide(usize, i32),
+    ClearWorkbenchGuides,
+    SetGridSpacing(u32),
+    SetPanButton(PanButton),
+    SetGridSubdivisions(u32),
+    SetOriginOffset(Vector2D<i32>),
     EnableSpriteDarkening,
     DisableSpriteDarkening,
     HideSprite,
     ShowSprite,
     HideHitboxes,
     ShowHitboxes,
+    ToggleHitboxVisibility(String),
+    ShowAllHitboxes,
     HideOrigin,
     ShowOrigin,
     CreateAnimation,
+    CreateAnimationFromSelectedFrames,
     EditAnimation(String),
     BeginRenameSelection,
     BeginRenameAnimation(String),
@@ -61,6 +98,11 @@ pub enum Command {
     EndRenameAnimation(String),
     EndRenameHitbox(String),
     DeleteAnimation(String),
+    DeleteAnimationsNotMatching(String),
+    NormalizeAnimationNames(Case),
+    SwapAnimationNames(String, String),
+    ReorderAnimations(Vec<String>),
+    SetAnimationFrame(PathBuf),
     DeleteSelectedAnimations,
     Tick(Duration),
     Play,
@@ -70,10 +112,12 @@ pub enum Command {
     JumpToAnimationEnd,
     JumpToPreviousFrame,
     JumpToNextFrame,
+    JumpToKeyframe(usize),
     SetSnapKeyframeDurations(bool),
     SetSnapKeyframeToOtherKeyframes(bool),
     SetSnapKeyframeToMultiplesOfDuration(bool),
     SetKeyframeSnappingBaseDuration(Duration),
+    SetMinKeyframeDuration(Duration),
     ZoomInTimeline,
     ZoomInTimelineAround(Duration),
     ZoomOutTimeline,
@@ -81,17 +125,39 @@ pub enum Command {
     SetTimelineZoomAmount(f32),
     SetTimelineOffset(Duration),
     PanTimeline(f32),
+    SetFollowPlayhead(bool),
+    SetTimelineViewportWidth(f32),
     ResetTimelineZoom,
+    ResetView,
     SetAnimationLooping(bool),
+    SetSelectedAnimationsLooping(bool),
+    SetAnimationStartDelay(Duration),
+    SetAnimationOrigin(Vector2D<i32>),
+    NudgeAnimationOrigin(NudgeDirection, bool),
+    ExtractCommonOffset,
     ApplyDirectionPreset(DirectionPreset),
     SelectDirection(Direction),
     BeginDragAndDropFrame(PathBuf),
     DropFrameOnTimeline(Direction, usize),
     EndDragAndDropFrame,
     DeleteSelectedKeyframes,
+    MoveSelectedKeyframesEarlier,
+    MoveSelectedKeyframesLater,
+    TrimAnimationEnds(HashSet<PathBuf>),
+    MergeAdjacentDuplicateKeyframes,
+    ResetKeyframeDuration,
+    ResetSelectedKeyframeOffsets,
+    AlignKeyframeOffsets(Align, HashMap<PathBuf, Vector2D<u32>>),
     SetKeyframeDuration(Duration),
+    SetKeyframeDurationInFrames(u32, f64),
+    SetKeyframeHold(u8),
+    QuantizeKeyframeDurations,
+    SetUniformKeyframeDuration(Duration),
     SetKeyframeOffsetX(i32),
     SetKeyframeOffsetY(i32),
+    TranslateSelectedKeyframes(Vector2D<i32>),
+    AddKeyframeEvent(String),
+    RemoveKeyframeEvent(String),
     BeginDragAndDropKeyframe(Direction, usize),
     DropKeyframeOnTimeline(Direction, usize),
     EndDragAndDropKeyframe,
@@ -103,17 +169,35 @@ pub enum Command {
     EndNudgeKeyframe(),
     CreateHitbox(Option<Vector2D<i32>>),
     DeleteHitbox(String),
+    DeleteHitboxFromAllKeyframes(String),
     DeleteSelectedHitboxes,
+    DeleteAllHitboxesOnKeyframe,
+    DuplicateSelectedHitboxes,
+    SubdivideHitbox(String, u32, u32),
+    PrefixHitboxNames(String),
+    SuffixHitboxNames(String),
     LockHitboxes,
     UnlockHitboxes,
     SetHitboxPositionX(i32),
     SetHitboxPositionY(i32),
     SetHitboxWidth(u32),
     SetHitboxHeight(u32),
+    MatchHitboxSizes,
+    FlipHitboxesHorizontally(HashMap<PathBuf, Vector2D<u32>>),
+    SetHitboxKind(HitboxKind),
     TogglePreserveAspectRatio,
+    TogglePreciseDurations,
+    SetDefaultPreserveAspectRatio(bool),
     BeginNudgeHitbox(String),
     UpdateNudgeHitbox(Vector2D<i32>, bool),
     EndNudgeHitbox,
+    CreateAnchor(Option<Vector2D<i32>>),
+    SetAnchorPosition(String, Vector2D<i32>),
+    RenameAnchor(String, String),
+    DeleteAnchor(String),
+    BeginNudgeAnchor(String),
+    UpdateNudgeAnchor(Vector2D<i32>, bool),
+    EndNudgeAnchor,
     BeginResizeHitbox(String, ResizeAxis),
     UpdateResizeHitbox(Vector2D<i32>, bool),
     EndResizeHitbox,
@@ -122,8 +206,31 @@ pub enum Command {
     SetExportTextureFile(PathBuf),
     SetExportMetadataFile(PathBuf),
     SetExportMetadataPathsRoot(PathBuf),
+    SetExportFormat(ExportFormat),
+    SetExportTextureFormat(TextureFormat),
+    SetExportTextureScale(u32),
+    SetPackingAlgorithm(PackingAlgorithm),
+    SetPackingOrder(PackingOrder),
+    SetExportPathStyle(PathStyle),
+    SetExportDeduplicateFrames(bool),
+    SetExportAllowRotation(bool),
+    SetExportMipSafePadding(u32),
+    SetExportWriteManifest(bool),
+    AddExportTarget,
+    RemoveExportTarget(usize),
+    SetExportTargetTemplateFile(usize, PathBuf),
+    SetExportTargetMetadataFile(usize, PathBuf),
     CancelExportAs,
     EndExportAs,
+    SetExportSettings(ExportSettings<Any>),
+}
+
+// Sequence of edits made to a document, meant to be attached to bug reports
+#[derive(Clone, Debug, Serialize)]
+pub struct CommandHistory {
+    pub document: PathBuf,
+    pub commands: Vec<String>,
+    pub undone_commands: Vec<String>,
 }
 
 #[derive(Debug, Default)]
@@ -139,21 +246,38 @@ impl Document {
         match command {
             Command::Undo => self.undo()?,
             Command::Redo => self.redo()?,
+            Command::ClearHistory => self.clear_history()?,
             Command::DetachedNavigation => (),
             Command::Paste(ref c) => self.paste(c.clone())?,
+            Command::PasteAfterSelection(ref c) => self.paste_after_selection(c.clone())?,
+            Command::PasteProperties(ref c) => self.paste_properties(c.clone())?,
+            Command::PasteKeyframeHitboxes(ref c) => self.paste_keyframe_hitboxes(c.clone())?,
             Command::SetFramesListMode(m) => self.view.frames_list_mode = m,
             Command::FilterFrames(ref q) => self.view.frames_filter = q.clone(),
             Command::FilterAnimations(ref q) => self.view.animations_filter = q.clone(),
             Command::SetAnimationsListOffset(o) => self.view.animations_list_offset = o,
             Command::SetFramesListOffset(o) => self.view.set_frames_list_offset(o),
             Command::SetHitboxesListOffset(o) => self.view.hitboxes_list_offset = o,
+            Command::RecoverAutosave => self.recover_autosave()?,
+            Command::DiscardAutosave => self.persistent.recoverable_autosave = None,
+            Command::RepairSheet => self.repair_sheet(),
             Command::ImportFrames(ref p) => self.import_frames(p),
+            Command::ImportFramesIntoAnimation(ref p) => self.import_frames_into_animation(p)?,
+            Command::ImportAseprite(ref s) => self.import_sheet(s),
+            Command::ImportAtlasRegions(ref p) => self.import_frames(p),
+            Command::ImportSpriteStrip(ref p) => self.import_frames(p),
             Command::BeginRelocateFrames => self.begin_relocate_frames(),
             Command::RelocateFrame(ref from, ref to) => {
                 self.relocate_frame(from.clone(), to.clone())?
             }
             Command::CancelRelocateFrames => self.cancel_relocate_frames(),
             Command::EndRelocateFrames => self.end_relocate_frames()?,
+            Command::RelocateFramesRoot(ref from, ref to) => self.relocate_frames_root(from, to),
+            Command::RenameFrameFile(ref from, ref to) => self.rename_frame_file(from, to),
+            Command::ReplaceFrameSource(ref old, ref new) => self.replace_frame_source(old, new),
+            Command::SetFrameNormalMap(ref f, ref n) => {
+                self.sheet.set_frame_normal_map(f, n.as_ref())?
+            }
             Command::DeleteFrame(ref p) => self.sheet.delete_frame(p),
             Command::DeleteSelectedFrames => self.delete_selected_frames(),
             Command::DeleteSelection => self.delete_selection()?,
@@ -163,6 +287,12 @@ impl Document {
             Command::BrowseToStart(shift) => self.browse_to_start(shift)?,
             Command::ClearSelection => self.view.selection.clear(),
             Command::SelectAll => self.select_all()?,
+            Command::SelectNextAnimation => self.select_next_animation()?,
+            Command::SelectPreviousAnimation => self.select_previous_animation()?,
+            Command::InvertHitboxSelection => self.invert_hitbox_selection(),
+            Command::SelectKeyframesInRange(start, end) => {
+                self.select_keyframes_in_range(start, end)?
+            }
             Command::SelectFrame(ref p, shift, ctrl) => self.select_frame(p, shift, ctrl),
             Command::SelectAnimation(ref n, shift, ctrl) => self.select_animation(n, shift, ctrl),
             Command::SelectKeyframe(d, i, shift, ctrl) => {
@@ -171,21 +301,35 @@ impl Document {
             Command::SelectHitbox(ref n, shift, ctrl) => self.select_hitbox(n, shift, ctrl)?,
             Command::Pan(delta) => self.view.pan(delta),
             Command::CenterWorkbench => self.view.center_workbench(),
+            Command::FitWorkbenchToItem(ref s, v) => self.fit_workbench_to_item(s, v)?,
             Command::ZoomInWorkbench => self.view.zoom_in_workbench(),
             Command::ZoomInWorkbenchAround(ref p) => self.view.zoom_in_workbench_around(p),
             Command::ZoomOutWorkbench => self.view.zoom_out_workbench(),
             Command::ZoomOutWorkbenchAround(ref p) => self.view.zoom_out_workbench_around(p),
             Command::SetWorkbenchZoomFactor(f) => self.view.set_workbench_zoom_factor(f),
             Command::ResetWorkbenchZoom => self.view.reset_workbench_zoom(),
+            Command::AddWorkbenchGuide(g) => self.view.add_workbench_guide(g),
+            Command::RemoveWorkbenchGuide(i) => self.view.remove_workbench_guide(i),
+            Command::MoveWorkbenchGuide(i, p) => self.view.move_workbench_guide(i, p),
+            Command::ClearWorkbenchGuides => self.view.clear_workbench_guides(),
+            Command::SetGridSpacing(s) => self.view.set_grid_spacing(s),
+            Command::SetPanButton(b) => self.view.pan_button = b,
+            Command::SetGridSubdivisions(s) => self.view.set_grid_subdivisions(s),
+            Command::SetOriginOffset(o) => self.view.set_origin_offset(o),
             Command::EnableSpriteDarkening => self.view.darken_sprites = true,
             Command::DisableSpriteDarkening => self.view.darken_sprites = false,
             Command::HideSprite => self.view.hide_sprite = true,
             Command::ShowSprite => self.view.hide_sprite = false,
             Command::HideHitboxes => self.view.hide_hitboxes = true,
             Command::ShowHitboxes => self.view.hide_hitboxes = false,
+            Command::ToggleHitboxVisibility(ref n) => self.view.toggle_hitbox_visibility(n),
+            Command::ShowAllHitboxes => self.view.show_all_hitboxes(),
             Command::HideOrigin => self.view.hide_origin = true,
             Command::ShowOrigin => self.view.hide_origin = false,
             Command::CreateAnimation => self.create_animation()?,
+            Command::CreateAnimationFromSelectedFrames => {
+                self.create_animation_from_selected_frames()?
+            }
             Command::EditAnimation(ref name) => self.edit_animation(name)?,
             Command::BeginRenameSelection => self.begin_rename_selection(),
             Command::BeginRenameAnimation(ref n) => self.begin_rename_animation(n.clone()),
@@ -194,6 +338,11 @@ impl Document {
             Command::EndRenameAnimation(ref n) => self.end_rename_animation(n.clone())?,
             Command::EndRenameHitbox(ref n) => self.end_rename_hitbox(n.clone())?,
             Command::DeleteAnimation(ref name) => self.delete_animation(name),
+            Command::DeleteAnimationsNotMatching(ref q) => self.delete_animations_not_matching(q),
+            Command::NormalizeAnimationNames(c) => self.normalize_animation_names(c)?,
+            Command::SwapAnimationNames(ref a, ref b) => self.swap_animation_names(a, b)?,
+            Command::ReorderAnimations(ref names) => self.reorder_animations(names)?,
+            Command::SetAnimationFrame(ref frame) => self.set_animation_frame(frame)?,
             Command::DeleteSelectedAnimations => self.delete_selected_animations(),
             Command::Tick(dt) => self.advance_timeline(dt),
             Command::Play => self.play()?,
@@ -203,6 +352,7 @@ impl Document {
             Command::JumpToAnimationEnd => self.jump_to_animation_end()?,
             Command::JumpToPreviousFrame => self.jump_to_previous_frame()?,
             Command::JumpToNextFrame => self.jump_to_next_frame()?,
+            Command::JumpToKeyframe(i) => self.jump_to_keyframe(i)?,
             Command::SetSnapKeyframeDurations(s) => self.view.snap_keyframe_durations = s,
             Command::SetSnapKeyframeToOtherKeyframes(s) => {
                 self.view.snap_keyframes_to_other_keyframes = s
@@ -214,24 +364,54 @@ impl Document {
                 self.view.keyframe_snapping_base_duration =
                     d.clamp(Duration::from_millis(1), Duration::from_millis(1_000))
             }
+            Command::SetMinKeyframeDuration(d) => {
+                self.view.min_keyframe_duration =
+                    d.clamp(Duration::from_millis(1), Duration::from_millis(1_000))
+            }
             Command::ZoomInTimeline => self.view.zoom_in_timeline(),
             Command::ZoomInTimelineAround(t) => self.view.zoom_in_timeline_around(t),
             Command::ZoomOutTimeline => self.view.zoom_out_timeline(),
             Command::ZoomOutTimelineAround(t) => self.view.zoom_out_timeline_around(t),
             Command::SetTimelineZoomAmount(a) => self.view.set_timeline_zoom_amount(a),
             Command::ResetTimelineZoom => self.view.reset_timeline_zoom(),
+            Command::ResetView => self.view.reset(),
             Command::SetTimelineOffset(d) => self.view.set_timeline_offset(d),
             Command::PanTimeline(d) => self.view.pan_timeline(d),
+            Command::SetFollowPlayhead(f) => self.view.follow_playhead = f,
+            Command::SetTimelineViewportWidth(w) => self.set_timeline_viewport_width(w),
             Command::SetAnimationLooping(l) => self.set_animation_looping(l)?,
+            Command::SetSelectedAnimationsLooping(l) => self.set_selected_animations_looping(l),
+            Command::SetAnimationStartDelay(d) => self.set_animation_start_delay(d)?,
+            Command::SetAnimationOrigin(o) => self.set_animation_origin(o)?,
+            Command::NudgeAnimationOrigin(d, l) => self.nudge_animation_origin(d, l)?,
+            Command::ExtractCommonOffset => self.extract_common_offset()?,
             Command::ApplyDirectionPreset(p) => self.apply_direction_preset(p)?,
             Command::SelectDirection(d) => self.select_direction(d)?,
             Command::BeginDragAndDropFrame(ref f) => self.begin_drag_and_drop_frame(f.clone()),
             Command::DropFrameOnTimeline(d, i) => self.drop_frame_on_timeline(d, i)?,
             Command::EndDragAndDropFrame => self.end_drag_and_drop_frame(),
             Command::DeleteSelectedKeyframes => self.delete_selected_keyframes()?,
+            Command::MoveSelectedKeyframesEarlier => self.move_selected_keyframes_earlier()?,
+            Command::MoveSelectedKeyframesLater => self.move_selected_keyframes_later()?,
+            Command::TrimAnimationEnds(ref f) => self.trim_animation_ends(f)?,
+            Command::MergeAdjacentDuplicateKeyframes => {
+                self.merge_adjacent_duplicate_keyframes()?
+            }
+            Command::ResetKeyframeDuration => self.reset_keyframe_duration()?,
+            Command::ResetSelectedKeyframeOffsets => self.reset_selected_keyframe_offsets()?,
+            Command::AlignKeyframeOffsets(a, ref s) => self.align_keyframe_offsets(a, s)?,
             Command::SetKeyframeDuration(d) => self.set_keyframe_duration(d)?,
+            Command::SetKeyframeDurationInFrames(n, fps) => {
+                self.set_keyframe_duration_in_frames(n, fps)?
+            }
+            Command::SetKeyframeHold(h) => self.set_keyframe_hold(h)?,
+            Command::QuantizeKeyframeDurations => self.quantize_keyframe_durations()?,
+            Command::SetUniformKeyframeDuration(d) => self.set_uniform_keyframe_duration(d)?,
             Command::SetKeyframeOffsetX(x) => self.set_keyframe_offset_x(x)?,
             Command::SetKeyframeOffsetY(y) => self.set_keyframe_offset_y(y)?,
+            Command::TranslateSelectedKeyframes(d) => self.translate_selected_keyframes(d)?,
+            Command::AddKeyframeEvent(ref e) => self.add_keyframe_event(e)?,
+            Command::RemoveKeyframeEvent(ref e) => self.remove_keyframe_event(e)?,
             Command::BeginDragAndDropKeyframe(d, i) => self.begin_drag_and_drop_keyframe(d, i)?,
             Command::DropKeyframeOnTimeline(d, i) => self.drop_keyframe_on_timeline(d, i)?,
             Command::EndDragAndDropKeyframe => self.end_drag_and_drop_keyframe(),
@@ -243,19 +423,43 @@ impl Document {
             Command::EndNudgeKeyframe() => self.end_nudge_keyframe(),
             Command::CreateHitbox(p) => self.create_hitbox(p)?,
             Command::DeleteHitbox(ref name) => self.delete_hitbox(name)?,
+            Command::DeleteHitboxFromAllKeyframes(ref name) => {
+                self.delete_hitbox_from_all_keyframes(name)?
+            }
             Command::DeleteSelectedHitboxes => self.delete_selected_hitboxes()?,
+            Command::DeleteAllHitboxesOnKeyframe => self.delete_all_hitboxes_on_keyframe(),
+            Command::DuplicateSelectedHitboxes => self.duplicate_selected_hitboxes()?,
+            Command::SubdivideHitbox(ref n, c, r) => self.subdivide_hitbox(n, c, r)?,
+            Command::PrefixHitboxNames(ref p) => self.prefix_hitbox_names(p)?,
+            Command::SuffixHitboxNames(ref s) => self.suffix_hitbox_names(s)?,
             Command::LockHitboxes => self.view.lock_hitboxes = true,
             Command::UnlockHitboxes => self.view.lock_hitboxes = false,
             Command::SetHitboxPositionX(x) => self.set_hitbox_position_x(x)?,
             Command::SetHitboxPositionY(y) => self.set_hitbox_position_y(y)?,
             Command::SetHitboxWidth(width) => self.set_hitbox_width(width)?,
             Command::SetHitboxHeight(height) => self.set_hitbox_height(height)?,
+            Command::MatchHitboxSizes => self.match_hitbox_sizes()?,
+            Command::FlipHitboxesHorizontally(ref s) => self.flip_hitboxes_horizontally(s)?,
+            Command::SetHitboxKind(kind) => self.set_hitbox_kind(kind)?,
             Command::TogglePreserveAspectRatio => {
                 self.persistent.preserve_aspect_ratio = !self.persistent.preserve_aspect_ratio
             }
+            Command::TogglePreciseDurations => {
+                self.persistent.precise_durations = !self.persistent.precise_durations
+            }
+            Command::SetDefaultPreserveAspectRatio(p) => {
+                self.persistent.default_preserve_aspect_ratio = p
+            }
             Command::BeginNudgeHitbox(ref n) => self.begin_nudge_hitbox(n)?,
             Command::UpdateNudgeHitbox(d, b) => self.update_nudge_hitbox(d, b)?,
             Command::EndNudgeHitbox => self.end_nudge_hitbox(),
+            Command::CreateAnchor(p) => self.create_anchor(p)?,
+            Command::SetAnchorPosition(ref n, p) => self.set_anchor_position(n, p)?,
+            Command::RenameAnchor(ref o, ref n) => self.rename_anchor(o, n)?,
+            Command::DeleteAnchor(ref n) => self.delete_anchor(n)?,
+            Command::BeginNudgeAnchor(ref n) => self.begin_nudge_anchor(n)?,
+            Command::UpdateNudgeAnchor(d, b) => self.update_nudge_anchor(d, b)?,
+            Command::EndNudgeAnchor => self.end_nudge_anchor(),
             Command::BeginResizeHitbox(ref n, a) => self.begin_resize_hitbox(n, a)?,
             Command::UpdateResizeHitbox(d, a) => self.update_resize_hitbox(d, a)?,
             Command::EndResizeHitbox => self.end_resize_hitbox(),
@@ -264,8 +468,27 @@ impl Document {
             Command::SetExportTextureFile(ref p) => self.set_export_texture_file(p)?,
             Command::SetExportMetadataFile(ref p) => self.set_export_metadata_file(p)?,
             Command::SetExportMetadataPathsRoot(ref p) => self.set_export_metadata_paths_root(p)?,
+            Command::SetExportFormat(f) => self.set_export_format(f)?,
+            Command::SetExportTextureFormat(f) => self.set_export_texture_format(f)?,
+            Command::SetExportTextureScale(s) => self.set_export_texture_scale(s)?,
+            Command::SetPackingAlgorithm(a) => self.set_export_packing_algorithm(a)?,
+            Command::SetPackingOrder(o) => self.set_export_packing_order(o)?,
+            Command::SetExportPathStyle(s) => self.set_export_path_style(s)?,
+            Command::SetExportDeduplicateFrames(d) => self.set_export_deduplicate_frames(d)?,
+            Command::SetExportAllowRotation(r) => self.set_export_allow_rotation(r)?,
+            Command::SetExportMipSafePadding(p) => self.set_export_mip_safe_padding(p)?,
+            Command::SetExportWriteManifest(w) => self.set_export_write_manifest(w)?,
+            Command::AddExportTarget => self.add_export_target()?,
+            Command::RemoveExportTarget(i) => self.remove_export_target(i)?,
+            Command::SetExportTargetTemplateFile(i, ref p) => {
+                self.set_export_target_template_file(i, p)?
+            }
+            Command::SetExportTargetMetadataFile(i, ref p) => {
+                self.set_export_target_metadata_file(i, p)?
+            }
             Command::CancelExportAs => self.cancel_export_as(),
             Command::EndExportAs => self.end_export_as()?,
+            Command::SetExportSettings(s) => self.set_export_settings(s)?,
         }
 
         Ok(())
@@ -295,8 +518,11 @@ impl Document {
                 | Command::UpdateNudgeKeyframe(_, _)
                 | Command::BeginNudgeHitbox(_)
                 | Command::UpdateNudgeHitbox(_, _)
+                | Command::BeginNudgeAnchor(_)
+                | Command::UpdateNudgeAnchor(_, _)
                 | Command::BeginResizeHitbox(_, _)
                 | Command::UpdateResizeHitbox(_, _)
+                | Command::RenameFrameFile(_, _)
         ) {
             self.transient = Default::default();
             self.record_command(command);
@@ -409,6 +635,27 @@ impl Document {
         Ok(())
     }
 
+    // History cannot be rewritten while a drag or nudge holds uncommitted changes to the sheet
+    fn can_use_undo_system(&self) -> bool {
+        self.sheet == self.history[self.history_index].sheet
+    }
+
+    pub fn clear_history(&mut self) -> DocumentResult<()> {
+        if !self.can_use_undo_system() {
+            return Err(DocumentError::EditInProgress);
+        }
+        // The current version is preserved so that the document's saved status is unaffected
+        let current_entry = std::mem::take(&mut self.history[self.history_index]);
+        self.history = vec![HistoryEntry {
+            last_command: None,
+            view: self.view.clone(),
+            ..current_entry
+        }];
+        self.history_index = 0;
+        self.detached_view = None;
+        Ok(())
+    }
+
     fn undo_command(&self) -> Option<&Command> {
         self.history[self.history_index].last_command.as_ref()
     }
@@ -428,6 +675,25 @@ impl Document {
     pub fn redo_effect(&self) -> Option<String> {
         self.redo_command().map(|c| c.to_string())
     }
+
+    pub fn command_history(&self, redact_paths: bool) -> CommandHistory {
+        let labels = |entries: &[HistoryEntry]| {
+            entries
+                .iter()
+                .filter_map(|entry| entry.last_command.as_ref())
+                .map(|command| command.to_string())
+                .collect::<Vec<_>>()
+        };
+        let (done, undone) = self.history.split_at(self.history_index + 1);
+        CommandHistory {
+            document: match redact_paths {
+                true => self.path.file_name().unwrap_or_default().into(),
+                false => self.path.clone(),
+            },
+            commands: labels(done),
+            undone_commands: labels(undone),
+        }
+    }
 }
 
 impl Display for Command {
@@ -442,24 +708,39 @@ impl Display for Command {
             | Command::BrowseToStart(_)
             | Command::ClearSelection
             | Command::SelectAll
+            | Command::SelectNextAnimation
+            | Command::SelectPreviousAnimation
+            | Command::InvertHitboxSelection
+            | Command::SelectKeyframesInRange(_, _)
             | Command::SelectFrame(_, _, _)
             | Command::SelectAnimation(_, _, _)
             | Command::SelectKeyframe(_, _, _, _)
             | Command::SelectHitbox(_, _, _)
             | Command::Pan(_)
             | Command::CenterWorkbench
+            | Command::FitWorkbenchToItem(_, _)
             | Command::ZoomInWorkbench
             | Command::ZoomInWorkbenchAround(_)
             | Command::ZoomOutWorkbench
             | Command::ZoomOutWorkbenchAround(_)
             | Command::SetWorkbenchZoomFactor(_)
             | Command::ResetWorkbenchZoom
+            | Command::AddWorkbenchGuide(_)
+            | Command::RemoveWorkbenchGuide(_)
+            | Command::MoveWorkbenchGuide(_, _)
+            | Command::ClearWorkbenchGuides
+            | Command::SetGridSpacing(_)
+            | Command::SetPanButton(_)
+            | Command::SetGridSubdivisions(_)
+            | Command::SetOriginOffset(_)
             | Command::EnableSpriteDarkening
             | Command::DisableSpriteDarkening
             | Command::HideSprite
             | Command::ShowSprite
             | Command::HideHitboxes
             | Command::ShowHitboxes
+            | Command::ToggleHitboxVisibility(_)
+            | Command::ShowAllHitboxes
             | Command::HideOrigin
             | Command::ShowOrigin
             | Command::ScrubTimeline(_)
@@ -467,6 +748,7 @@ impl Display for Command {
             | Command::JumpToAnimationEnd
             | Command::JumpToPreviousFrame
             | Command::JumpToNextFrame
+            | Command::JumpToKeyframe(_)
             | Command::ZoomInTimeline
             | Command::ZoomInTimelineAround(_)
             | Command::ZoomOutTimeline
@@ -477,59 +759,136 @@ impl Display for Command {
             | Command::SetTimelineZoomAmount(_)
             | Command::SetTimelineOffset(_)
             | Command::PanTimeline(_)
+            | Command::SetFollowPlayhead(_)
+            | Command::SetTimelineViewportWidth(_)
             | Command::Play
             | Command::Pause
-            | Command::ResetTimelineZoom => f.write_str("Navigation"),
+            | Command::ResetTimelineZoom
+            | Command::ResetView => f.write_str("Navigation"),
 
             Command::BeginExportAs
             | Command::SetExportTemplateFile(_)
             | Command::SetExportTextureFile(_)
             | Command::SetExportMetadataFile(_)
             | Command::SetExportMetadataPathsRoot(_)
+            | Command::SetExportFormat(_)
+            | Command::SetExportTextureFormat(_)
+            | Command::SetExportTextureScale(_)
+            | Command::SetPackingAlgorithm(_)
+            | Command::SetPackingOrder(_)
+            | Command::SetExportPathStyle(_)
+            | Command::SetExportDeduplicateFrames(_)
+            | Command::SetExportAllowRotation(_)
+            | Command::SetExportMipSafePadding(_)
+            | Command::SetExportWriteManifest(_)
+            | Command::AddExportTarget
+            | Command::RemoveExportTarget(_)
+            | Command::SetExportTargetTemplateFile(_, _)
+            | Command::SetExportTargetMetadataFile(_, _)
             | Command::CancelExportAs
-            | Command::EndExportAs => f.write_str("Change Export Settings"),
+            | Command::EndExportAs
+            | Command::SetExportSettings(_) => f.write_str("Change Export Settings"),
 
             Command::Undo => f.write_str("Undo"),
             Command::Redo => f.write_str("Redo"),
-            Command::Paste(c) => match c {
+            Command::ClearHistory => f.write_str("Clear History"),
+            Command::Paste(c) | Command::PasteAfterSelection(c) => match c {
                 Clipboard::Animations(_) => f.write_str("Paste Animations"),
                 Clipboard::Keyframes(_) => f.write_str("Paste Keyframes"),
                 Clipboard::Hitboxes(_) => f.write_str("Paste Hitboxes"),
             },
-            Command::ImportFrames(_) => f.write_str("Import Frames"),
+            Command::PasteProperties(_) => f.write_str("Paste Keyframe Properties"),
+            Command::PasteKeyframeHitboxes(_) => f.write_str("Paste Hitboxes"),
+            Command::ImportFrames(_) | Command::ImportFramesIntoAnimation(_) => {
+                f.write_str("Import Frames")
+            }
+            Command::ImportAseprite(_) => f.write_str("Import Aseprite Sheet"),
+            Command::ImportAtlasRegions(_) => f.write_str("Import Atlas Regions"),
+            Command::ImportSpriteStrip(_) => f.write_str("Import Sprite Strip"),
+            Command::RecoverAutosave => f.write_str("Recover Autosave"),
+            Command::DiscardAutosave => f.write_str("Discard Autosave"),
+            Command::RepairSheet => f.write_str("Repair Spritesheet"),
             Command::DeleteFrame(_) => f.write_str("Delete Frame"),
+            Command::RenameFrameFile(_, _) => f.write_str("Rename Frame"),
+            Command::ReplaceFrameSource(_, _) => f.write_str("Replace Frame"),
+            Command::SetFrameNormalMap(_, _) => f.write_str("Set Normal Map"),
             Command::DeleteSelectedFrames => f.write_str("Delete Frames"),
             Command::DeleteSelection => f.write_str("Delete"),
             Command::NudgeSelection(_, _) => f.write_str("Nudge"),
             Command::CreateAnimation => f.write_str("Create Animation"),
+            Command::CreateAnimationFromSelectedFrames => {
+                f.write_str("Create Animation From Frames")
+            }
             Command::EditAnimation(_) => f.write_str("Open Animation"),
             Command::DeleteAnimation(_) => f.write_str("Delete Animation"),
+            Command::DeleteAnimationsNotMatching(_) => f.write_str("Delete Animations"),
+            Command::NormalizeAnimationNames(_) => f.write_str("Normalize Animation Names"),
+            Command::SwapAnimationNames(_, _) => f.write_str("Swap Animation Names"),
+            Command::ReorderAnimations(_) => f.write_str("Reorder Animations"),
+            Command::SetAnimationFrame(_) => f.write_str("Set Animation Frame"),
             Command::DeleteSelectedAnimations => f.write_str("Delete Animations"),
             Command::Tick(_) => f.write_str("Tick"),
             Command::SetAnimationLooping(_) => f.write_str("Toggle Looping"),
+            Command::SetSelectedAnimationsLooping(_) => f.write_str("Toggle Looping"),
+            Command::SetAnimationStartDelay(_) => f.write_str("Change Start Delay"),
+            Command::SetAnimationOrigin(_) => f.write_str("Move Animation Origin"),
+            Command::NudgeAnimationOrigin(_, _) => f.write_str("Move Animation Origin"),
+            Command::ExtractCommonOffset => f.write_str("Extract Common Offset"),
             Command::ApplyDirectionPreset(_) => f.write_str("Set Perspective"),
             Command::SelectDirection(_) => f.write_str("Select Directions"),
             Command::DeleteSelectedKeyframes => f.write_str("Delete Keyframes"),
-            Command::SetKeyframeDuration(_) => f.write_str("Set Keyframe Duration"),
+            Command::MoveSelectedKeyframesEarlier | Command::MoveSelectedKeyframesLater => {
+                f.write_str("Reorder Keyframes")
+            }
+            Command::TrimAnimationEnds(_) => f.write_str("Trim Animation"),
+            Command::MergeAdjacentDuplicateKeyframes => f.write_str("Merge Duplicate Keyframes"),
+            Command::ResetKeyframeDuration => f.write_str("Reset Keyframe Duration"),
+            Command::ResetSelectedKeyframeOffsets => f.write_str("Reset Keyframe Offsets"),
+            Command::AlignKeyframeOffsets(_, _) => f.write_str("Align Keyframes"),
+            Command::SetKeyframeDuration(_) | Command::SetKeyframeDurationInFrames(_, _) => {
+                f.write_str("Set Keyframe Duration")
+            }
+            Command::SetKeyframeHold(_) => f.write_str("Set Keyframe Hold"),
+            Command::QuantizeKeyframeDurations => f.write_str("Quantize Keyframe Durations"),
+            Command::SetUniformKeyframeDuration(_) => f.write_str("Set Uniform Keyframe Duration"),
             Command::SetKeyframeOffsetX(_) => f.write_str("Start Keyframe X Offset"),
             Command::SetKeyframeOffsetY(_) => f.write_str("Start Keyframe Y Offset"),
+            Command::TranslateSelectedKeyframes(_) => f.write_str("Move Keyframes"),
+            Command::AddKeyframeEvent(_) => f.write_str("Add Keyframe Event"),
+            Command::RemoveKeyframeEvent(_) => f.write_str("Remove Keyframe Event"),
             Command::CreateHitbox(_) => f.write_str("Create Hitbox"),
             Command::DeleteHitbox(_) => f.write_str("Delete Hitbox"),
-            Command::DeleteSelectedHitboxes => f.write_str("Delete Hitboxes"),
+            Command::DeleteHitboxFromAllKeyframes(_) => f.write_str("Delete Hitbox From Animation"),
+            Command::DeleteSelectedHitboxes | Command::DeleteAllHitboxesOnKeyframe => {
+                f.write_str("Delete Hitboxes")
+            }
+            Command::DuplicateSelectedHitboxes => f.write_str("Duplicate Hitboxes"),
+            Command::SubdivideHitbox(_, _, _) => f.write_str("Split Hitbox"),
+            Command::PrefixHitboxNames(_) | Command::SuffixHitboxNames(_) => {
+                f.write_str("Rename Hitboxes")
+            }
             Command::LockHitboxes => f.write_str("Lock Hitboxes"),
             Command::UnlockHitboxes => f.write_str("Unlock Hitboxes"),
             Command::SetHitboxPositionX(_) => f.write_str("Set Hitbox X Position"),
             Command::SetHitboxPositionY(_) => f.write_str("Set Hitbox Y Position"),
             Command::SetHitboxWidth(_) => f.write_str("Set Hitbox Width"),
             Command::SetHitboxHeight(_) => f.write_str("Set Hitbox Height"),
+            Command::MatchHitboxSizes => f.write_str("Match Hitbox Sizes"),
+            Command::FlipHitboxesHorizontally(_) => f.write_str("Flip Hitboxes"),
+            Command::SetHitboxKind(_) => f.write_str("Set Hitbox Kind"),
             Command::TogglePreserveAspectRatio => f.write_str("Toggle Preserve Aspect Ratio"),
+            Command::TogglePreciseDurations => f.write_str("Toggle Precise Durations"),
+            Command::SetDefaultPreserveAspectRatio(_) => {
+                f.write_str("Set Default Preserve Aspect Ratio")
+            }
             Command::SetSnapKeyframeDurations(true) => f.write_str("Enable Keyframe Snapping"),
             Command::SetSnapKeyframeDurations(false) => f.write_str("Disable Keyframe Snapping"),
 
             Command::BeginRelocateFrames
             | Command::RelocateFrame(_, _)
             | Command::CancelRelocateFrames
-            | Command::EndRelocateFrames => f.write_str("Relocate Frames"),
+            | Command::EndRelocateFrames
+            | Command::RelocateFramesRoot(_, _) => f.write_str("Relocate Frames"),
 
             Command::SetSnapKeyframeToOtherKeyframes(_)
             | Command::SetSnapKeyframeToMultiplesOfDuration(_)
@@ -537,6 +896,8 @@ impl Display for Command {
                 f.write_str("Adjust Snapping Settings")
             }
 
+            Command::SetMinKeyframeDuration(_) => f.write_str("Change Minimum Keyframe Duration"),
+
             Command::BeginDragAndDropFrame(_)
             | Command::DropFrameOnTimeline(_, _)
             | Command::EndDragAndDropFrame => f.write_str("Create Keyframe"),
@@ -557,6 +918,14 @@ impl Display for Command {
             | Command::UpdateNudgeHitbox(_, _)
             | Command::EndNudgeHitbox => f.write_str("Nudge Hitbox"),
 
+            Command::CreateAnchor(_) => f.write_str("Create Anchor"),
+            Command::RenameAnchor(_, _) => f.write_str("Rename Anchor"),
+            Command::DeleteAnchor(_) => f.write_str("Delete Anchor"),
+            Command::SetAnchorPosition(_, _)
+            | Command::BeginNudgeAnchor(_)
+            | Command::UpdateNudgeAnchor(_, _)
+            | Command::EndNudgeAnchor => f.write_str("Move Anchor"),
+
             Command::BeginResizeHitbox(_, _)
             | Command::UpdateResizeHitbox(_, _)
             | Command::EndResizeHitbox => f.write_str("Resize Hitbox"),
@@ -615,6 +984,52 @@ mod test {
         assert_eq!(list_frames(&d), all_three);
     }
 
+    #[test]
+    fn can_clear_history() {
+        let mut d = Document::new("tmp");
+        run(&mut d, Command::ImportFrames(vec!["frame_1".into()]));
+        run(&mut d, Command::ImportFrames(vec!["frame_2".into()]));
+        run(&mut d, Command::Undo);
+        let version = d.version();
+        d.mark_as_saved(version);
+
+        run(&mut d, Command::ClearHistory);
+        assert_eq!(d.undo_effect(), None);
+        assert_eq!(d.redo_effect(), None);
+        assert_eq!(d.version(), version);
+        assert!(d.is_saved());
+        assert_eq!(list_frames(&d), vec![String::from("frame_1")]);
+
+        run(&mut d, Command::Undo);
+        assert_eq!(list_frames(&d), vec![String::from("frame_1")]);
+        run(&mut d, Command::ImportFrames(vec!["frame_3".into()]));
+        assert!(!d.is_saved());
+        run(&mut d, Command::Undo);
+        assert!(d.is_saved());
+    }
+
+    #[test]
+    fn can_export_command_history() {
+        let mut d = Document::new("some/directory/sheet.tiger");
+        run(&mut d, Command::ImportFrames(vec!["frame_1".into()]));
+        run(&mut d, Command::CreateAnimation);
+        run(&mut d, Command::SetAnimationLooping(true));
+        run(&mut d, Command::Undo);
+
+        let history = d.command_history(false);
+        assert_eq!(
+            history.document,
+            PathBuf::from("some/directory/sheet.tiger")
+        );
+        assert_eq!(history.commands, vec!["Import Frames", "Create Animation"]);
+        assert_eq!(history.undone_commands, vec!["Toggle Looping"]);
+
+        let history = d.command_history(true);
+        assert_eq!(history.document, PathBuf::from("sheet.tiger"));
+        let log: serde_json::Value = serde_json::to_value(&history).unwrap();
+        assert_eq!(log["commands"][1], "Create Animation");
+    }
+
     #[test]
     fn can_undo_multiple_view_changes_at_once() {
         let mut d = Document::new("tmp");
@@ -1,12 +1,115 @@
+use crate::document::view::animation_matches_query;
 use crate::document::*;
+use crate::import::ImportedSheet;
 use crate::sheet::DirectionPreset;
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Case {
+    Snake,
+    Kebab,
+    Camel,
+}
+
+impl Case {
+    pub fn apply<T: AsRef<str>>(&self, name: T) -> String {
+        let words = split_words(name.as_ref());
+        match self {
+            Case::Snake => words.join("_"),
+            Case::Kebab => words.join("-"),
+            Case::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(index, word)| {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) if index > 0 => first.to_uppercase().chain(chars).collect(),
+                        _ => word.clone(),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+// Words are separated by any non-alphanumeric character or by a lowercase
+// letter followed by an uppercase one, so `Walk Up`, `walk-up` and `walkUp`
+// all split into `walk` and `up`.
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut previous_is_lowercase = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            previous_is_lowercase = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_is_lowercase && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        previous_is_lowercase = c.is_lowercase() || c.is_numeric();
+        word.extend(c.to_lowercase());
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
 impl Document {
     pub(super) fn import_frames(&mut self, frames: &Vec<PathBuf>) {
         self.sheet.add_frames(frames);
         self.select_frames_only(frames.clone());
     }
 
+    pub(super) fn import_frames_into_animation(
+        &mut self,
+        frames: &Vec<PathBuf>,
+    ) -> DocumentResult<()> {
+        let (animation_name, _) = self.workbench_animation()?;
+        let animation_name = animation_name.clone();
+        let (direction, sequence) = self.workbench_sequence_mut()?;
+        let first_index = sequence.num_keyframes();
+        for (index, frame) in frames.iter().enumerate() {
+            sequence.insert_keyframe(Keyframe::new(frame), first_index + index)?;
+        }
+        self.sheet.add_frames(frames);
+        self.select_keyframes_only(
+            (first_index..(first_index + frames.len()))
+                .map(|i| (animation_name.clone(), direction, i)),
+        );
+        Ok(())
+    }
+
+    pub(super) fn import_sheet(&mut self, imported_sheet: &ImportedSheet) {
+        self.sheet.add_frames(&imported_sheet.frames);
+        for imported_animation in &imported_sheet.animations {
+            let (_, animation) = self.sheet.create_animation(&imported_animation.name);
+            animation.apply_direction_preset(DirectionPreset::FixedAngle);
+            if let Some(sequence) = animation.sequence_mut(Direction::North) {
+                for (index, (frame, duration)) in imported_animation.keyframes.iter().enumerate() {
+                    let mut keyframe = Keyframe::new(frame);
+                    keyframe.set_duration_millis(*duration);
+                    sequence.insert_keyframe(keyframe, index).ok();
+                }
+            }
+        }
+        self.select_frames_only(imported_sheet.frames.clone());
+    }
+
+    pub(super) fn recover_autosave(&mut self) -> DocumentResult<()> {
+        self.sheet = std::mem::take(&mut self.persistent.recoverable_autosave)
+            .ok_or(DocumentError::NoAutosaveToRecover)?;
+        Ok(())
+    }
+
+    pub(super) fn repair_sheet(&mut self) {
+        let missing_frames = self.sheet.missing_frames();
+        self.sheet.add_frames(&missing_frames);
+    }
+
     pub(super) fn delete_selected_frames(&mut self) {
         let selected_frames = self.view.selection.frames().collect::<Vec<_>>();
         for frame in selected_frames {
@@ -21,6 +124,25 @@ impl Document {
         self.edit_animation(animation_name)
     }
 
+    pub(super) fn create_animation_from_selected_frames(&mut self) -> DocumentResult<()> {
+        let selected_frames = self
+            .sheet
+            .sorted_frames()
+            .into_iter()
+            .map(|f| f.source().to_owned())
+            .filter(|f| self.selection().is_frame_selected(f))
+            .collect::<Vec<_>>();
+        let (animation_name, animation) = self.sheet.create_animation("New Animation");
+        animation.apply_direction_preset(DirectionPreset::FixedAngle);
+        if let Some(sequence) = animation.sequence_mut(Direction::North) {
+            for (index, frame) in selected_frames.iter().enumerate() {
+                sequence.insert_keyframe(Keyframe::new(frame), index)?;
+            }
+        }
+        self.select_animation_only(animation_name.clone());
+        self.edit_animation(animation_name)
+    }
+
     pub(super) fn edit_animation<T: AsRef<str>>(&mut self, name: T) -> DocumentResult<()> {
         self.view.current_animation = Some(name.as_ref().to_owned());
         self.view.center_workbench();
@@ -51,6 +173,99 @@ impl Document {
         Ok(())
     }
 
+    pub(super) fn swap_animation_names<T: AsRef<str>, U: AsRef<str>>(
+        &mut self,
+        name_a: T,
+        name_b: U,
+    ) -> DocumentResult<()> {
+        let (name_a, name_b) = (name_a.as_ref(), name_b.as_ref());
+        self.sheet.swap_animation_names(name_a, name_b)?;
+
+        let swap = |name: &String| {
+            if name == name_a {
+                name_b.to_owned()
+            } else if name == name_b {
+                name_a.to_owned()
+            } else {
+                name.clone()
+            }
+        };
+
+        let selection = &self.view.selection;
+        let animations = selection.animations().map(swap).collect::<Vec<_>>();
+        let keyframes = selection
+            .keyframes()
+            .map(|(n, d, i)| (swap(n), *d, *i))
+            .collect::<Vec<_>>();
+        let hitboxes = selection
+            .hitboxes()
+            .map(|(n, d, i, h)| (swap(n), *d, *i, h.clone()))
+            .collect::<Vec<_>>();
+        if !animations.is_empty() {
+            self.select_animations_only(animations);
+        } else if !keyframes.is_empty() {
+            self.select_keyframes_only(keyframes);
+        } else if !hitboxes.is_empty() {
+            self.select_hitboxes_only(hitboxes);
+        }
+
+        self.view.current_animation = self.view.current_animation.as_ref().map(swap);
+        Ok(())
+    }
+
+    // Animations whose normalized name is already taken keep their current name
+    // and are reported back through `DocumentError::AnimationNameCollisions`.
+    pub(super) fn normalize_animation_names(&mut self, case: Case) -> DocumentResult<()> {
+        let mut old_names = self
+            .sheet
+            .animations_iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        old_names.sort();
+
+        let mut selected_animations = self
+            .view
+            .selection
+            .animations()
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut collisions = Vec::new();
+
+        for old_name in old_names {
+            let new_name = case.apply(&old_name);
+            if new_name.is_empty() || new_name == old_name {
+                continue;
+            }
+            if self.sheet.has_animation(&new_name) {
+                collisions.push(old_name);
+                continue;
+            }
+            self.sheet.rename_animation(&old_name, &new_name)?;
+            for selected in selected_animations.iter_mut() {
+                if *selected == old_name {
+                    *selected = new_name.clone();
+                }
+            }
+            if Some(old_name.as_str()) == self.current_animation().as_deref() {
+                self.view.current_animation = Some(new_name);
+            }
+        }
+
+        if !selected_animations.is_empty() {
+            self.select_animations_only(selected_animations);
+        }
+
+        if collisions.is_empty() {
+            Ok(())
+        } else {
+            Err(DocumentError::AnimationNameCollisions(collisions))
+        }
+    }
+
+    pub(super) fn reorder_animations(&mut self, names: &[String]) -> DocumentResult<()> {
+        Ok(self.sheet.set_animation_order(names)?)
+    }
+
     pub(super) fn delete_animation<T: AsRef<str>>(&mut self, name: T) {
         self.sheet.delete_animation(&name);
     }
@@ -62,6 +277,27 @@ impl Document {
         }
     }
 
+    // Uses the same matching rules as the animations list filter
+    pub(super) fn delete_animations_not_matching<T: AsRef<str>>(&mut self, query: T) {
+        let doomed_animations = self
+            .sheet
+            .animations_iter()
+            .filter(|(name, _)| !animation_matches_query(name, &query))
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        for animation in doomed_animations {
+            self.sheet.delete_animation(animation);
+        }
+    }
+
+    pub(super) fn set_selected_animations_looping(&mut self, is_looping: bool) {
+        for name in self.view.selection.animations() {
+            if let Some(animation) = self.sheet.animation_mut(name) {
+                animation.set_looping(is_looping);
+            }
+        }
+    }
+
     pub fn set_missing_textures(&mut self, missing_textures: HashSet<PathBuf>) {
         self.persistent.missing_textures = missing_textures;
     }
@@ -73,4 +309,315 @@ impl Document {
     pub fn is_frame_missing_on_disk<T: AsRef<Path>>(&self, frame: T) -> bool {
         self.persistent.missing_textures.contains(frame.as_ref())
     }
+
+    pub fn missing_keyframe_frames(&self) -> Vec<(String, Direction, usize)> {
+        let mut missing_keyframes = vec![];
+        for (name, animation) in self.sheet.sorted_animations() {
+            for (direction, sequence) in animation.sequences_iter() {
+                for (index, keyframe) in sequence.keyframes_iter().enumerate() {
+                    if self.is_frame_missing_on_disk(keyframe.frame()) {
+                        missing_keyframes.push((name.clone(), *direction, index));
+                    }
+                }
+            }
+        }
+        missing_keyframes
+    }
+
+    // Animations with fewer than two keyframes (across all directions) cannot animate anything
+    pub fn degenerate_animations(&self) -> Vec<String> {
+        self.sheet
+            .sorted_animations()
+            .into_iter()
+            .filter(|(_, animation)| {
+                let num_keyframes = animation
+                    .sequences_iter()
+                    .map(|(_, sequence)| sequence.num_keyframes())
+                    .sum::<usize>();
+                num_keyframes < 2
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::TigerAppMock;
+
+    #[test]
+    fn can_convert_names_to_case() {
+        assert_eq!(Case::Snake.apply("Walk Up"), "walk_up");
+        assert_eq!(Case::Kebab.apply("walkUp"), "walk-up");
+        assert_eq!(Case::Camel.apply("walk_up_2"), "walkUp2");
+        assert_eq!(Case::Snake.apply("  Attack--Heavy "), "attack_heavy");
+    }
+
+    #[test]
+    fn can_normalize_animation_names() {
+        let mut d = Document::new("tmp");
+        d.sheet
+            .add_test_animation::<_, &str>("Walk Up", HashMap::new());
+        d.sheet
+            .add_test_animation::<_, &str>("Idle Left", HashMap::new());
+        d.sheet
+            .add_test_animation::<_, &str>("idle_left", HashMap::new());
+        d.process_command(Command::EditAnimation("Walk Up".to_owned()))
+            .unwrap();
+        d.process_command(Command::SelectAnimation("Walk Up".to_owned(), false, false))
+            .unwrap();
+
+        let result = d.process_command(Command::NormalizeAnimationNames(Case::Snake));
+        assert!(matches!(
+            result,
+            Err(DocumentError::AnimationNameCollisions(ref n)) if n == &vec!["Idle Left".to_owned()]
+        ));
+        assert!(d.sheet.has_animation("walk_up"));
+        assert!(!d.sheet.has_animation("Walk Up"));
+        assert!(d.sheet.has_animation("Idle Left"));
+        assert!(d.sheet.has_animation("idle_left"));
+        assert_eq!(d.current_animation().as_deref(), Some("walk_up"));
+        assert!(d.view.selection.is_animation_selected("walk_up"));
+
+        d.process_command(Command::Undo).unwrap();
+        assert!(d.sheet.has_animation("Walk Up"));
+        assert!(!d.sheet.has_animation("walk_up"));
+    }
+
+    #[test]
+    fn can_delete_animations_not_matching_query() {
+        let mut d = Document::new("tmp");
+        for name in ["boss_idle", "boss_attack", "player_idle"] {
+            d.sheet.add_test_animation::<_, &str>(name, HashMap::new());
+        }
+        d.process_command(Command::SelectAnimation(
+            "player_idle".to_owned(),
+            false,
+            false,
+        ))
+        .unwrap();
+
+        d.process_command(Command::DeleteAnimationsNotMatching("boss".to_owned()))
+            .unwrap();
+        assert!(d.sheet.has_animation("boss_idle"));
+        assert!(d.sheet.has_animation("boss_attack"));
+        assert!(!d.sheet.has_animation("player_idle"));
+        assert!(!d.view.selection.is_animation_selected("player_idle"));
+
+        d.process_command(Command::Undo).unwrap();
+        assert_eq!(d.sheet.animations_iter().count(), 3);
+    }
+
+    #[test]
+    fn can_swap_animation_names() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1"])]),
+        );
+        d.sheet
+            .add_test_animation("run", HashMap::from([(Direction::North, vec!["run_0"])]));
+        d.process_command(Command::EditAnimation("walk".to_owned()))
+            .unwrap();
+        d.process_command(Command::SelectAnimation("walk".to_owned(), false, false))
+            .unwrap();
+
+        d.process_command(Command::SwapAnimationNames(
+            "walk".to_owned(),
+            "run".to_owned(),
+        ))
+        .unwrap();
+        let keyframe_count = |d: &Document, name: &str| {
+            d.sheet
+                .animation(name)
+                .unwrap()
+                .sequence(Direction::North)
+                .unwrap()
+                .num_keyframes()
+        };
+        assert_eq!(keyframe_count(&d, "run"), 2);
+        assert_eq!(keyframe_count(&d, "walk"), 1);
+        assert_eq!(d.current_animation().as_deref(), Some("run"));
+        assert!(d.view.selection.is_animation_selected("run"));
+        assert!(!d.view.selection.is_animation_selected("walk"));
+
+        d.process_command(Command::Undo).unwrap();
+        assert_eq!(keyframe_count(&d, "walk"), 2);
+        assert_eq!(keyframe_count(&d, "run"), 1);
+
+        assert!(d
+            .process_command(Command::SwapAnimationNames(
+                "walk".to_owned(),
+                "missing".to_owned(),
+            ))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn can_reorder_animations() {
+        let app = TigerAppMock::new();
+        app.open_documents(vec!["test-data/samurai.tiger"]).await;
+        let names = || {
+            app.client_state().documents[0]
+                .sheet
+                .animations
+                .iter()
+                .map(|a| a.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(), vec!["attack", "dead", "idle", "walk"]);
+
+        app.reorder_animations(vec!["walk", "idle", "attack", "dead"]);
+        assert_eq!(names(), vec!["walk", "idle", "attack", "dead"]);
+    }
+
+    #[tokio::test]
+    async fn can_list_degenerate_animations() {
+        let app = TigerAppMock::new();
+        app.open_documents(vec!["test-data/samurai.tiger"]).await;
+        assert_eq!(app.get_degenerate_animations(), vec!["dead"]);
+
+        app.create_animation();
+        assert_eq!(
+            app.get_degenerate_animations(),
+            vec!["dead", "New Animation"]
+        );
+    }
+
+    #[test]
+    fn can_set_selected_animations_looping() {
+        let mut d = Document::new("tmp");
+        d.sheet
+            .add_test_animation::<_, &str>("hurt_low", HashMap::new());
+        d.sheet
+            .add_test_animation::<_, &str>("hurt_high", HashMap::new());
+        d.sheet
+            .add_test_animation::<_, &str>("idle", HashMap::new());
+        for name in ["hurt_low", "hurt_high", "idle"] {
+            d.sheet.animation_mut(name).unwrap().set_looping(true);
+        }
+        d.process_command(Command::SelectAnimation(
+            "hurt_low".to_owned(),
+            false,
+            false,
+        ))
+        .unwrap();
+        d.process_command(Command::SelectAnimation(
+            "hurt_high".to_owned(),
+            false,
+            true,
+        ))
+        .unwrap();
+
+        d.process_command(Command::SetSelectedAnimationsLooping(false))
+            .unwrap();
+        assert!(!d.sheet.animation("hurt_low").unwrap().looping());
+        assert!(!d.sheet.animation("hurt_high").unwrap().looping());
+        assert!(d.sheet.animation("idle").unwrap().looping());
+
+        d.process_command(Command::Undo).unwrap();
+        assert!(d.sheet.animation("hurt_low").unwrap().looping());
+        assert!(d.sheet.animation("hurt_high").unwrap().looping());
+    }
+
+    #[test]
+    fn can_import_frames_into_animation() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk_cycle",
+            HashMap::from([(Direction::North, vec!["walk_0"])]),
+        );
+        d.process_command(Command::EditAnimation("walk_cycle".to_owned()))
+            .unwrap();
+
+        d.process_command(Command::ImportFramesIntoAnimation(vec![
+            "walk_1".into(),
+            "walk_2".into(),
+        ]))
+        .unwrap();
+
+        assert!(d.sheet.has_frame("walk_1"));
+        assert!(d.sheet.has_frame("walk_2"));
+        let sequence = d
+            .sheet
+            .animation("walk_cycle")
+            .unwrap()
+            .sequence(Direction::North)
+            .unwrap();
+        assert_eq!(sequence.num_keyframes(), 3);
+        assert_eq!(sequence.keyframe(1).unwrap().frame(), Path::new("walk_1"));
+        assert_eq!(sequence.keyframe(2).unwrap().frame(), Path::new("walk_2"));
+        assert!(d
+            .view
+            .selection
+            .is_keyframe_selected("walk_cycle", Direction::North, 2));
+    }
+
+    #[test]
+    fn cannot_import_frames_into_animation_without_animation() {
+        let mut d = Document::new("tmp");
+        let result = d.process_command(Command::ImportFramesIntoAnimation(vec!["frame".into()]));
+        assert!(result.is_err());
+        assert!(!d.sheet.has_frame("frame"));
+    }
+
+    #[test]
+    fn can_create_animation_from_selected_frames() {
+        let mut d = Document::new("tmp");
+        d.sheet
+            .add_frames(&vec!["walk_2", "walk_0", "walk_1", "idle_0"]);
+        d.process_command(Command::SelectFrame("walk_2".into(), false, false))
+            .unwrap();
+        d.process_command(Command::SelectFrame("walk_0".into(), false, true))
+            .unwrap();
+        d.process_command(Command::SelectFrame("walk_1".into(), false, true))
+            .unwrap();
+
+        d.process_command(Command::CreateAnimationFromSelectedFrames)
+            .unwrap();
+
+        let (name, animation) = d.workbench_animation().unwrap();
+        assert_eq!(name, "New Animation");
+        assert!(d.view.selection.is_animation_selected("New Animation"));
+        let frames = animation
+            .sequence(Direction::North)
+            .unwrap()
+            .keyframes_iter()
+            .map(|k| k.frame().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            frames,
+            vec![
+                PathBuf::from("walk_0"),
+                PathBuf::from("walk_1"),
+                PathBuf::from("walk_2")
+            ]
+        );
+    }
+
+    #[test]
+    fn can_repair_sheet_with_missing_frames() {
+        let mut sheet: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string("test-data/flame.tiger").unwrap())
+                .unwrap();
+        sheet["sheet"]["frames"].as_array_mut().unwrap().remove(0);
+        let path = PathBuf::from("test-output/can_repair_sheet_with_missing_frames.tiger");
+        std::fs::create_dir_all("test-output").unwrap();
+        std::fs::write(&path, serde_json::to_string(&sheet).unwrap()).unwrap();
+
+        let mut d = Document::open(&path).unwrap();
+        let missing_frames = d.sheet().missing_frames();
+        assert_eq!(missing_frames.len(), 1);
+        assert!(missing_frames[0].ends_with("flame-hit-0.png"));
+        assert!(!d.sheet().has_frame(&missing_frames[0]));
+
+        d.process_command(Command::RepairSheet).unwrap();
+        assert!(d.sheet().missing_frames().is_empty());
+        assert!(d.sheet().has_frame(&missing_frames[0]));
+
+        d.process_command(Command::Undo).unwrap();
+        assert_eq!(d.sheet().missing_frames(), missing_frames);
+    }
 }
@@ -15,6 +15,9 @@ pub enum ExportSettingsError {
     ExpectedFile,
     FileNotFound,
     TemplateError(String),
+    InvalidTextureScale,
+    InvalidTextureExtension,
+    UnsupportedRotation,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -23,6 +26,15 @@ pub struct TemplateExportSettingsValidation {
     texture_file_error: Option<ExportSettingsError>,
     metadata_file_error: Option<ExportSettingsError>,
     metadata_paths_root_error: Option<ExportSettingsError>,
+    texture_scale_error: Option<ExportSettingsError>,
+    allow_rotation_error: Option<ExportSettingsError>,
+    additional_targets: Vec<ExportTargetValidation>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ExportTargetValidation {
+    template_file_error: Option<ExportSettingsError>,
+    metadata_file_error: Option<ExportSettingsError>,
 }
 
 impl Document {
@@ -95,13 +107,120 @@ impl Document {
         Ok(())
     }
 
+    pub(super) fn set_export_packing_algorithm(
+        &mut self,
+        algorithm: PackingAlgorithm,
+    ) -> DocumentResult<()> {
+        self.template_export_settings_mut()?
+            .set_packing_algorithm(algorithm);
+        Ok(())
+    }
+
+    pub(super) fn set_export_packing_order(&mut self, order: PackingOrder) -> DocumentResult<()> {
+        self.template_export_settings_mut()?
+            .set_packing_order(order);
+        Ok(())
+    }
+
+    pub(super) fn set_export_path_style(&mut self, style: PathStyle) -> DocumentResult<()> {
+        self.template_export_settings_mut()?.set_path_style(style);
+        Ok(())
+    }
+
+    pub(super) fn set_export_deduplicate_frames(
+        &mut self,
+        deduplicate: bool,
+    ) -> DocumentResult<()> {
+        self.template_export_settings_mut()?
+            .set_deduplicate_frames(deduplicate);
+        Ok(())
+    }
+
+    pub(super) fn set_export_allow_rotation(&mut self, allow_rotation: bool) -> DocumentResult<()> {
+        self.template_export_settings_mut()?
+            .set_allow_rotation(allow_rotation);
+        Ok(())
+    }
+
+    pub(super) fn set_export_mip_safe_padding(&mut self, padding: u32) -> DocumentResult<()> {
+        self.template_export_settings_mut()?
+            .set_mip_safe_padding(padding);
+        Ok(())
+    }
+
+    pub(super) fn set_export_write_manifest(&mut self, write_manifest: bool) -> DocumentResult<()> {
+        self.template_export_settings_mut()?
+            .set_write_manifest(write_manifest);
+        Ok(())
+    }
+
+    pub(super) fn set_export_format(&mut self, format: ExportFormat) -> DocumentResult<()> {
+        self.template_export_settings_mut()?.set_format(format);
+        Ok(())
+    }
+
+    // Also changes the extension of the texture file to match the new format
+    pub(super) fn set_export_texture_format(
+        &mut self,
+        format: TextureFormat,
+    ) -> DocumentResult<()> {
+        let settings = self.template_export_settings_mut()?;
+        settings.set_texture_format(format);
+        if settings.texture_file().file_name().is_some() {
+            let texture_file = settings.texture_file().with_extension(format.extension());
+            settings.set_texture_file(texture_file);
+        }
+        Ok(())
+    }
+
+    pub(super) fn set_export_texture_scale(&mut self, scale: u32) -> DocumentResult<()> {
+        self.template_export_settings_mut()?
+            .set_texture_scale(scale);
+        Ok(())
+    }
+
+    pub(super) fn add_export_target(&mut self) -> DocumentResult<()> {
+        self.template_export_settings_mut()?.add_target();
+        Ok(())
+    }
+
+    pub(super) fn remove_export_target(&mut self, index: usize) -> DocumentResult<()> {
+        self.template_export_settings_mut()?.remove_target(index)?;
+        Ok(())
+    }
+
+    pub(super) fn set_export_target_template_file<T: AsRef<Path>>(
+        &mut self,
+        index: usize,
+        file: T,
+    ) -> DocumentResult<()> {
+        self.template_export_settings_mut()?
+            .target_mut(index)?
+            .set_template_file(file);
+        Ok(())
+    }
+
+    pub(super) fn set_export_target_metadata_file<T: AsRef<Path>>(
+        &mut self,
+        index: usize,
+        file: T,
+    ) -> DocumentResult<()> {
+        self.template_export_settings_mut()?
+            .target_mut(index)?
+            .set_metadata_file(file);
+        Ok(())
+    }
+
     pub fn validate_export_settings(&self) -> DocumentResult<ExportSettingsValidation> {
-        let validation = match self.export_settings_edit()? {
+        Ok(self.validate_settings(self.export_settings_edit()?))
+    }
+
+    fn validate_settings(&self, settings: &ExportSettings<Any>) -> ExportSettingsValidation {
+        match settings {
             ExportSettings::Template(s) => {
                 ExportSettingsValidation::Template(self.validate_template_export_settings(s))
             }
-        };
-        Ok(validation)
+        }
     }
 
     fn validate_template_export_settings(
@@ -109,12 +228,27 @@ impl Document {
         settings: &TemplateExportSettings<Any>,
     ) -> TemplateExportSettingsValidation {
         TemplateExportSettingsValidation {
-            template_file_error: validate_template_path(settings.template_file()),
-            texture_file_error: validate_output_file_path(settings.texture_file()),
+            template_file_error: match settings.format() {
+                ExportFormat::Template => validate_template_path(settings.template_file()),
+                ExportFormat::GodotSpriteFrames | ExportFormat::Css => None,
+            },
+            texture_file_error: validate_output_file_path(settings.texture_file()).or_else(|| {
+                validate_texture_extension(settings.texture_file(), settings.texture_format())
+            }),
             metadata_file_error: validate_output_file_path(settings.metadata_file()),
             metadata_paths_root_error: validate_output_directory_path(
                 settings.metadata_paths_root(),
             ),
+            texture_scale_error: validate_texture_scale(settings.texture_scale()),
+            allow_rotation_error: validate_allow_rotation(settings),
+            additional_targets: settings
+                .additional_targets()
+                .iter()
+                .map(|target| ExportTargetValidation {
+                    template_file_error: validate_template_path(target.template_file()),
+                    metadata_file_error: validate_output_file_path(target.metadata_file()),
+                })
+                .collect(),
         }
     }
 
@@ -127,6 +261,27 @@ impl Document {
         self.persistent.export_settings_edit = None;
         Ok(())
     }
+
+    // Applies complete export settings without going through the export dialog
+    pub(super) fn set_export_settings(
+        &mut self,
+        export_settings: ExportSettings<Any>,
+    ) -> DocumentResult<()> {
+        if !self.validate_settings(&export_settings).is_valid() {
+            return Err(DocumentError::InvalidExportSettings);
+        }
+        self.sheet
+            .set_export_settings(export_settings.with_absolute_paths()?);
+        Ok(())
+    }
+}
+
+impl ExportSettingsValidation {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            ExportSettingsValidation::Template(s) => s.is_valid(),
+        }
+    }
 }
 
 impl TemplateExportSettingsValidation {
@@ -145,6 +300,42 @@ impl TemplateExportSettingsValidation {
     pub fn metadata_paths_root_error(&self) -> Option<&ExportSettingsError> {
         self.metadata_paths_root_error.as_ref()
     }
+
+    pub fn texture_scale_error(&self) -> Option<&ExportSettingsError> {
+        self.texture_scale_error.as_ref()
+    }
+
+    pub fn allow_rotation_error(&self) -> Option<&ExportSettingsError> {
+        self.allow_rotation_error.as_ref()
+    }
+
+    pub fn additional_targets(&self) -> &[ExportTargetValidation] {
+        &self.additional_targets
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.template_file_error.is_none()
+            && self.texture_file_error.is_none()
+            && self.metadata_file_error.is_none()
+            && self.metadata_paths_root_error.is_none()
+            && self.texture_scale_error.is_none()
+            && self.allow_rotation_error.is_none()
+            && self.additional_targets.iter().all(|t| t.is_valid())
+    }
+}
+
+impl ExportTargetValidation {
+    pub fn template_file_error(&self) -> Option<&ExportSettingsError> {
+        self.template_file_error.as_ref()
+    }
+
+    pub fn metadata_file_error(&self) -> Option<&ExportSettingsError> {
+        self.metadata_file_error.as_ref()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.template_file_error.is_none() && self.metadata_file_error.is_none()
+    }
 }
 
 fn validate_template_path(path: &Path) -> Option<ExportSettingsError> {
@@ -181,6 +372,34 @@ fn validate_output_directory_path(p: &Path) -> Option<ExportSettingsError> {
     }
 }
 
+fn validate_texture_extension(p: &Path, format: TextureFormat) -> Option<ExportSettingsError> {
+    match p.extension() {
+        Some(e) if e.eq_ignore_ascii_case(format.extension()) => None,
+        _ => Some(ExportSettingsError::InvalidTextureExtension),
+    }
+}
+
+fn validate_texture_scale(scale: u32) -> Option<ExportSettingsError> {
+    if scale < 1 {
+        Some(ExportSettingsError::InvalidTextureScale)
+    } else {
+        None
+    }
+}
+
+// Only templates can describe rotated frames, other formats expect frames to be packed upright
+fn validate_allow_rotation(settings: &TemplateExportSettings<Any>) -> Option<ExportSettingsError> {
+    let supports_rotation = match settings.format() {
+        ExportFormat::Template => true,
+        ExportFormat::GodotSpriteFrames | ExportFormat::Css => false,
+    };
+    if settings.allow_rotation() && !supports_rotation {
+        Some(ExportSettingsError::UnsupportedRotation)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -196,7 +415,10 @@ mod test {
                 template_file_error: Some(ExportSettingsError::ExpectedAbsolutePath),
                 texture_file_error: Some(ExportSettingsError::ExpectedAbsolutePath),
                 metadata_file_error: Some(ExportSettingsError::ExpectedAbsolutePath),
-                metadata_paths_root_error: Some(ExportSettingsError::ExpectedAbsolutePath)
+                metadata_paths_root_error: Some(ExportSettingsError::ExpectedAbsolutePath),
+                texture_scale_error: None,
+                allow_rotation_error: None,
+                additional_targets: vec![],
             })
         );
     }
@@ -216,7 +438,10 @@ mod test {
                 template_file_error: Some(ExportSettingsError::ExpectedAbsolutePath),
                 texture_file_error: Some(ExportSettingsError::ExpectedAbsolutePath),
                 metadata_file_error: Some(ExportSettingsError::ExpectedAbsolutePath),
-                metadata_paths_root_error: Some(ExportSettingsError::ExpectedAbsolutePath)
+                metadata_paths_root_error: Some(ExportSettingsError::ExpectedAbsolutePath),
+                texture_scale_error: None,
+                allow_rotation_error: None,
+                additional_targets: vec![],
             })
         );
     }
@@ -239,7 +464,10 @@ mod test {
                 template_file_error: Some(ExportSettingsError::ExpectedFile),
                 texture_file_error: Some(ExportSettingsError::ExpectedFile),
                 metadata_file_error: Some(ExportSettingsError::ExpectedFile),
-                metadata_paths_root_error: Some(ExportSettingsError::ExpectedDirectory)
+                metadata_paths_root_error: Some(ExportSettingsError::ExpectedDirectory),
+                texture_scale_error: None,
+                allow_rotation_error: None,
+                additional_targets: vec![],
             })
         );
     }
@@ -264,4 +492,159 @@ mod test {
             assert!(test(validation.template_file_error));
         }
     }
+
+    #[test]
+    fn validates_texture_scale() {
+        let mut d = Document::new("tmp.tiger");
+        d.begin_export_as();
+        d.set_export_texture_scale(0).unwrap();
+        let ExportSettingsValidation::Template(validation) = d.validate_export_settings().unwrap();
+        assert_eq!(
+            validation.texture_scale_error,
+            Some(ExportSettingsError::InvalidTextureScale)
+        );
+
+        d.set_export_texture_scale(2).unwrap();
+        let ExportSettingsValidation::Template(validation) = d.validate_export_settings().unwrap();
+        assert!(validation.texture_scale_error.is_none());
+    }
+
+    #[test]
+    fn validates_rotation_for_godot_sprite_frames() {
+        let mut d = Document::new("tmp.tiger");
+        d.begin_export_as();
+        d.set_export_allow_rotation(true).unwrap();
+        let ExportSettingsValidation::Template(validation) = d.validate_export_settings().unwrap();
+        assert!(validation.allow_rotation_error.is_none());
+
+        d.set_export_format(ExportFormat::GodotSpriteFrames)
+            .unwrap();
+        let ExportSettingsValidation::Template(validation) = d.validate_export_settings().unwrap();
+        assert_eq!(
+            validation.allow_rotation_error,
+            Some(ExportSettingsError::UnsupportedRotation)
+        );
+
+        d.set_export_allow_rotation(false).unwrap();
+        let ExportSettingsValidation::Template(validation) = d.validate_export_settings().unwrap();
+        assert!(validation.allow_rotation_error.is_none());
+    }
+
+    #[test]
+    fn validates_rotation_for_css_sprites() {
+        let mut d = Document::new("tmp.tiger");
+        d.begin_export_as();
+        d.set_export_format(ExportFormat::Css).unwrap();
+        let ExportSettingsValidation::Template(validation) = d.validate_export_settings().unwrap();
+        assert!(validation.allow_rotation_error.is_none());
+
+        d.set_export_allow_rotation(true).unwrap();
+        let ExportSettingsValidation::Template(validation) = d.validate_export_settings().unwrap();
+        assert_eq!(
+            validation.allow_rotation_error,
+            Some(ExportSettingsError::UnsupportedRotation)
+        );
+    }
+
+    #[test]
+    fn validates_texture_extension() {
+        let mut d = Document::new("tmp.tiger");
+        let output = std::env::current_dir().unwrap().join("test-output");
+        d.begin_export_as();
+        d.set_export_texture_file(output.join("sheet.png")).unwrap();
+        let ExportSettingsValidation::Template(validation) = d.validate_export_settings().unwrap();
+        assert!(validation.texture_file_error.is_none());
+
+        d.process_command(Command::SetExportTextureFormat(TextureFormat::Qoi))
+            .unwrap();
+        let settings = d.template_export_settings_mut().unwrap();
+        assert_eq!(settings.texture_file(), output.join("sheet.qoi"));
+        let ExportSettingsValidation::Template(validation) = d.validate_export_settings().unwrap();
+        assert!(validation.texture_file_error.is_none());
+
+        d.set_export_texture_file(output.join("sheet.png")).unwrap();
+        let ExportSettingsValidation::Template(validation) = d.validate_export_settings().unwrap();
+        assert_eq!(
+            validation.texture_file_error,
+            Some(ExportSettingsError::InvalidTextureExtension)
+        );
+    }
+
+    #[test]
+    fn validates_additional_export_targets() {
+        let mut d = Document::new("tmp.tiger");
+        let template = PathBuf::from("test-data/export.template")
+            .canonicalize()
+            .unwrap();
+        d.begin_export_as();
+        d.process_command(Command::AddExportTarget).unwrap();
+        d.process_command(Command::AddExportTarget).unwrap();
+        d.process_command(Command::SetExportTargetTemplateFile(0, template))
+            .unwrap();
+        d.process_command(Command::SetExportTargetMetadataFile(
+            0,
+            "relative.json".into(),
+        ))
+        .unwrap();
+        d.process_command(Command::RemoveExportTarget(1)).unwrap();
+
+        let ExportSettingsValidation::Template(validation) = d.validate_export_settings().unwrap();
+        assert_eq!(
+            validation.additional_targets,
+            vec![ExportTargetValidation {
+                template_file_error: None,
+                metadata_file_error: Some(ExportSettingsError::ExpectedAbsolutePath),
+            }]
+        );
+        assert!(d.process_command(Command::RemoveExportTarget(1)).is_err());
+    }
+
+    #[test]
+    fn can_set_complete_export_settings() {
+        let mut d = Document::new("tmp.tiger");
+        let template = PathBuf::from("test-data/export.template")
+            .canonicalize()
+            .unwrap();
+        let output = std::env::current_dir().unwrap().join("test-output");
+
+        let mut settings = TemplateExportSettings::<Any>::default();
+        settings.set_template_file(&template);
+        settings.set_texture_file(output.join("sheet.png"));
+        settings.set_metadata_file(output.join("sheet.json"));
+        settings.set_metadata_paths_root(&output);
+        settings.set_texture_scale(2);
+        settings.set_packing_algorithm(PackingAlgorithm::Shelf);
+        settings.set_packing_order(PackingOrder::Sheet);
+        settings.set_path_style(PathStyle::BasenameOnly);
+        settings.set_deduplicate_frames(true);
+        settings.set_write_manifest(true);
+        settings.add_target();
+        settings.target_mut(0).unwrap().set_template_file(&template);
+        settings
+            .target_mut(0)
+            .unwrap()
+            .set_metadata_file(output.join("other.json"));
+        let settings = ExportSettings::Template(settings);
+
+        d.process_command(Command::SetExportSettings(settings.clone()))
+            .unwrap();
+        assert!(d.export_settings_edit().is_err());
+        assert_eq!(
+            d.sheet().export_settings().as_ref(),
+            Some(&settings.with_absolute_paths().unwrap())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_export_settings() {
+        let mut d = Document::new("tmp.tiger");
+        let mut settings = TemplateExportSettings::<Any>::default();
+        settings.set_template_file("relative/path.template");
+        assert!(d
+            .process_command(Command::SetExportSettings(ExportSettings::Template(
+                settings
+            )))
+            .is_err());
+        assert!(d.sheet().export_settings().is_none());
+    }
 }
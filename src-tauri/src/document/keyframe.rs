@@ -1,16 +1,113 @@
-use euclid::{default::Vector2D, vec2};
+use euclid::{
+    default::{Rect, Vector2D},
+    vec2,
+};
 use std::time::Duration;
 
 use crate::document::*;
 
+const DUPLICATE_HITBOX_OFFSET: Vector2D<i32> = Vector2D::new(4, 4);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+    Top,
+    Bottom,
+}
+
 impl Document {
     pub(super) fn set_keyframe_duration(&mut self, duration: Duration) -> DocumentResult<()> {
+        if self.persistent.precise_durations {
+            let duration = duration.max(self.view.min_keyframe_duration).as_micros() as u64;
+            for (_, _, keyframe) in self.selected_keyframes_mut()? {
+                keyframe.set_duration_micros(duration);
+            }
+        } else {
+            let duration = self.clamp_keyframe_duration(duration.as_millis() as u64);
+            for (_, _, keyframe) in self.selected_keyframes_mut()? {
+                keyframe.set_duration_millis(duration);
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn clamp_keyframe_duration(&self, duration_millis: u64) -> u64 {
+        duration_millis.max(self.view.min_keyframe_duration.as_millis() as u64)
+    }
+
+    pub(super) fn set_keyframe_hold(&mut self, hold: u8) -> DocumentResult<()> {
         for (_, _, keyframe) in self.selected_keyframes_mut()? {
-            keyframe.set_duration_millis(duration.as_millis() as u64);
+            keyframe.set_hold(hold);
+        }
+        Ok(())
+    }
+
+    // Durations are rounded to the nearest multiple of the snapping base duration,
+    // with halfway values rounding up (75ms becomes 100ms with a 50ms base).
+    pub(super) fn quantize_keyframe_durations(&mut self) -> DocumentResult<()> {
+        let base = (self.view.keyframe_snapping_base_duration.as_millis() as u64).max(1);
+        let min_duration = self.clamp_keyframe_duration(0);
+        let (_, animation) = self.workbench_animation_mut()?;
+        for (_, sequence) in animation.sequences_iter_mut() {
+            for keyframe in sequence.keyframes_iter_mut() {
+                let multiple = ((keyframe.duration_millis() + base / 2) / base).max(1);
+                keyframe.set_duration_millis((multiple * base).max(min_duration));
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn set_uniform_keyframe_duration(
+        &mut self,
+        duration: Duration,
+    ) -> DocumentResult<()> {
+        let duration = self.clamp_keyframe_duration(duration.as_millis() as u64);
+        let (_, animation) = self.workbench_animation_mut()?;
+        for (_, sequence) in animation.sequences_iter_mut() {
+            for keyframe in sequence.keyframes_iter_mut() {
+                keyframe.set_duration_millis(duration);
+            }
         }
         Ok(())
     }
 
+    pub(super) fn set_keyframe_duration_in_frames(
+        &mut self,
+        frames: u32,
+        fps: f64,
+    ) -> DocumentResult<()> {
+        if fps <= 0.0 {
+            return Err(DocumentError::InvalidFrameRate);
+        }
+        let duration = match self.persistent.precise_durations {
+            true => frames_to_precise_duration(frames, fps),
+            false => frames_to_duration(frames, fps),
+        };
+        self.set_keyframe_duration(duration)
+    }
+
+    pub fn keyframe_durations_in_frames(
+        &self,
+        fps: f64,
+    ) -> DocumentResult<HashMap<Direction, Vec<f64>>> {
+        if fps <= 0.0 {
+            return Err(DocumentError::InvalidFrameRate);
+        }
+        let (_, animation) = self.workbench_animation()?;
+        Ok(animation
+            .sequences_iter()
+            .map(|(direction, sequence)| {
+                let frames = sequence
+                    .keyframes_iter()
+                    .map(|k| duration_to_frames(k.duration_micros(), fps))
+                    .collect();
+                (*direction, frames)
+            })
+            .collect())
+    }
+
     pub(super) fn set_keyframe_offset_x(&mut self, x: i32) -> DocumentResult<()> {
         for (_, _, keyframe) in self.selected_keyframes_mut()? {
             Document::nudge_keyframe(keyframe, vec2(x, keyframe.offset().y));
@@ -25,6 +122,101 @@ impl Document {
         Ok(())
     }
 
+    pub(super) fn translate_selected_keyframes(
+        &mut self,
+        delta: Vector2D<i32>,
+    ) -> DocumentResult<()> {
+        for (_, _, keyframe) in self.selected_keyframes_mut()? {
+            Document::nudge_keyframe(keyframe, keyframe.offset() + delta);
+        }
+        Ok(())
+    }
+
+    pub(super) fn set_animation_frame(&mut self, frame: &Path) -> DocumentResult<()> {
+        if !self.sheet.has_frame(frame) {
+            return Err(DocumentError::FrameNotInDocument(frame.to_owned()));
+        }
+        let (_, animation) = self.workbench_animation_mut()?;
+        for (_, sequence) in animation.sequences_iter_mut() {
+            for keyframe in sequence.keyframes_iter_mut() {
+                keyframe.set_frame(frame);
+            }
+        }
+        Ok(())
+    }
+
+    // Animations have no duration setting of their own, so keyframes are reset to the duration
+    // they were created with.
+    pub(super) fn reset_keyframe_duration(&mut self) -> DocumentResult<()> {
+        let duration = self.clamp_keyframe_duration(DEFAULT_KEYFRAME_DURATION_MILLIS);
+        for (_, _, keyframe) in self.selected_keyframes_mut()? {
+            keyframe.set_duration_millis(duration);
+        }
+        Ok(())
+    }
+
+    pub(super) fn reset_selected_keyframe_offsets(&mut self) -> DocumentResult<()> {
+        for (_, _, keyframe) in self.selected_keyframes_mut()? {
+            Document::nudge_keyframe(keyframe, Vector2D::zero());
+        }
+        Ok(())
+    }
+
+    pub(super) fn align_keyframe_offsets(
+        &mut self,
+        align: Align,
+        frame_sizes: &HashMap<PathBuf, Vector2D<u32>>,
+    ) -> DocumentResult<()> {
+        let mut keyframes = self
+            .selected_keyframes_mut()?
+            .into_iter()
+            .filter_map(|(_, _, keyframe)| {
+                let size = frame_sizes.get(keyframe.frame())?.to_i32();
+                Some((keyframe, size))
+            })
+            .collect::<Vec<_>>();
+
+        let Some(bounds) = keyframes
+            .iter()
+            .map(|(keyframe, size)| {
+                let top_left = keyframe.offset() - *size / 2;
+                Rect::new(top_left.to_point(), size.to_size())
+            })
+            .reduce(|a, b| a.union(&b))
+        else {
+            return Ok(());
+        };
+
+        for (keyframe, size) in keyframes.iter_mut() {
+            let offset = keyframe.offset();
+            let half_size = *size / 2;
+            let new_offset = match align {
+                Align::Left => vec2(bounds.min_x() + half_size.x, offset.y),
+                Align::Center => vec2(bounds.center().x, offset.y),
+                Align::Right => vec2(bounds.max_x() - size.x + half_size.x, offset.y),
+                Align::Top => vec2(offset.x, bounds.min_y() + half_size.y),
+                Align::Bottom => vec2(offset.x, bounds.max_y() - size.y + half_size.y),
+            };
+            Document::nudge_keyframe(keyframe, new_offset);
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn add_keyframe_event<T: AsRef<str>>(&mut self, name: T) -> DocumentResult<()> {
+        for (_, _, keyframe) in self.selected_keyframes_mut()? {
+            keyframe.add_event(&name);
+        }
+        Ok(())
+    }
+
+    pub(super) fn remove_keyframe_event<T: AsRef<str>>(&mut self, name: T) -> DocumentResult<()> {
+        for (_, _, keyframe) in self.selected_keyframes_mut()? {
+            keyframe.remove_event(&name);
+        }
+        Ok(())
+    }
+
     pub(super) fn create_hitbox(&mut self, position: Option<Vector2D<i32>>) -> DocumentResult<()> {
         let (animation_name, _) = self.workbench_animation()?;
         let animation_name = animation_name.clone();
@@ -51,12 +243,108 @@ impl Document {
         Ok(())
     }
 
+    pub(super) fn prefix_hitbox_names<T: AsRef<str>>(&mut self, prefix: T) -> DocumentResult<()> {
+        self.rename_keyframe_hitboxes(|name| format!("{}{name}", prefix.as_ref()))
+    }
+
+    pub(super) fn suffix_hitbox_names<T: AsRef<str>>(&mut self, suffix: T) -> DocumentResult<()> {
+        self.rename_keyframe_hitboxes(|name| format!("{name}{}", suffix.as_ref()))
+    }
+
+    fn rename_keyframe_hitboxes<F: Fn(&str) -> String>(&mut self, rename: F) -> DocumentResult<()> {
+        let (animation_name, _) = self.workbench_animation()?;
+        let animation_name = animation_name.clone();
+        let ((direction, index), keyframe) = self.workbench_keyframe_mut()?;
+
+        // Longer names are renamed first, so new names never collide with a hitbox which is
+        // yet to be renamed (e.g. `atk_a` becomes `atk_atk_a` before `a` becomes `atk_a`)
+        let mut old_names = keyframe
+            .hitboxes_iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        old_names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+
+        let mut new_names = HashMap::new();
+        for old_name in old_names {
+            let new_name = rename(&old_name);
+            keyframe.rename_hitbox(&old_name, &new_name)?;
+            new_names.insert(old_name, new_name);
+        }
+
+        let selected_hitboxes = self
+            .view
+            .selection
+            .hitboxes()
+            .cloned()
+            .map(|(a, d, i, name)| {
+                let name = match a == animation_name && d == direction && i == index {
+                    true => new_names.get(&name).cloned().unwrap_or(name),
+                    false => name,
+                };
+                (a, d, i, name)
+            })
+            .collect::<Vec<_>>();
+        self.select_hitboxes_only(selected_hitboxes);
+        Ok(())
+    }
+
     pub(super) fn delete_hitbox<T: AsRef<str>>(&mut self, name: T) -> DocumentResult<()> {
         let (_, keyframe) = self.workbench_keyframe_mut()?;
         keyframe.delete_hitbox(&name);
         Ok(())
     }
 
+    // Covers every keyframe of every direction, not just the keyframe under the playhead
+    pub(super) fn delete_hitbox_from_all_keyframes<T: AsRef<str>>(
+        &mut self,
+        name: T,
+    ) -> DocumentResult<()> {
+        let (_, animation) = self.workbench_animation_mut()?;
+        for (_, sequence) in animation.sequences_iter_mut() {
+            for keyframe in sequence.keyframes_iter_mut() {
+                keyframe.delete_hitbox(&name);
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn create_anchor(&mut self, position: Option<Vector2D<i32>>) -> DocumentResult<()> {
+        let (_, keyframe) = self.workbench_keyframe_mut()?;
+        keyframe.create_anchor("New Anchor", position.unwrap_or_default());
+        Ok(())
+    }
+
+    pub(super) fn set_anchor_position<T: AsRef<str>>(
+        &mut self,
+        name: T,
+        position: Vector2D<i32>,
+    ) -> DocumentResult<()> {
+        let (_, keyframe) = self.workbench_keyframe_mut()?;
+        Ok(keyframe.set_anchor_position(name, position)?)
+    }
+
+    pub(super) fn rename_anchor<T: AsRef<str>, U: AsRef<str>>(
+        &mut self,
+        old_name: T,
+        new_name: U,
+    ) -> DocumentResult<()> {
+        let (_, keyframe) = self.workbench_keyframe_mut()?;
+        Ok(keyframe.rename_anchor(old_name, new_name)?)
+    }
+
+    pub(super) fn delete_anchor<T: AsRef<str>>(&mut self, name: T) -> DocumentResult<()> {
+        let (_, keyframe) = self.workbench_keyframe_mut()?;
+        keyframe.delete_anchor(name);
+        Ok(())
+    }
+
+    pub(super) fn delete_all_hitboxes_on_keyframe(&mut self) {
+        if let Ok((_, keyframe)) = self.workbench_keyframe_mut() {
+            keyframe.delete_all_hitboxes();
+        }
+        self.view.selection.hitboxes.clear();
+    }
+
     pub(super) fn delete_selected_hitboxes(&mut self) -> DocumentResult<()> {
         let selected_hitboxes = self
             .view
@@ -72,6 +360,93 @@ impl Document {
         Ok(())
     }
 
+    // Copies are offset from their originals so they don't render exactly on top of them.
+    pub(super) fn duplicate_selected_hitboxes(&mut self) -> DocumentResult<()> {
+        let (animation_name, _) = self.workbench_animation()?;
+        let animation_name = animation_name.clone();
+        let mut selected_hitboxes = self
+            .view
+            .selection
+            .hitboxes()
+            .map(|(_, _, _, h)| h.clone())
+            .collect::<Vec<_>>();
+        selected_hitboxes.sort();
+
+        let ((direction, index), keyframe) = self.workbench_keyframe_mut()?;
+        let mut new_hitbox_names = vec![];
+        for hitbox_name in selected_hitboxes {
+            let Some(hitbox) = keyframe.hitbox(&hitbox_name).map(|h| h.duplicate()) else {
+                continue;
+            };
+            let (new_hitbox_name, new_hitbox) = keyframe.create_hitbox(&hitbox_name);
+            *new_hitbox = hitbox;
+            new_hitbox.set_position(new_hitbox.position() + DUPLICATE_HITBOX_OFFSET);
+            new_hitbox_names.push(new_hitbox_name);
+        }
+
+        if !new_hitbox_names.is_empty() {
+            self.select_hitboxes_only(
+                new_hitbox_names
+                    .into_iter()
+                    .map(|n| (animation_name.clone(), direction, index, n)),
+            );
+        }
+        Ok(())
+    }
+
+    // Cells in the last column and row absorb leftover pixels when the hitbox size is not a
+    // multiple of the number of columns or rows.
+    pub(super) fn subdivide_hitbox<T: AsRef<str>>(
+        &mut self,
+        name: T,
+        columns: u32,
+        rows: u32,
+    ) -> DocumentResult<()> {
+        let (animation_name, _) = self.workbench_animation()?;
+        let animation_name = animation_name.clone();
+        let ((direction, index), keyframe) = self.workbench_keyframe_mut()?;
+        let hitbox = keyframe
+            .hitbox(&name)
+            .ok_or_else(|| SheetError::HitboxNotFound(name.as_ref().to_owned()))?
+            .duplicate();
+        let (position, size) = (hitbox.position(), hitbox.size());
+        if columns == 0 || rows == 0 || columns > size.x || rows > size.y {
+            return Err(DocumentError::InvalidHitboxSubdivision(columns, rows));
+        }
+
+        keyframe.delete_hitbox(&name);
+        let cell_size = vec2(size.x / columns, size.y / rows);
+        let mut new_hitbox_names = vec![];
+        for row in 0..rows {
+            for column in 0..columns {
+                let cell_name = format!("{} {}", name.as_ref(), row * columns + column + 1);
+                let (cell_name, cell) = keyframe.create_hitbox(cell_name);
+                *cell = hitbox.duplicate();
+                cell.set_position(
+                    position + vec2(column * cell_size.x, row * cell_size.y).to_i32(),
+                );
+                cell.set_size(vec2(
+                    match column == columns - 1 {
+                        true => size.x - cell_size.x * (columns - 1),
+                        false => cell_size.x,
+                    },
+                    match row == rows - 1 {
+                        true => size.y - cell_size.y * (rows - 1),
+                        false => cell_size.y,
+                    },
+                ));
+                new_hitbox_names.push(cell_name);
+            }
+        }
+
+        self.select_hitboxes_only(
+            new_hitbox_names
+                .into_iter()
+                .map(|n| (animation_name.clone(), direction, index, n)),
+        );
+        Ok(())
+    }
+
     pub(super) fn set_hitbox_position_x(&mut self, x: i32) -> DocumentResult<()> {
         for (_, hitbox) in self.selected_hitboxes_mut()? {
             let new_position = vec2(x, hitbox.position().y);
@@ -88,6 +463,13 @@ impl Document {
         Ok(())
     }
 
+    pub(super) fn set_hitbox_kind(&mut self, kind: HitboxKind) -> DocumentResult<()> {
+        for (_, hitbox) in self.selected_hitboxes_mut()? {
+            hitbox.set_kind(kind);
+        }
+        Ok(())
+    }
+
     pub(super) fn set_hitbox_width(&mut self, new_width: u32) -> DocumentResult<()> {
         let preserve_ar = self.persistent.preserve_aspect_ratio;
         for (_, hitbox) in self.selected_hitboxes_mut()? {
@@ -101,6 +483,47 @@ impl Document {
         Ok(())
     }
 
+    // The last hitbox the user interacted with serves as the reference size
+    pub(super) fn match_hitbox_sizes(&mut self) -> DocumentResult<()> {
+        let Some((animation_name, direction, index, hitbox_name)) =
+            self.view.selection.last_interacted_hitbox().clone()
+        else {
+            return Ok(());
+        };
+        let Some(size) = self
+            .sheet
+            .animation(&animation_name)
+            .and_then(|a| a.sequence(direction))
+            .and_then(|s| s.keyframe(index))
+            .and_then(|k| k.hitbox(&hitbox_name))
+            .map(|h| h.size())
+        else {
+            return Ok(());
+        };
+        for (_, hitbox) in self.selected_hitboxes_mut()? {
+            hitbox.set_size(size);
+        }
+        Ok(())
+    }
+
+    pub(super) fn flip_hitboxes_horizontally(
+        &mut self,
+        frame_sizes: &HashMap<PathBuf, Vector2D<u32>>,
+    ) -> DocumentResult<()> {
+        let (_, keyframe) = self.workbench_keyframe_mut()?;
+        let Some(size) = frame_sizes.get(keyframe.frame()) else {
+            return Ok(());
+        };
+        let left = keyframe.offset().x - size.to_i32().x / 2;
+        let right = left + size.x as i32;
+        for (_, hitbox) in keyframe.hitboxes_iter_mut() {
+            let position = hitbox.position();
+            let new_x = left + right - position.x - hitbox.size().x as i32;
+            hitbox.set_position(vec2(new_x, position.y));
+        }
+        Ok(())
+    }
+
     pub(super) fn set_hitbox_height(&mut self, new_height: u32) -> DocumentResult<()> {
         let preserve_ar = self.persistent.preserve_aspect_ratio;
         for (_, hitbox) in self.selected_hitboxes_mut()? {
@@ -115,12 +538,421 @@ impl Document {
     }
 }
 
+fn frames_to_duration(frames: u32, fps: f64) -> Duration {
+    Duration::from_millis((frames as f64 * 1_000.0 / fps).round() as u64)
+}
+
+fn frames_to_precise_duration(frames: u32, fps: f64) -> Duration {
+    Duration::from_micros((frames as f64 * 1_000_000.0 / fps).round() as u64)
+}
+
+// Durations obtained from a whole number of frames are reported as that exact
+// number, despite the rounding in `frames_to_duration` and `frames_to_precise_duration`.
+fn duration_to_frames(duration_micros: u64, fps: f64) -> f64 {
+    let frames = duration_micros as f64 * fps / 1_000_000.0;
+    let whole_frames = frames.round();
+    let duration = Duration::from_micros(duration_micros);
+    if frames_to_duration(whole_frames as u32, fps) == duration
+        || frames_to_precise_duration(whole_frames as u32, fps) == duration
+    {
+        whole_frames
+    } else {
+        frames
+    }
+}
+
 #[cfg(test)]
 mod test {
 
+    use euclid::{default::Vector2D, vec2};
+
+    use crate::document::*;
     use crate::dto;
     use crate::mock::TigerAppMock;
 
+    #[test]
+    fn keyframe_events_persist_after_save() {
+        let mut d = Document::open("test-data/samurai.tiger").unwrap();
+        d.process_command(Command::EditAnimation("attack".to_owned()))
+            .unwrap();
+        d.process_command(Command::SelectKeyframe(Direction::East, 0, false, false))
+            .unwrap();
+        d.process_command(Command::AddKeyframeEvent("spawn_projectile".to_owned()))
+            .unwrap();
+
+        let destination = PathBuf::from("test-output/keyframe_events_persist_after_save.tiger");
+        d.sheet().clone().write(&destination).unwrap();
+
+        let reopened = Document::open(&destination).unwrap();
+        let keyframe = reopened
+            .sheet()
+            .animation("attack")
+            .and_then(|a| a.sequence(Direction::East))
+            .and_then(|s| s.keyframe(0))
+            .unwrap();
+        assert!(keyframe.has_event("spawn_projectile"));
+    }
+
+    #[test]
+    fn anchors_persist_after_save() {
+        let mut d = Document::open("test-data/samurai.tiger").unwrap();
+        d.process_command(Command::EditAnimation("attack".to_owned()))
+            .unwrap();
+        d.process_command(Command::SelectDirection(Direction::East))
+            .unwrap();
+        d.process_command(Command::CreateAnchor(Some(vec2(4, -12))))
+            .unwrap();
+        d.process_command(Command::RenameAnchor(
+            "New Anchor".to_owned(),
+            "muzzle".to_owned(),
+        ))
+        .unwrap();
+        d.process_command(Command::SetAnchorPosition(
+            "muzzle".to_owned(),
+            vec2(6, -10),
+        ))
+        .unwrap();
+
+        let destination = PathBuf::from("test-output/anchors_persist_after_save.tiger");
+        d.sheet().clone().write(&destination).unwrap();
+
+        let reopened = Document::open(&destination).unwrap();
+        let keyframe = reopened
+            .sheet()
+            .animation("attack")
+            .and_then(|a| a.sequence(Direction::East))
+            .and_then(|s| s.keyframe(0))
+            .unwrap();
+        assert_eq!(keyframe.anchor("muzzle"), Some(vec2(6, -10)));
+        assert!(!keyframe.has_anchor("New Anchor"));
+    }
+
+    #[test]
+    fn can_quantize_keyframe_durations() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([
+                (Direction::North, vec!["walk_0", "walk_1", "walk_2"]),
+                (Direction::South, vec!["walk_3", "walk_4"]),
+            ]),
+        );
+        for (index, duration) in [73, 121, 100].into_iter().enumerate() {
+            d.sheet
+                .keyframe_mut("walk", Direction::North, index)
+                .set_duration_millis(duration);
+        }
+        for (index, duration) in [75, 10].into_iter().enumerate() {
+            d.sheet
+                .keyframe_mut("walk", Direction::South, index)
+                .set_duration_millis(duration);
+        }
+        d.process_command(Command::EditAnimation("walk".to_owned()))
+            .unwrap();
+        d.process_command(Command::SelectDirection(Direction::North))
+            .unwrap();
+        d.process_command(Command::SetKeyframeSnappingBaseDuration(
+            Duration::from_millis(50),
+        ))
+        .unwrap();
+        d.process_command(Command::ScrubTimeline(Duration::from_millis(290)))
+            .unwrap();
+
+        d.process_command(Command::QuantizeKeyframeDurations)
+            .unwrap();
+        let durations = |d: &Document, direction| {
+            d.sheet
+                .sequence("walk", direction)
+                .keyframes_iter()
+                .map(|k| k.duration_millis())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(durations(&d, Direction::North), vec![50, 100, 100]);
+        assert_eq!(durations(&d, Direction::South), vec![100, 50]);
+        assert_eq!(d.timeline_clock(), Duration::from_millis(250));
+
+        d.process_command(Command::Undo).unwrap();
+        assert_eq!(durations(&d, Direction::North), vec![73, 121, 100]);
+        assert_eq!(durations(&d, Direction::South), vec![75, 10]);
+    }
+
+    #[test]
+    fn can_set_uniform_keyframe_duration() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1", "walk_2"])]),
+        );
+        for (index, duration) in [50, 200, 75].into_iter().enumerate() {
+            d.sheet
+                .keyframe_mut("walk", Direction::North, index)
+                .set_duration_millis(duration);
+        }
+        d.process_command(Command::EditAnimation("walk".to_owned()))
+            .unwrap();
+        d.process_command(Command::SelectDirection(Direction::North))
+            .unwrap();
+        d.process_command(Command::ScrubTimeline(Duration::from_millis(320)))
+            .unwrap();
+
+        d.process_command(Command::SetUniformKeyframeDuration(Duration::from_millis(
+            100,
+        )))
+        .unwrap();
+        let durations = |d: &Document| {
+            d.sheet
+                .sequence("walk", Direction::North)
+                .keyframes_iter()
+                .map(|k| k.duration_millis())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(durations(&d), vec![100, 100, 100]);
+        assert_eq!(d.timeline_clock(), Duration::from_millis(300));
+
+        d.process_command(Command::Undo).unwrap();
+        assert_eq!(durations(&d), vec![50, 200, 75]);
+    }
+
+    #[test]
+    fn keyframe_durations_respect_minimum_duration() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1"])]),
+        );
+        d.edit_animation("walk").unwrap();
+        d.select_keyframes_only([("walk".to_owned(), Direction::North, 0)]);
+
+        d.process_command(Command::SetKeyframeDuration(Duration::ZERO))
+            .unwrap();
+        assert_eq!(
+            d.sheet
+                .keyframe("walk", Direction::North, 0)
+                .duration_millis(),
+            1
+        );
+
+        d.process_command(Command::SetMinKeyframeDuration(Duration::from_millis(50)))
+            .unwrap();
+        d.process_command(Command::SetKeyframeDuration(Duration::from_millis(20)))
+            .unwrap();
+        assert_eq!(
+            d.sheet
+                .keyframe("walk", Direction::North, 0)
+                .duration_millis(),
+            50
+        );
+
+        d.process_command(Command::SetKeyframeDuration(Duration::from_millis(80)))
+            .unwrap();
+        assert_eq!(
+            d.sheet
+                .keyframe("walk", Direction::North, 0)
+                .duration_millis(),
+            80
+        );
+    }
+
+    #[test]
+    fn can_set_keyframe_duration_in_frames() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1"])]),
+        );
+        d.edit_animation("walk").unwrap();
+        d.select_keyframes_only([("walk".to_owned(), Direction::North, 0)]);
+
+        d.process_command(Command::SetKeyframeDurationInFrames(3, 10.0))
+            .unwrap();
+        assert_eq!(
+            d.sheet
+                .keyframe("walk", Direction::North, 0)
+                .duration_millis(),
+            300
+        );
+        assert_eq!(
+            d.keyframe_durations_in_frames(10.0).unwrap()[&Direction::North],
+            vec![3.0, 1.0]
+        );
+
+        d.process_command(Command::SetKeyframeDurationInFrames(1, 60.0))
+            .unwrap();
+        assert_eq!(
+            d.keyframe_durations_in_frames(60.0).unwrap()[&Direction::North][0],
+            1.0
+        );
+
+        assert!(d
+            .process_command(Command::SetKeyframeDurationInFrames(1, 0.0))
+            .is_err());
+    }
+
+    #[test]
+    fn can_set_precise_keyframe_duration_in_frames() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1"])]),
+        );
+        d.edit_animation("walk").unwrap();
+        d.select_keyframes_only([("walk".to_owned(), Direction::North, 0)]);
+
+        d.process_command(Command::TogglePreciseDurations).unwrap();
+        assert!(d.uses_precise_durations());
+        d.process_command(Command::SetKeyframeDurationInFrames(1, 60.0))
+            .unwrap();
+        let keyframe = d.sheet.keyframe("walk", Direction::North, 0);
+        assert_eq!(keyframe.duration_micros(), 16_667);
+        assert_eq!(keyframe.duration_millis(), 16);
+        assert_eq!(
+            d.keyframe_durations_in_frames(60.0).unwrap()[&Direction::North][0],
+            1.0
+        );
+
+        d.process_command(Command::TogglePreciseDurations).unwrap();
+        d.process_command(Command::SetKeyframeDurationInFrames(1, 60.0))
+            .unwrap();
+        let keyframe = d.sheet.keyframe("walk", Direction::North, 0);
+        assert_eq!(keyframe.duration_micros(), 17_000);
+    }
+
+    #[test]
+    fn can_reset_keyframe_duration() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1"])]),
+        );
+        d.sheet
+            .keyframe_mut("walk", Direction::North, 0)
+            .set_duration_millis(500);
+        d.sheet
+            .keyframe_mut("walk", Direction::North, 1)
+            .set_duration_millis(300);
+        d.edit_animation("walk").unwrap();
+        d.select_keyframes_only([("walk".to_owned(), Direction::North, 0)]);
+
+        d.process_command(Command::ResetKeyframeDuration).unwrap();
+        let sequence = d.sheet.sequence("walk", Direction::North);
+        assert_eq!(
+            sequence.keyframe(0).unwrap().duration_millis(),
+            DEFAULT_KEYFRAME_DURATION_MILLIS
+        );
+        assert_eq!(sequence.keyframe(1).unwrap().duration_millis(), 300);
+
+        d.process_command(Command::Undo).unwrap();
+        assert_eq!(
+            d.sheet
+                .keyframe("walk", Direction::North, 0)
+                .duration_millis(),
+            500
+        );
+    }
+
+    #[test]
+    fn can_reset_keyframe_offsets() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1"])]),
+        );
+        d.sheet
+            .keyframe_mut("walk", Direction::North, 0)
+            .set_offset(vec2(5, -3));
+        d.sheet
+            .keyframe_mut("walk", Direction::North, 1)
+            .set_offset(vec2(-8, 2));
+        d.edit_animation("walk").unwrap();
+        d.select_keyframes_only([
+            ("walk".to_owned(), Direction::North, 0),
+            ("walk".to_owned(), Direction::North, 1),
+        ]);
+
+        d.process_command(Command::ResetSelectedKeyframeOffsets)
+            .unwrap();
+
+        let sequence = d.sheet.sequence("walk", Direction::North);
+        assert!(sequence
+            .keyframes_iter()
+            .all(|k| k.offset() == Vector2D::zero()));
+    }
+
+    #[test]
+    fn can_center_align_keyframe_offsets() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["small", "large", "unselected"])]),
+        );
+        d.sheet
+            .keyframe_mut("walk", Direction::North, 0)
+            .set_offset(vec2(-20, 4));
+        d.sheet
+            .keyframe_mut("walk", Direction::North, 1)
+            .set_offset(vec2(10, -6));
+        d.sheet
+            .keyframe_mut("walk", Direction::North, 2)
+            .set_offset(vec2(30, 0));
+        d.edit_animation("walk").unwrap();
+        d.select_keyframes_only([
+            ("walk".to_owned(), Direction::North, 0),
+            ("walk".to_owned(), Direction::North, 1),
+        ]);
+
+        let frame_sizes = HashMap::from([
+            (PathBuf::from("small"), vec2(8, 8)),
+            (PathBuf::from("large"), vec2(16, 16)),
+            (PathBuf::from("unselected"), vec2(8, 8)),
+        ]);
+        d.process_command(Command::AlignKeyframeOffsets(Align::Center, frame_sizes))
+            .unwrap();
+
+        let offsets = d
+            .sheet
+            .sequence("walk", Direction::North)
+            .keyframes_iter()
+            .map(|k| k.offset())
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, vec![vec2(-3, 4), vec2(-3, -6), vec2(30, 0)]);
+    }
+
+    #[test]
+    fn can_set_animation_frame() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_frame("placeholder.png");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1", "walk_2"])]),
+        );
+        d.sheet
+            .keyframe_mut("walk", Direction::North, 1)
+            .set_duration_millis(250);
+        d.edit_animation("walk").unwrap();
+
+        d.process_command(Command::SetAnimationFrame(PathBuf::from("placeholder.png")))
+            .unwrap();
+
+        let keyframes = d
+            .sheet
+            .sequence("walk", Direction::North)
+            .keyframes_iter()
+            .map(|k| (k.frame().to_owned(), k.duration_millis()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keyframes,
+            vec![
+                (PathBuf::from("placeholder.png"), 100),
+                (PathBuf::from("placeholder.png"), 250),
+                (PathBuf::from("placeholder.png"), 100),
+            ]
+        );
+
+        assert!(d
+            .process_command(Command::SetAnimationFrame(PathBuf::from("missing.png")))
+            .is_err());
+    }
+
     #[test]
     fn can_move_keyframe() {
         let app = TigerAppMock::new();
@@ -142,6 +974,93 @@ mod test {
         assert_eq!(keyframe.offset, (10, 20));
     }
 
+    #[test]
+    fn keyframe_offset_reads_back_as_typed() {
+        let app = TigerAppMock::new();
+        app.new_document("tmp");
+        app.import_frames(vec!["frame"]);
+        app.create_animation();
+        app.begin_drag_and_drop_frame("frame");
+        app.drop_frame_on_timeline(dto::Direction::North, 0);
+
+        app.set_keyframe_offset_x(-7);
+        app.set_keyframe_offset_y(3);
+
+        let keyframe = app.client_state().documents[0].sheet.animations[0]
+            .sequences
+            .get(&dto::Direction::North)
+            .unwrap()
+            .keyframes[0]
+            .clone();
+        assert_eq!(keyframe.offset, (-7, 3));
+        assert_eq!(app.get_keyframe_offset(), Some((-7, 3)));
+    }
+
+    #[test]
+    fn setting_keyframe_offset_y_leaves_x_unchanged() {
+        let app = TigerAppMock::new();
+        app.new_document("tmp");
+        app.import_frames(vec!["frame"]);
+        app.create_animation();
+        app.begin_drag_and_drop_frame("frame");
+        app.drop_frame_on_timeline(dto::Direction::North, 0);
+
+        app.set_keyframe_offset_x(5);
+        app.set_keyframe_offset_y(-2);
+        assert_eq!(app.get_keyframe_offset(), Some((5, -2)));
+
+        app.set_keyframe_offset_y(9);
+        assert_eq!(app.get_keyframe_offset(), Some((5, 9)));
+    }
+
+    #[test]
+    fn can_translate_selected_keyframes() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1", "walk_2"])]),
+        );
+        d.process_command(Command::EditAnimation("walk".to_owned()))
+            .unwrap();
+        d.sheet
+            .animation_mut("walk")
+            .unwrap()
+            .sequence_mut(Direction::North)
+            .unwrap()
+            .keyframe_mut(1)
+            .unwrap()
+            .set_offset(vec2(10, 20));
+        d.process_command(Command::SelectKeyframe(Direction::North, 0, false, false))
+            .unwrap();
+        d.process_command(Command::SelectKeyframe(Direction::North, 1, false, true))
+            .unwrap();
+
+        d.process_command(Command::TranslateSelectedKeyframes(vec2(5, -3)))
+            .unwrap();
+
+        let sequence = d
+            .sheet
+            .animation("walk")
+            .unwrap()
+            .sequence(Direction::North)
+            .unwrap();
+        assert_eq!(sequence.keyframe(0).unwrap().offset(), vec2(5, -3));
+        assert_eq!(sequence.keyframe(1).unwrap().offset(), vec2(15, 17));
+        assert_eq!(sequence.keyframe(2).unwrap().offset(), vec2(0, 0));
+    }
+
+    #[tokio::test]
+    async fn can_get_animation_keyframes() {
+        let app = TigerAppMock::new();
+        app.open_documents(vec!["test-data/samurai.tiger"]).await;
+
+        let keyframes = app.get_animation_keyframes("walk");
+        assert_eq!(keyframes.len(), 16);
+        assert_eq!(keyframes[0].duration_millis, 100);
+        assert!(keyframes[0].frame_path.ends_with("samurai-walk-east-0.png"));
+        assert!(app.get_animation_keyframes("not an animation").is_empty());
+    }
+
     #[test]
     fn can_change_keyframe_duration() {
         let app = TigerAppMock::new();
@@ -185,6 +1104,114 @@ mod test {
         assert_eq!(hitbox.top_left, (10, 20));
     }
 
+    #[test]
+    fn can_duplicate_selected_hitboxes() {
+        let app = TigerAppMock::new();
+        app.new_document("tmp");
+        app.import_frames(vec!["frame"]);
+        app.create_animation();
+        app.begin_drag_and_drop_frame("frame");
+        app.drop_frame_on_timeline(dto::Direction::North, 0);
+        app.create_hitbox(Some((10, 20)));
+
+        app.duplicate_selected_hitboxes();
+
+        let keyframe = app.client_state().documents[0].sheet.animations[0]
+            .sequences
+            .get(&dto::Direction::North)
+            .unwrap()
+            .keyframes[0]
+            .clone();
+        assert_eq!(keyframe.hitboxes.len(), 2);
+        assert_ne!(keyframe.hitboxes[0].name, keyframe.hitboxes[1].name);
+        let copy = keyframe.hitboxes.iter().find(|h| h.selected).unwrap();
+        assert_eq!(copy.top_left, (14, 24));
+    }
+
+    #[test]
+    fn can_subdivide_hitbox() {
+        let app = TigerAppMock::new();
+        app.new_document("tmp");
+        app.import_frames(vec!["frame"]);
+        app.create_animation();
+        app.begin_drag_and_drop_frame("frame");
+        app.drop_frame_on_timeline(dto::Direction::North, 0);
+        app.create_hitbox(Some((-10, 5)));
+        app.set_hitbox_width(40);
+        app.set_hitbox_height(40);
+
+        app.subdivide_hitbox("New Hitbox", 2, 2);
+
+        let rects = || {
+            let mut rects = app.client_state().documents[0].sheet.animations[0]
+                .sequences
+                .get(&dto::Direction::North)
+                .unwrap()
+                .keyframes[0]
+                .hitboxes
+                .iter()
+                .map(|h| (h.name.clone(), h.top_left, h.size))
+                .collect::<Vec<_>>();
+            rects.sort();
+            rects
+        };
+        assert_eq!(
+            rects(),
+            vec![
+                ("New Hitbox 1".to_owned(), (-10, 5), (20, 20)),
+                ("New Hitbox 2".to_owned(), (10, 5), (20, 20)),
+                ("New Hitbox 3".to_owned(), (-10, 25), (20, 20)),
+                ("New Hitbox 4".to_owned(), (10, 25), (20, 20)),
+            ]
+        );
+
+        {
+            let state_handle = app.state();
+            let mut state = state_handle.lock();
+            let document = state.current_document_mut().unwrap();
+            document.process_command(Command::Undo).unwrap();
+        }
+        app.select_hitbox("New Hitbox", false, false);
+        app.set_hitbox_width(41);
+        app.subdivide_hitbox("New Hitbox", 3, 1);
+        assert_eq!(
+            rects(),
+            vec![
+                ("New Hitbox 1".to_owned(), (-10, 5), (13, 40)),
+                ("New Hitbox 2".to_owned(), (3, 5), (13, 40)),
+                ("New Hitbox 3".to_owned(), (16, 5), (15, 40)),
+            ]
+        );
+    }
+
+    #[test]
+    fn hitbox_rect_matches_displayed_values() {
+        let app = TigerAppMock::new();
+        app.new_document("tmp");
+        app.import_frames(vec!["frame"]);
+        app.create_animation();
+        app.begin_drag_and_drop_frame("frame");
+        app.drop_frame_on_timeline(dto::Direction::North, 0);
+        app.create_hitbox(Some((0, 0)));
+
+        app.set_hitbox_position_x(10);
+        app.set_hitbox_position_y(-20);
+        app.set_hitbox_width(7);
+        app.set_hitbox_height(9);
+
+        let hitbox = app.client_state().documents[0].sheet.animations[0]
+            .sequences
+            .get(&dto::Direction::North)
+            .unwrap()
+            .keyframes[0]
+            .hitboxes[0]
+            .clone();
+        let rect = app.get_hitbox_rect(&hitbox.name);
+        assert_eq!(rect, Some(((10, -20), (7, 9))));
+        assert_eq!(rect, Some((hitbox.top_left, hitbox.size)));
+        assert_eq!(app.get_hitbox_rect("not a hitbox"), None);
+    }
+
     #[test]
     fn can_resize_hitbox() {
         let app = TigerAppMock::new();
@@ -232,6 +1259,99 @@ mod test {
         assert_eq!(get_hitbox().size, (15, 18));
     }
 
+    #[test]
+    fn can_match_hitbox_sizes() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_frames(&vec!["frame"]);
+        d.sheet
+            .add_test_animation("idle", HashMap::from([(Direction::North, vec!["frame"])]));
+        let keyframe = d.sheet.keyframe_mut("idle", Direction::North, 0);
+        keyframe.create_hitbox("small").1.set_size(vec2(4, 6));
+        let (_, large) = keyframe.create_hitbox("large");
+        large.set_size(vec2(20, 30));
+        large.set_position(vec2(5, 5));
+        d.process_command(Command::EditAnimation("idle".to_owned()))
+            .unwrap();
+        d.process_command(Command::SelectHitbox("large".to_owned(), false, false))
+            .unwrap();
+        d.process_command(Command::SelectHitbox("small".to_owned(), false, true))
+            .unwrap();
+
+        d.process_command(Command::MatchHitboxSizes).unwrap();
+
+        let small = d.sheet.hitbox("idle", Direction::North, 0, "small");
+        let large = d.sheet.hitbox("idle", Direction::North, 0, "large");
+        assert_eq!(small.size(), vec2(4, 6));
+        assert_eq!(large.size(), vec2(4, 6));
+        assert_eq!(large.position(), vec2(5, 5));
+    }
+
+    #[test]
+    fn can_prefix_hitbox_names() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_frames(&vec!["frame"]);
+        d.sheet
+            .add_test_animation("idle", HashMap::from([(Direction::North, vec!["frame"])]));
+        let keyframe = d.sheet.keyframe_mut("idle", Direction::North, 0);
+        keyframe.create_hitbox("a");
+        keyframe.create_hitbox("b");
+        d.process_command(Command::EditAnimation("idle".to_owned()))
+            .unwrap();
+        d.process_command(Command::SelectHitbox("a".to_owned(), false, false))
+            .unwrap();
+
+        let hitbox_names = |d: &Document| {
+            d.sheet
+                .keyframe("idle", Direction::North, 0)
+                .sorted_hitboxes()
+                .into_iter()
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        d.process_command(Command::PrefixHitboxNames("atk_".to_owned()))
+            .unwrap();
+        assert_eq!(hitbox_names(&d), vec!["atk_a", "atk_b"]);
+        assert!(d
+            .view
+            .selection
+            .is_hitbox_selected("idle", Direction::North, 0, "atk_a"));
+
+        d.process_command(Command::Undo).unwrap();
+        assert_eq!(hitbox_names(&d), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn can_flip_hitboxes_horizontally() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_frames(&vec!["frame"]);
+        d.sheet
+            .add_test_animation("idle", HashMap::from([(Direction::North, vec!["frame"])]));
+        let keyframe = d.sheet.keyframe_mut("idle", Direction::North, 0);
+        keyframe.set_offset(vec2(16, 8));
+        let (_, hitbox) = keyframe.create_hitbox("hand");
+        hitbox.set_position(vec2(2, 5));
+        hitbox.set_size(vec2(8, 4));
+        d.process_command(Command::EditAnimation("idle".to_owned()))
+            .unwrap();
+
+        let frame_sizes = HashMap::from([(PathBuf::from("frame"), vec2(32, 16))]);
+        d.process_command(Command::FlipHitboxesHorizontally(frame_sizes))
+            .unwrap();
+        let hitbox = d.sheet.hitbox("idle", Direction::North, 0, "hand");
+        assert_eq!(hitbox.position(), vec2(22, 5));
+        assert_eq!(hitbox.size(), vec2(8, 4));
+
+        d.process_command(Command::Undo).unwrap();
+        let hitbox = d.sheet.hitbox("idle", Direction::North, 0, "hand");
+        assert_eq!(hitbox.position(), vec2(2, 5));
+
+        d.process_command(Command::FlipHitboxesHorizontally(HashMap::new()))
+            .unwrap();
+        let hitbox = d.sheet.hitbox("idle", Direction::North, 0, "hand");
+        assert_eq!(hitbox.position(), vec2(2, 5));
+    }
+
     #[test]
     fn can_create_and_delete_hitbox() {
         let app = TigerAppMock::new();
@@ -263,4 +1383,69 @@ mod test {
         app.delete_hitbox(hitbox_names()[0].clone());
         assert!(hitbox_names().is_empty());
     }
+
+    #[test]
+    fn can_delete_all_hitboxes_on_keyframe() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_frames(&vec!["frame"]);
+        d.sheet
+            .add_test_animation("idle", HashMap::from([(Direction::North, vec!["frame"])]));
+        d.process_command(Command::EditAnimation("idle".to_owned()))
+            .unwrap();
+        for _ in 0..3 {
+            d.process_command(Command::CreateHitbox(None)).unwrap();
+        }
+
+        let num_hitboxes = |d: &Document| {
+            d.sheet
+                .keyframe("idle", Direction::North, 0)
+                .hitboxes_iter()
+                .count()
+        };
+        assert_eq!(num_hitboxes(&d), 3);
+
+        d.process_command(Command::DeleteAllHitboxesOnKeyframe)
+            .unwrap();
+        assert_eq!(num_hitboxes(&d), 0);
+        assert_eq!(d.view.selection.hitboxes().count(), 0);
+
+        d.process_command(Command::Undo).unwrap();
+        assert_eq!(num_hitboxes(&d), 3);
+    }
+
+    #[test]
+    fn can_delete_hitbox_from_all_keyframes() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_frames(&vec!["frame_0", "frame_1", "frame_2"]);
+        d.sheet.add_test_animation(
+            "idle",
+            HashMap::from([(Direction::North, vec!["frame_0", "frame_1", "frame_2"])]),
+        );
+        for index in 0..3 {
+            d.sheet
+                .keyframe_mut("idle", Direction::North, index)
+                .create_hitbox("hurtbox");
+        }
+        d.process_command(Command::EditAnimation("idle".to_owned()))
+            .unwrap();
+
+        let has_hurtbox = |d: &Document, index: usize| {
+            d.sheet
+                .keyframe("idle", Direction::North, index)
+                .has_hitbox("hurtbox")
+        };
+        assert!((0..3).all(|i| has_hurtbox(&d, i)));
+
+        d.process_command(Command::DeleteHitboxFromAllKeyframes("hurtbox".to_owned()))
+            .unwrap();
+        assert!((0..3).all(|i| !has_hurtbox(&d, i)));
+
+        d.process_command(Command::Undo).unwrap();
+        assert!((0..3).all(|i| has_hurtbox(&d, i)));
+
+        let version = d.version();
+        d.process_command(Command::DeleteHitboxFromAllKeyframes("missing".to_owned()))
+            .unwrap();
+        assert_eq!(d.version(), version);
+    }
 }
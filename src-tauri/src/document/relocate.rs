@@ -68,11 +68,32 @@ impl Document {
         self.sheet.relocate_frames(&mapping);
         Ok(())
     }
+
+    pub(super) fn relocate_frames_root(&mut self, old_root: &Path, new_root: &Path) {
+        self.sheet.relocate_frames_root(old_root, new_root);
+    }
+
+    pub(super) fn replace_frame_source(&mut self, old: &Path, new: &Path) {
+        self.sheet.replace_frame_source(old, new);
+    }
+
+    // Renaming a file on disk cannot be undone, so the new path is applied to the
+    // whole history instead of being recorded as an undo step.
+    pub(super) fn rename_frame_file(&mut self, from: &Path, to: &Path) {
+        let mapping = HashMap::from([(from.to_owned(), to.to_owned())]);
+        self.sheet.relocate_frames(&mapping);
+        for entry in self.history.iter_mut() {
+            entry.sheet.relocate_frames(&mapping);
+        }
+        self.persistent.disk_version = None;
+    }
 }
 
 #[cfg(test)]
 mod test {
 
+    use std::fs::File;
+
     use crate::{mock::TigerAppMock, TigerApp};
 
     use super::*;
@@ -115,4 +136,142 @@ mod test {
         assert!(d.sheet.has_frame("test-data/samurai-idle-west.png"));
         assert!(d.sheet.has_frame("bad-frame.png"));
     }
+
+    #[test]
+    fn can_relocate_frames_root() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_frames(&vec!["art/old/a.png", "art/old/b.png"]);
+        d.process_command(Command::RelocateFramesRoot(
+            PathBuf::from("art/old"),
+            PathBuf::from("art/new"),
+        ))
+        .unwrap();
+        assert!(d.sheet.has_frame("art/new/a.png"));
+        assert!(d.sheet.has_frame("art/new/b.png"));
+
+        d.process_command(Command::Undo).unwrap();
+        assert!(d.sheet.has_frame("art/old/a.png"));
+        assert!(d.sheet.has_frame("art/old/b.png"));
+    }
+
+    #[test]
+    fn can_replace_frame_source() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["old.png", "other.png", "old.png"])]),
+        );
+        d.process_command(Command::ReplaceFrameSource(
+            PathBuf::from("old.png"),
+            PathBuf::from("new.png"),
+        ))
+        .unwrap();
+
+        assert!(!d.sheet.has_frame("old.png"));
+        assert!(d.sheet.has_frame("new.png"));
+        let frames = d
+            .sheet
+            .sequence("walk", Direction::North)
+            .keyframes_iter()
+            .map(|k| k.frame().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            frames,
+            vec![
+                PathBuf::from("new.png"),
+                PathBuf::from("other.png"),
+                PathBuf::from("new.png")
+            ]
+        );
+
+        d.process_command(Command::Undo).unwrap();
+        assert!(d.sheet.has_frame("old.png"));
+        assert!(!d.sheet.has_frame("new.png"));
+    }
+
+    #[test]
+    fn can_replace_frame_source_with_existing_frame() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["old.png", "other.png"])]),
+        );
+        d.process_command(Command::ReplaceFrameSource(
+            PathBuf::from("old.png"),
+            PathBuf::from("other.png"),
+        ))
+        .unwrap();
+
+        assert!(!d.sheet.has_frame("old.png"));
+        assert_eq!(d.sheet.frames_iter().count(), 1);
+        assert!(d
+            .sheet
+            .sequence("walk", Direction::North)
+            .keyframes_iter()
+            .all(|k| k.frame() == Path::new("other.png")));
+    }
+
+    #[tokio::test]
+    async fn can_rename_frame_file() {
+        let before = PathBuf::from("test-output/can_rename_frame_file_before.png");
+        let after = PathBuf::from("test-output/can_rename_frame_file_after.png");
+        std::fs::remove_file(&after).ok();
+        File::create(&before).unwrap();
+
+        let app = TigerAppMock::new();
+        app.new_document("tmp.tiger");
+        app.import_frames(vec![before.clone()]);
+        app.rename_frame_file(before.clone(), after.clone()).await;
+
+        assert!(!before.exists());
+        assert!(after.exists());
+        let state_handle = app.state();
+        let state = state_handle.lock();
+        let sheet = state.current_document().unwrap().sheet();
+        assert!(!sheet.has_frame(&before));
+        assert!(sheet.has_frame(&after));
+        std::fs::remove_file(&after).ok();
+    }
+
+    #[test]
+    fn rename_frame_file_is_not_undoable() {
+        let mut d = Document::new("tmp.tiger");
+        d.process_command(Command::ImportFrames(vec![PathBuf::from("before.png")]))
+            .unwrap();
+        d.process_command(Command::RenameFrameFile(
+            PathBuf::from("before.png"),
+            PathBuf::from("after.png"),
+        ))
+        .unwrap();
+        assert!(!d.is_saved());
+
+        d.process_command(Command::Undo).unwrap();
+        assert!(!d.sheet.has_frame("before.png"));
+        assert!(!d.sheet.has_frame("after.png"));
+
+        d.process_command(Command::Redo).unwrap();
+        assert!(!d.sheet.has_frame("before.png"));
+        assert!(d.sheet.has_frame("after.png"));
+    }
+
+    #[tokio::test]
+    async fn rename_frame_file_reports_errors() {
+        let missing = PathBuf::from("test-output/rename_frame_file_reports_errors.png");
+        std::fs::remove_file(&missing).ok();
+
+        let app = TigerAppMock::new();
+        app.new_document("tmp.tiger");
+        app.import_frames(vec![missing.clone()]);
+        app.rename_frame_file(missing.clone(), "test-output/renamed.png")
+            .await;
+
+        assert!(app.client_state().error.is_some());
+        let state_handle = app.state();
+        let state = state_handle.lock();
+        assert!(state
+            .current_document()
+            .unwrap()
+            .sheet()
+            .has_frame(&missing));
+    }
 }
@@ -177,6 +177,41 @@ impl Document {
         Ok(())
     }
 
+    pub(super) fn invert_hitbox_selection(&mut self) {
+        let Ok(selectable_hitboxes) = self.selectable_hitboxes() else {
+            return;
+        };
+        let inverted_selection = selectable_hitboxes
+            .into_iter()
+            .filter(|h| !self.view.selection.hitboxes.contains(h))
+            .collect::<Vec<_>>();
+        self.select_hitboxes_only(inverted_selection);
+    }
+
+    // Selects keyframes whose span overlaps the range. Keyframes which only touch the range
+    // at their boundary (ending exactly at `start` or beginning exactly at `end`) are excluded.
+    pub(super) fn select_keyframes_in_range(
+        &mut self,
+        start: Duration,
+        end: Duration,
+    ) -> DocumentResult<()> {
+        let (start, end) = (start.as_millis() as u64, end.as_millis() as u64);
+        let (animation_name, animation) = self.workbench_animation()?;
+        let keyframes = animation
+            .sequences_iter()
+            .flat_map(|(direction, sequence)| {
+                sequence
+                    .keyframe_time_ranges()
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, range)| range.start < end && range.end > start)
+                    .map(|(index, _)| (animation_name.clone(), *direction, index))
+            })
+            .collect::<Vec<_>>();
+        self.select_keyframes_only(keyframes);
+        Ok(())
+    }
+
     pub(super) fn select_frame<T: AsRef<Path>>(&mut self, path: T, shift: bool, ctrl: bool) {
         self.view.selection.animations.clear();
         self.view.selection.keyframes.clear();
@@ -384,6 +419,36 @@ impl Document {
         }
     }
 
+    pub(super) fn select_next_animation(&mut self) -> DocumentResult<()> {
+        self.cycle_animations(1)
+    }
+
+    pub(super) fn select_previous_animation(&mut self) -> DocumentResult<()> {
+        self.cycle_animations(-1)
+    }
+
+    // Wraps around at both ends of the list, and starts from the first animation when none is selected
+    fn cycle_animations(&mut self, delta: isize) -> DocumentResult<()> {
+        let item_pool = self.selectable_animations();
+        if item_pool.is_empty() {
+            return Ok(());
+        }
+        let index = match self
+            .view
+            .selection
+            .animations
+            .last_interacted
+            .as_ref()
+            .and_then(|name| item_pool.iter().position(|n| n == name))
+        {
+            Some(index) => (index as isize + delta).rem_euclid(item_pool.len() as isize) as usize,
+            None => 0,
+        };
+        let animation_name = item_pool[index].clone();
+        self.select_animation_only(animation_name.clone());
+        self.edit_animation(animation_name)
+    }
+
     fn browse_hitboxes(&mut self, direction: BrowseDirection, shift: bool) -> DocumentResult<()> {
         let item_pool = self.selectable_hitboxes()?;
         let delta = direction.as_list_offset(ListMode::Linear);
@@ -1154,6 +1219,28 @@ mod test {
         assert_eq!(&d.view.selection.animations.selected_items, &a_b_c);
     }
 
+    #[test]
+    fn can_cycle_animations() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation::<_, &str>("A", HashMap::new());
+        d.sheet.add_test_animation::<_, &str>("B", HashMap::new());
+        let just_a = HashSet::from(["A".to_owned()]);
+        let just_b = HashSet::from(["B".to_owned()]);
+
+        d.select_next_animation().unwrap();
+        assert_eq!(&d.view.selection.animations.selected_items, &just_a);
+        assert_eq!(d.current_animation().as_deref(), Some("A"));
+        d.select_next_animation().unwrap();
+        assert_eq!(&d.view.selection.animations.selected_items, &just_b);
+        assert_eq!(d.current_animation().as_deref(), Some("B"));
+        d.select_next_animation().unwrap();
+        assert_eq!(&d.view.selection.animations.selected_items, &just_a);
+        assert_eq!(d.current_animation().as_deref(), Some("A"));
+        d.select_previous_animation().unwrap();
+        assert_eq!(&d.view.selection.animations.selected_items, &just_b);
+        assert_eq!(d.current_animation().as_deref(), Some("B"));
+    }
+
     #[test]
     fn can_browse_frames_as_a_list() {
         let mut d = Document::new("tmp");
@@ -1297,6 +1384,79 @@ mod test {
         );
     }
 
+    #[test]
+    fn can_invert_hitbox_selection() {
+        let to_set = |v: Vec<&str>| {
+            v.into_iter()
+                .map(|h| ("walk_cycle".into(), Direction::North, 0, h.into()))
+                .collect()
+        };
+
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk_cycle",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1"])]),
+        );
+        d.invert_hitbox_selection();
+        assert!(d.view.selection.hitboxes.is_empty());
+
+        d.edit_animation("walk_cycle").unwrap();
+        let keyframe = d.sheet.keyframe_mut("walk_cycle", Direction::North, 0);
+        keyframe.create_hitbox("H0");
+        keyframe.create_hitbox("H1");
+        keyframe.create_hitbox("H2");
+
+        d.select_hitbox("H1", false, false).unwrap();
+        d.invert_hitbox_selection();
+        assert_eq!(
+            d.view.selection.hitboxes.selected_items,
+            to_set(vec!["H0", "H2"])
+        );
+
+        d.invert_hitbox_selection();
+        assert_eq!(d.view.selection.hitboxes.selected_items, to_set(vec!["H1"]));
+    }
+
+    #[test]
+    fn can_select_keyframes_in_range() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk_cycle",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1", "walk_2"])]),
+        );
+        d.edit_animation("walk_cycle").unwrap();
+
+        d.select_keyframes_in_range(Duration::from_millis(90), Duration::from_millis(210))
+            .unwrap();
+        assert!(d
+            .view
+            .selection
+            .is_keyframe_selected("walk_cycle", Direction::North, 0));
+        assert!(d
+            .view
+            .selection
+            .is_keyframe_selected("walk_cycle", Direction::North, 1));
+        assert!(d
+            .view
+            .selection
+            .is_keyframe_selected("walk_cycle", Direction::North, 2));
+
+        d.select_keyframes_in_range(Duration::from_millis(100), Duration::from_millis(200))
+            .unwrap();
+        assert!(!d
+            .view
+            .selection
+            .is_keyframe_selected("walk_cycle", Direction::North, 0));
+        assert!(d
+            .view
+            .selection
+            .is_keyframe_selected("walk_cycle", Direction::North, 1));
+        assert!(!d
+            .view
+            .selection
+            .is_keyframe_selected("walk_cycle", Direction::North, 2));
+    }
+
     #[test]
     fn can_browse_hitboxes() {
         let to_set = |v: Vec<&str>| {
@@ -1,4 +1,8 @@
 use enum_iterator::{all, last, reverse_all};
+use euclid::default::Vector2D;
+use euclid::vec2;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
 use std::time::Duration;
 
 use crate::document::*;
@@ -7,23 +11,30 @@ use crate::sheet::{Direction, DirectionPreset};
 impl Document {
     pub fn advance_timeline(&mut self, delta: Duration) {
         if self.is_timeline_playing() {
-            self.view.timeline_clock += delta;
+            // Playback runs on animation time, which includes the start delay. The playhead
+            // stays at the start of the timeline until the delay has elapsed.
+            self.view.playback_clock += delta;
             if let Ok((_, animation)) = self.workbench_animation() {
-                if let Ok((_, sequence)) = self.workbench_sequence() {
-                    match sequence.duration_millis() {
+                if let Ok((direction, _)) = self.workbench_sequence() {
+                    let start_delay = animation.start_delay();
+                    match animation.duration_micros(direction) {
                         Some(d) if d > 0 => {
-                            let clock_ms = self.timeline_clock().as_millis() as u64;
+                            let clock_micros = self.view.playback_clock.as_micros() as u64;
                             // Loop animation
                             if animation.looping() {
-                                self.view.timeline_clock = Duration::from_millis(clock_ms % d);
+                                self.view.playback_clock = Duration::from_micros(clock_micros % d);
 
                             // Stop playhead at the end of animation
-                            } else if clock_ms >= d {
+                            } else if clock_micros >= d {
                                 self.persistent.timeline_is_playing = false;
-                                self.view.timeline_clock = Duration::from_millis(d);
-                                if self.view.selection.keyframes().count() <= 1 {
-                                    self.select_current_keyframe().ok();
-                                }
+                                self.view.playback_clock = Duration::from_micros(d);
+                            }
+                            self.view.timeline_clock =
+                                self.view.playback_clock.saturating_sub(start_delay);
+                            if !self.is_timeline_playing()
+                                && self.view.selection.keyframes().count() <= 1
+                            {
+                                self.select_current_keyframe().ok();
                             }
                         }
 
@@ -35,20 +46,38 @@ impl Document {
                     };
                 }
             }
+            if self.view.follow_playhead {
+                let visible_duration = Duration::from_secs_f32(
+                    (self.persistent.timeline_viewport_width / self.view.timeline_zoom_factor())
+                        .max(0.0)
+                        / 1_000.0,
+                );
+                self.view.follow_playhead(visible_duration);
+            }
         }
     }
 
+    pub(super) fn set_timeline_viewport_width(&mut self, width: f32) {
+        self.persistent.timeline_viewport_width = width.max(0.0);
+    }
+
     pub(super) fn play(&mut self) -> DocumentResult<()> {
         if self
             .workbench_sequence()?
             .1
-            .duration_millis()
-            .map(Duration::from_millis)
+            .duration()
             .map(|d| d <= self.view.timeline_clock)
             .unwrap_or_default()
         {
             self.view.skip_to_timeline_start();
         }
+        // Playing from the start of the timeline waits for the start delay first
+        let start_delay = self.workbench_animation()?.1.start_delay();
+        self.view.playback_clock = if self.view.timeline_clock.is_zero() {
+            Duration::ZERO
+        } else {
+            self.view.timeline_clock + start_delay
+        };
         self.persistent.timeline_is_playing = true;
         self.view.selection.hitboxes.clear();
         Ok(())
@@ -116,6 +145,16 @@ impl Document {
         Ok(())
     }
 
+    pub(super) fn jump_to_keyframe(&mut self, index: usize) -> DocumentResult<()> {
+        let (_, sequence) = self.workbench_sequence()?;
+        let time = *sequence
+            .keyframe_times()
+            .get(index)
+            .ok_or(DocumentError::NoKeyframeAtIndex(index))?;
+        self.view.timeline_clock = Duration::from_millis(time);
+        self.select_current_keyframe()
+    }
+
     pub(super) fn cycle_directions_backward(&mut self) -> DocumentResult<()> {
         let now = self.view.timeline_clock;
         let old_direction = self
@@ -174,6 +213,63 @@ impl Document {
         Ok(())
     }
 
+    pub(super) fn set_animation_start_delay(
+        &mut self,
+        start_delay: Duration,
+    ) -> DocumentResult<()> {
+        let (_, animation) = self.workbench_animation_mut()?;
+        animation.set_start_delay(start_delay);
+        Ok(())
+    }
+
+    pub(super) fn set_animation_origin(&mut self, origin: Vector2D<i32>) -> DocumentResult<()> {
+        let (_, animation) = self.workbench_animation_mut()?;
+        animation.set_origin(origin);
+        Ok(())
+    }
+
+    pub(super) fn nudge_animation_origin(
+        &mut self,
+        direction: NudgeDirection,
+        large_nudge: bool,
+    ) -> DocumentResult<()> {
+        let mut delta = match direction {
+            NudgeDirection::Up => vec2(0, -1),
+            NudgeDirection::Down => vec2(0, 1),
+            NudgeDirection::Left => vec2(-1, 0),
+            NudgeDirection::Right => vec2(1, 0),
+        };
+        if large_nudge {
+            delta *= 10;
+        }
+        let (_, animation) = self.workbench_animation_mut()?;
+        animation.set_origin(animation.origin() + delta);
+        Ok(())
+    }
+
+    // Moves an offset shared by every keyframe of the animation into its origin. Animations whose
+    // keyframes have different offsets are left untouched.
+    pub(super) fn extract_common_offset(&mut self) -> DocumentResult<()> {
+        let (_, animation) = self.workbench_animation_mut()?;
+        let mut offsets = animation
+            .sequences_iter()
+            .flat_map(|(_, sequence)| sequence.keyframes_iter())
+            .map(|keyframe| keyframe.offset());
+        let Some(common_offset) = offsets.next() else {
+            return Ok(());
+        };
+        if !offsets.all(|offset| offset == common_offset) {
+            return Ok(());
+        }
+        for (_, sequence) in animation.sequences_iter_mut() {
+            for keyframe in sequence.keyframes_iter_mut() {
+                Document::nudge_keyframe(keyframe, Vector2D::zero());
+            }
+        }
+        animation.set_origin(animation.origin() + common_offset);
+        Ok(())
+    }
+
     pub(super) fn apply_direction_preset(&mut self, preset: DirectionPreset) -> DocumentResult<()> {
         let (_, animation) = self.workbench_animation_mut()?;
         animation.apply_direction_preset(preset);
@@ -224,6 +320,123 @@ impl Document {
         self.select_current_keyframe().ok();
         Ok(())
     }
+
+    pub(super) fn move_selected_keyframes_earlier(&mut self) -> DocumentResult<()> {
+        self.move_selected_keyframes(false)
+    }
+
+    pub(super) fn move_selected_keyframes_later(&mut self) -> DocumentResult<()> {
+        self.move_selected_keyframes(true)
+    }
+
+    // Selected keyframes are grouped per sequence and shifted by one position. Keyframes which
+    // were not adjacent are gathered together, like when dragging them on the timeline.
+    fn move_selected_keyframes(&mut self, later: bool) -> DocumentResult<()> {
+        let mut selection = BTreeMap::<(String, Direction), Vec<usize>>::new();
+        for (a, d, i) in self.view.selection.keyframes() {
+            selection.entry((a.clone(), *d)).or_default().push(*i);
+        }
+
+        let timeline_is_playing = self.persistent.timeline_is_playing;
+        let current_animation = self.current_animation().clone();
+        let current_direction = self.view.current_sequence;
+
+        let mut new_selection = Vec::new();
+        let mut new_clock = None;
+        for ((animation_name, direction), mut indices) in selection {
+            indices.sort();
+            let num_keyframes = self
+                .sheet
+                .animation(&animation_name)
+                .ok_or_else(|| DocumentError::AnimationNotInDocument(animation_name.clone()))?
+                .sequence(direction)
+                .ok_or(DocumentError::SequenceNotInAnimation(direction))?
+                .num_keyframes();
+            let target_index = match (indices.first(), indices.last()) {
+                (Some(first), _) if !later && *first > 0 => Some(first - 1),
+                (_, Some(last)) if later && last + 1 < num_keyframes => Some(last + 2),
+                _ => None,
+            };
+
+            if let Some(target_index) = target_index {
+                let keyframes = indices.iter().map(|i| (direction, *i)).collect::<Vec<_>>();
+                let first_index =
+                    self.reorder_keyframes(&animation_name, &keyframes, direction, target_index)?;
+                indices = (first_index..(first_index + keyframes.len())).collect();
+                if !timeline_is_playing
+                    && current_animation.as_ref() == Some(&animation_name)
+                    && current_direction == Some(direction)
+                {
+                    let (_, sequence) = self.workbench_sequence()?;
+                    new_clock = sequence.keyframe_times().get(first_index).copied();
+                }
+            }
+
+            new_selection.extend(
+                indices
+                    .into_iter()
+                    .map(|i| (animation_name.clone(), direction, i)),
+            );
+        }
+
+        self.select_keyframes_only(new_selection);
+        if let Some(clock) = new_clock {
+            self.view.timeline_clock = Duration::from_millis(clock);
+        }
+        Ok(())
+    }
+
+    pub(super) fn trim_animation_ends(
+        &mut self,
+        empty_frames: &HashSet<PathBuf>,
+    ) -> DocumentResult<()> {
+        let is_empty = |k: &Keyframe<Absolute>| empty_frames.contains(k.frame());
+
+        let (_, sequence) = self.workbench_sequence()?;
+        let trimmed_micros: u64 = sequence
+            .keyframes_iter()
+            .take_while(|k| is_empty(k))
+            .map(|k| k.effective_duration_micros())
+            .sum();
+        let trimmed_millis = trimmed_micros / 1_000;
+        let new_clock =
+            (self.view.timeline_clock.as_millis() as u64).saturating_sub(trimmed_millis);
+
+        let (_, animation) = self.workbench_animation_mut()?;
+        for (_, sequence) in animation.sequences_iter_mut() {
+            sequence.trim_keyframes(is_empty);
+        }
+
+        self.view.selection.keyframes.clear();
+        self.scrub_timeline(Duration::from_millis(new_clock))
+    }
+
+    pub(super) fn merge_adjacent_duplicate_keyframes(&mut self) -> DocumentResult<()> {
+        let (_, animation) = self.workbench_animation_mut()?;
+        for (_, sequence) in animation.sequences_iter_mut() {
+            sequence.merge_adjacent_duplicate_keyframes();
+        }
+        self.view.selection.keyframes.clear();
+        Ok(())
+    }
+
+    pub fn keyframe_thumbnail_size(&self, duration: Duration) -> Option<u32> {
+        keyframe_thumbnail_size(duration.as_millis() as f32 * self.timeline_zoom_factor())
+    }
+}
+
+const KEYFRAME_BAR_PADDING: f32 = 16.0;
+const KEYFRAME_THUMBNAIL_SIZE: u32 = 20;
+const KEYFRAME_MIN_LABEL_WIDTH: f32 = 24.0;
+
+// Size in pixels of the frame thumbnail drawn inside a timeline keyframe bar, or None
+// when the bar is too narrow to fit it next to a readable label.
+fn keyframe_thumbnail_size(bar_width: f32) -> Option<u32> {
+    let available_width = bar_width - KEYFRAME_BAR_PADDING - KEYFRAME_MIN_LABEL_WIDTH;
+    if available_width < KEYFRAME_THUMBNAIL_SIZE as f32 {
+        return None;
+    }
+    Some(KEYFRAME_THUMBNAIL_SIZE)
 }
 
 #[cfg(test)]
@@ -232,6 +445,9 @@ mod test {
     use std::collections::HashMap;
 
     use super::*;
+    use crate::dto;
+    use crate::mock::TigerAppMock;
+    use crate::TigerApp;
 
     #[test]
     fn can_toggle_playback() {
@@ -287,6 +503,63 @@ mod test {
         assert_eq!(d.timeline_clock().as_millis(), 0);
     }
 
+    #[test]
+    fn start_delay_shifts_animation_timing() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_frames(&vec!["walk_0", "walk_1", "walk_2"]);
+        d.sheet.add_test_animation(
+            "walk_cycle",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1", "walk_2"])]),
+        );
+        d.edit_animation("walk_cycle").unwrap();
+
+        let animation = d.sheet.animation("walk_cycle").unwrap();
+        assert_eq!(animation.keyframe_time_ranges(Direction::North)[0], 0..100);
+        assert_eq!(animation.duration_millis(Direction::North), Some(300));
+
+        d.process_command(Command::SetAnimationStartDelay(Duration::from_millis(100)))
+            .unwrap();
+
+        let animation = d.sheet.animation("walk_cycle").unwrap();
+        assert_eq!(animation.start_delay(), Duration::from_millis(100));
+        assert_eq!(
+            animation.keyframe_time_ranges(Direction::North)[0],
+            100..200
+        );
+        assert_eq!(animation.duration_millis(Direction::North), Some(400));
+        let index_at =
+            |millis| animation.keyframe_index_at(Direction::North, Duration::from_millis(millis));
+        assert_eq!(index_at(50), None);
+        assert_eq!(index_at(150), Some(0));
+        assert_eq!(index_at(250), Some(1));
+    }
+
+    #[test]
+    fn playback_waits_for_animation_start_delay() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_frames(&vec!["walk_0", "walk_1", "walk_2"]);
+        d.sheet.add_test_animation(
+            "walk_cycle",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1", "walk_2"])]),
+        );
+        d.edit_animation("walk_cycle").unwrap();
+        d.process_command(Command::SetAnimationStartDelay(Duration::from_millis(100)))
+            .unwrap();
+
+        d.play().unwrap();
+        d.advance_timeline(Duration::from_millis(50));
+        assert_eq!(d.timeline_clock().as_millis(), 0);
+        assert_eq!(d.current_keyframe_index(), None);
+
+        d.advance_timeline(Duration::from_millis(100));
+        assert_eq!(d.timeline_clock().as_millis(), 50);
+        assert_eq!(d.current_keyframe_index(), Some(0));
+
+        d.advance_timeline(Duration::from_millis(300));
+        assert_eq!(d.timeline_clock().as_millis(), 300);
+        assert!(!d.is_timeline_playing());
+    }
+
     #[test]
     fn scrubbing_blank_sequence_jumps_to_start() {
         let mut d = Document::new("tmp");
@@ -372,6 +645,27 @@ mod test {
         assert_eq!(d.timeline_clock().as_millis(), 0);
     }
 
+    #[test]
+    fn can_jump_to_keyframe() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_frames(&vec!["walk_0", "walk_1", "walk_2"]);
+        d.sheet.add_test_animation(
+            "walk_cycle",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1", "walk_2"])]),
+        );
+
+        d.edit_animation("walk_cycle").unwrap();
+        d.process_command(Command::JumpToKeyframe(2)).unwrap();
+        assert_eq!(d.timeline_clock().as_millis(), 200);
+        assert!(d
+            .view
+            .selection
+            .is_keyframe_selected("walk_cycle", Direction::North, 2));
+
+        assert!(d.process_command(Command::JumpToKeyframe(3)).is_err());
+        assert_eq!(d.timeline_clock().as_millis(), 200);
+    }
+
     #[test]
     fn can_cycle_directions() {
         let mut d = Document::new("tmp");
@@ -489,4 +783,316 @@ mod test {
             vec![(Direction::North, 0)]
         );
     }
+
+    #[test]
+    fn can_trim_animation_ends() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk_cycle",
+            HashMap::from([(
+                Direction::North,
+                vec!["blank", "walk_0", "blank", "walk_1", "blank", "blank"],
+            )]),
+        );
+        d.edit_animation("walk_cycle").unwrap();
+        d.scrub_timeline(Duration::from_millis(150)).unwrap();
+
+        d.trim_animation_ends(&HashSet::from([PathBuf::from("blank")]))
+            .unwrap();
+
+        let sequence = d.sheet.sequence("walk_cycle", Direction::North);
+        assert_eq!(
+            sequence
+                .keyframes_iter()
+                .map(|k| k.frame().to_owned())
+                .collect::<Vec<_>>(),
+            vec![
+                PathBuf::from("walk_0"),
+                PathBuf::from("blank"),
+                PathBuf::from("walk_1")
+            ]
+        );
+        assert_eq!(d.view.timeline_clock, Duration::from_millis(50));
+    }
+
+    #[test]
+    fn can_merge_adjacent_duplicate_keyframes() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk_cycle",
+            HashMap::from([(Direction::North, vec!["A", "A", "B"])]),
+        );
+        d.sheet
+            .keyframe_mut("walk_cycle", Direction::North, 0)
+            .create_hitbox("head");
+        d.sheet
+            .keyframe_mut("walk_cycle", Direction::North, 1)
+            .create_hitbox("body");
+        d.process_command(Command::EditAnimation("walk_cycle".to_owned()))
+            .unwrap();
+        d.select_keyframes_only([("walk_cycle".to_owned(), Direction::North, 2)]);
+
+        d.process_command(Command::MergeAdjacentDuplicateKeyframes)
+            .unwrap();
+
+        let sequence = d.sheet.sequence("walk_cycle", Direction::North);
+        assert_eq!(
+            sequence
+                .keyframes_iter()
+                .map(|k| (k.frame().to_owned(), k.duration_millis()))
+                .collect::<Vec<_>>(),
+            vec![(PathBuf::from("A"), 200), (PathBuf::from("B"), 100)]
+        );
+        let merged_keyframe = d.sheet.keyframe("walk_cycle", Direction::North, 0);
+        assert!(merged_keyframe.has_hitbox("head"));
+        assert!(merged_keyframe.has_hitbox("body"));
+        assert!(d.view.selection.keyframes.is_empty());
+
+        d.process_command(Command::Undo).unwrap();
+        assert_eq!(
+            d.sheet
+                .sequence("walk_cycle", Direction::North)
+                .num_keyframes(),
+            3
+        );
+    }
+
+    #[test]
+    fn can_move_keyframes_earlier_or_later() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk_cycle",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1", "walk_2"])]),
+        );
+        d.edit_animation("walk_cycle").unwrap();
+        let frames = |d: &Document| {
+            d.sheet
+                .sequence("walk_cycle", Direction::North)
+                .keyframes_iter()
+                .map(|k| k.frame().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        };
+
+        d.select_keyframes_only(vec![("walk_cycle".to_owned(), Direction::North, 0)]);
+        d.move_selected_keyframes_earlier().unwrap();
+        assert_eq!(frames(&d), vec!["walk_0", "walk_1", "walk_2"]);
+        assert!(d
+            .view
+            .selection
+            .is_keyframe_selected("walk_cycle", Direction::North, 0));
+
+        d.select_keyframes_only(vec![("walk_cycle".to_owned(), Direction::North, 1)]);
+        d.move_selected_keyframes_earlier().unwrap();
+        assert_eq!(frames(&d), vec!["walk_1", "walk_0", "walk_2"]);
+        assert!(d
+            .view
+            .selection
+            .is_keyframe_selected("walk_cycle", Direction::North, 0));
+
+        d.move_selected_keyframes_later().unwrap();
+        d.move_selected_keyframes_later().unwrap();
+        assert_eq!(frames(&d), vec!["walk_0", "walk_2", "walk_1"]);
+        assert!(d
+            .view
+            .selection
+            .is_keyframe_selected("walk_cycle", Direction::North, 2));
+
+        d.move_selected_keyframes_later().unwrap();
+        assert_eq!(frames(&d), vec!["walk_0", "walk_2", "walk_1"]);
+
+        d.select_keyframes_only(vec![
+            ("walk_cycle".to_owned(), Direction::North, 0),
+            ("walk_cycle".to_owned(), Direction::North, 1),
+        ]);
+        d.move_selected_keyframes_later().unwrap();
+        assert_eq!(frames(&d), vec!["walk_1", "walk_0", "walk_2"]);
+        assert!(d
+            .view
+            .selection
+            .is_keyframe_selected("walk_cycle", Direction::North, 1));
+        assert!(d
+            .view
+            .selection
+            .is_keyframe_selected("walk_cycle", Direction::North, 2));
+    }
+
+    #[test]
+    fn can_read_current_keyframe_index() {
+        let app = TigerAppMock::new();
+        app.new_document("tmp");
+        app.import_frames(vec!["walk_0", "walk_1", "walk_2"]);
+        app.create_animation();
+        {
+            let state_handle = app.state();
+            let mut state = state_handle.lock();
+            let document = state.current_document_mut().unwrap();
+            document
+                .process_command(Command::SelectDirection(Direction::North))
+                .unwrap();
+        }
+        for (index, frame) in ["walk_0", "walk_1", "walk_2"].iter().enumerate() {
+            app.begin_drag_and_drop_frame(*frame);
+            app.drop_frame_on_timeline(dto::Direction::North, index);
+        }
+        assert_eq!(app.get_current_keyframe_index(), Some(0));
+
+        {
+            let state_handle = app.state();
+            let mut state = state_handle.lock();
+            let document = state.current_document_mut().unwrap();
+            document
+                .process_command(Command::ScrubTimeline(Duration::from_millis(150)))
+                .unwrap();
+        }
+        assert_eq!(app.get_current_keyframe_index(), Some(1));
+    }
+
+    #[test]
+    fn can_read_timeline_clock() {
+        let app = TigerAppMock::new();
+        app.new_document("tmp");
+        app.import_frames(vec!["walk_0", "walk_1", "walk_2"]);
+        app.create_animation();
+        {
+            let state_handle = app.state();
+            let mut state = state_handle.lock();
+            let document = state.current_document_mut().unwrap();
+            document
+                .process_command(Command::SelectDirection(Direction::North))
+                .unwrap();
+        }
+        for (index, frame) in ["walk_0", "walk_1", "walk_2"].iter().enumerate() {
+            app.begin_drag_and_drop_frame(*frame);
+            app.drop_frame_on_timeline(dto::Direction::North, index);
+        }
+        assert_eq!(app.get_timeline_clock(), 0);
+
+        {
+            let state_handle = app.state();
+            let mut state = state_handle.lock();
+            let document = state.current_document_mut().unwrap();
+            document
+                .process_command(Command::ScrubTimeline(Duration::from_millis(150)))
+                .unwrap();
+        }
+        assert_eq!(app.get_timeline_clock(), 150);
+        assert_eq!(app.client_state().documents[0].timeline_clock_millis, 150);
+    }
+
+    #[test]
+    fn narrow_keyframes_do_not_show_thumbnails() {
+        assert_eq!(keyframe_thumbnail_size(0.0), None);
+        assert_eq!(keyframe_thumbnail_size(40.0), None);
+        assert_eq!(keyframe_thumbnail_size(59.0), None);
+        assert_eq!(keyframe_thumbnail_size(60.0), Some(20));
+        assert_eq!(keyframe_thumbnail_size(500.0), Some(20));
+    }
+
+    #[test]
+    fn keyframe_thumbnails_depend_on_timeline_zoom() {
+        let mut d = Document::new("tmp");
+        let duration = Duration::from_millis(100);
+        d.view.set_timeline_zoom_amount(0.0);
+        assert_eq!(d.keyframe_thumbnail_size(duration), None);
+        d.view.set_timeline_zoom_amount(1.0);
+        assert_eq!(d.keyframe_thumbnail_size(duration), Some(20));
+    }
+
+    #[test]
+    fn can_set_and_nudge_animation_origin() {
+        let app = TigerAppMock::new();
+        app.new_document("tmp");
+        app.create_animation();
+        {
+            let state_handle = app.state();
+            let mut state = state_handle.lock();
+            let document = state.current_document_mut().unwrap();
+            document
+                .process_command(Command::SetAnimationOrigin(vec2(4, 5)))
+                .unwrap();
+            document
+                .process_command(Command::NudgeAnimationOrigin(NudgeDirection::Left, false))
+                .unwrap();
+            document
+                .process_command(Command::NudgeAnimationOrigin(NudgeDirection::Down, true))
+                .unwrap();
+        }
+        assert_eq!(
+            app.client_state().documents[0].sheet.animations[0].origin,
+            (3, 15)
+        );
+        {
+            let state_handle = app.state();
+            let mut state = state_handle.lock();
+            let document = state.current_document_mut().unwrap();
+            document.process_command(Command::Undo).unwrap();
+        }
+        assert_eq!(
+            app.client_state().documents[0].sheet.animations[0].origin,
+            (3, 5)
+        );
+    }
+
+    #[test]
+    fn can_extract_common_offset() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([
+                (Direction::North, vec!["walk_0", "walk_1"]),
+                (Direction::South, vec!["walk_2"]),
+            ]),
+        );
+        for (direction, index) in [
+            (Direction::North, 0),
+            (Direction::North, 1),
+            (Direction::South, 0),
+        ] {
+            d.sheet
+                .keyframe_mut("walk", direction, index)
+                .set_offset(vec2(10, 5));
+        }
+        d.edit_animation("walk").unwrap();
+
+        d.process_command(Command::ExtractCommonOffset).unwrap();
+        let animation = d.sheet.animation("walk").unwrap();
+        assert_eq!(animation.origin(), vec2(10, 5));
+        assert!(animation
+            .sequences_iter()
+            .flat_map(|(_, s)| s.keyframes_iter())
+            .all(|k| k.offset() == Vector2D::zero()));
+
+        d.process_command(Command::Undo).unwrap();
+        assert_eq!(
+            d.sheet.animation("walk").unwrap().origin(),
+            Vector2D::zero()
+        );
+        assert_eq!(
+            d.sheet.keyframe("walk", Direction::South, 0).offset(),
+            vec2(10, 5)
+        );
+    }
+
+    #[test]
+    fn extract_common_offset_ignores_differing_offsets() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1"])]),
+        );
+        d.sheet
+            .keyframe_mut("walk", Direction::North, 0)
+            .set_offset(vec2(10, 5));
+        d.edit_animation("walk").unwrap();
+
+        d.process_command(Command::ExtractCommonOffset).unwrap();
+        assert_eq!(
+            d.sheet.animation("walk").unwrap().origin(),
+            Vector2D::zero()
+        );
+        assert_eq!(
+            d.sheet.keyframe("walk", Direction::North, 0).offset(),
+            vec2(10, 5)
+        );
+    }
 }
@@ -26,6 +26,12 @@ pub(super) struct HitboxNudge {
     pub(super) original_positions: HashMap<String, Vector2D<i32>>,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct AnchorNudge {
+    pub(super) anchor_being_dragged: String,
+    pub(super) original_position: Vector2D<i32>,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ResizeAxis {
     N,
@@ -59,6 +65,7 @@ pub struct Transient {
     pub(super) keyframe_drag_and_drop: Option<(Direction, usize)>,
     pub(super) keyframe_nudge: Option<KeyframeNudge>,
     pub(super) hitbox_nudge: Option<HitboxNudge>,
+    pub(super) anchor_nudge: Option<AnchorNudge>,
     pub(super) hitbox_resize: Option<HitboxResize>,
 }
 
@@ -107,6 +114,16 @@ impl Document {
         }
     }
 
+    // Drags and nudges are only recorded in history once they complete, so the current
+    // history entry holds the sheet as it was before any in-progress operation began.
+    pub fn cancel_transient(&mut self) {
+        let sheet_before_transient = &self.history[self.history_index].sheet;
+        if &self.sheet != sheet_before_transient {
+            self.sheet = sheet_before_transient.clone();
+        }
+        self.transient = Default::default();
+    }
+
     pub(super) fn begin_drag_and_drop_frame(&mut self, frame: PathBuf) {
         if !self.view.selection.is_frame_selected(&frame) {
             self.select_frame_only(frame.clone());
@@ -211,19 +228,56 @@ impl Document {
             selection
         };
 
-        let (animation_name, animation) = self.workbench_animation_mut()?;
+        let (animation_name, _) = self.workbench_animation()?;
+        let animation_name = animation_name.to_owned();
+        let insert_index = self.reorder_keyframes(&animation_name, &selection, direction, index)?;
+
+        // Update timeline position
+        if !timeline_is_playing {
+            let (_, sequence) = self.workbench_sequence()?;
+            let keyframe_times = sequence.keyframe_times();
+            let timeline_pos = *keyframe_times
+                .get(insert_index)
+                .ok_or(DocumentError::NoKeyframeAtIndex(insert_index))?;
+            self.view.timeline_clock = Duration::from_millis(timeline_pos);
+        }
+
+        // Update selection
+        let new_selection = (insert_index..(insert_index + selection.len()))
+            .map(|i| (animation_name.clone(), direction, i));
+        self.select_keyframes_only(new_selection);
+        self.view.current_sequence = Some(direction);
+
+        self.transient.keyframe_drag_and_drop = None;
+        Ok(())
+    }
+
+    // Moves keyframes of an animation next to each other, in the order they are listed, so that
+    // they land before the keyframe at `index` in the `direction` sequence. Returns the index
+    // of the first moved keyframe after the move.
+    pub(super) fn reorder_keyframes(
+        &mut self,
+        animation_name: &str,
+        keyframes: &[(Direction, usize)],
+        direction: Direction,
+        index: usize,
+    ) -> DocumentResult<usize> {
+        let animation = self
+            .sheet
+            .animation_mut(animation_name)
+            .ok_or_else(|| DocumentError::AnimationNotInDocument(animation_name.to_owned()))?;
 
         // Remove keyframes from their current sequence
-        let mut keyframes = Vec::with_capacity(selection.len());
-        for (d, i) in selection.iter().rev() {
+        let mut removed_keyframes = Vec::with_capacity(keyframes.len());
+        for (d, i) in keyframes.iter().rev() {
             let sequence = animation
                 .sequence_mut(*d)
                 .ok_or(DocumentError::SequenceNotInAnimation(*d))?;
-            keyframes.push(sequence.delete_keyframe(*i)?);
+            removed_keyframes.push(sequence.delete_keyframe(*i)?);
         }
 
         // Insert keyframes at target location
-        let num_affected_frames_before_insert_point = selection
+        let num_affected_frames_before_insert_point = keyframes
             .iter()
             .filter(|(d, i)| *d == direction && *i < index)
             .count();
@@ -232,27 +286,11 @@ impl Document {
         let sequence = animation
             .sequence_mut(direction)
             .ok_or(DocumentError::SequenceNotInAnimation(direction))?;
-        for keyframe in keyframes {
+        for keyframe in removed_keyframes {
             sequence.insert_keyframe(keyframe, insert_index)?;
         }
 
-        // Update timeline position
-        if !timeline_is_playing {
-            let keyframe_times = sequence.keyframe_times();
-            let timeline_pos = *keyframe_times
-                .get(insert_index)
-                .ok_or(DocumentError::NoKeyframeAtIndex(insert_index))?;
-            self.view.timeline_clock = Duration::from_millis(timeline_pos);
-        }
-
-        // Update selection
-        let new_selection = (insert_index..(insert_index + selection.len()))
-            .map(|i| (animation_name.clone(), direction, i));
-        self.select_keyframes_only(new_selection);
-        self.view.current_sequence = Some(direction);
-
-        self.transient.keyframe_drag_and_drop = None;
-        Ok(())
+        Ok(insert_index)
     }
 
     pub(super) fn end_drag_and_drop_keyframe(&mut self) {
@@ -363,7 +401,7 @@ impl Document {
             }
         }
 
-        let minimum_duration = 20.0 as u64;
+        let minimum_duration = self.clamp_keyframe_duration(20);
         for (d, i, keyframe) in self.selected_keyframes_mut()? {
             let old_duration = drag_state
                 .original_ranges
@@ -530,6 +568,15 @@ impl Document {
         for (_, hitbox) in keyframe.hitboxes_iter_mut() {
             hitbox.set_position(hitbox.position() + displacement);
         }
+        let anchors = keyframe
+            .anchors_iter()
+            .map(|(n, p)| (n.clone(), p))
+            .collect::<Vec<_>>();
+        for (anchor_name, position) in anchors {
+            keyframe
+                .set_anchor_position(anchor_name, position + displacement)
+                .ok();
+        }
     }
 
     pub(super) fn begin_nudge_hitbox<T: AsRef<str>>(
@@ -626,6 +673,53 @@ impl Document {
         }
     }
 
+    pub(super) fn begin_nudge_anchor<T: AsRef<str>>(
+        &mut self,
+        anchor_name: T,
+    ) -> DocumentResult<()> {
+        let (_, keyframe) = self.workbench_keyframe()?;
+        let original_position = keyframe
+            .anchor(&anchor_name)
+            .ok_or_else(|| SheetError::AnchorNotFound(anchor_name.as_ref().to_owned()))?;
+        self.transient.anchor_nudge = Some(AnchorNudge {
+            anchor_being_dragged: anchor_name.as_ref().to_owned(),
+            original_position,
+        });
+        Ok(())
+    }
+
+    pub(super) fn update_nudge_anchor(
+        &mut self,
+        mut displacement: Vector2D<i32>,
+        both_axis: bool,
+    ) -> DocumentResult<()> {
+        let zoom = self.workbench_zoom();
+        let nudge = self
+            .transient
+            .anchor_nudge
+            .clone()
+            .ok_or(DocumentError::NotNudgingAnchor)?;
+
+        if !both_axis {
+            if displacement.x.abs() > displacement.y.abs() {
+                displacement.y = 0;
+            } else {
+                displacement.x = 0;
+            }
+        }
+
+        let new_position = (nudge.original_position.to_f32() + displacement.to_f32() / zoom)
+            .floor()
+            .to_i32();
+        let (_, keyframe) = self.workbench_keyframe_mut()?;
+        keyframe.set_anchor_position(&nudge.anchor_being_dragged, new_position)?;
+        Ok(())
+    }
+
+    pub(super) fn end_nudge_anchor(&mut self) {
+        self.transient.anchor_nudge = None;
+    }
+
     pub(super) fn begin_resize_hitbox<T: AsRef<str>>(
         &mut self,
         hitbox_name: T,
@@ -663,6 +757,10 @@ impl Document {
             .map(|(_, _, _, hitbox_name)| hitbox_name.clone())
             .collect::<HashSet<_>>();
 
+        // Holding shift inverts the document default
+        let preserve_aspect_ratio =
+            preserve_aspect_ratio != self.persistent.default_preserve_aspect_ratio;
+
         let zoom = self.workbench_zoom();
         let (_, keyframe) = self.workbench_keyframe_mut()?;
 
@@ -1380,6 +1478,42 @@ mod test {
         );
     }
 
+    #[test]
+    fn can_preserve_aspect_ratio_by_default() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_frames(&vec!["walk_0", "walk_1", "walk_2"]);
+        d.sheet.add_test_animation(
+            "walk_cycle",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1", "walk_2"])]),
+        );
+        d.edit_animation("walk_cycle").unwrap();
+        d.view.set_workbench_zoom_factor(1);
+
+        let keyframe = d.sheet.keyframe_mut("walk_cycle", Direction::North, 0);
+        let (_, hitbox) = keyframe.create_hitbox("my_hitbox");
+        hitbox.set_position(vec2(0, 0));
+        hitbox.set_size(vec2(20, 10));
+
+        d.process_command(Command::SetDefaultPreserveAspectRatio(true))
+            .unwrap();
+        assert!(d.preserves_aspect_ratio_by_default());
+
+        d.select_hitbox_only("walk_cycle", Direction::North, 0, "my_hitbox");
+        d.begin_resize_hitbox("my_hitbox", ResizeAxis::SE).unwrap();
+        d.update_resize_hitbox(vec2(40, 5), false).unwrap();
+        let hitbox = d
+            .sheet
+            .hitbox("walk_cycle", Direction::North, 0, "my_hitbox");
+        assert_eq!(hitbox.rectangle(), euclid::rect(0, 0, 60, 30));
+
+        d.update_resize_hitbox(vec2(40, 5), true).unwrap();
+        d.end_resize_hitbox();
+        let hitbox = d
+            .sheet
+            .hitbox("walk_cycle", Direction::North, 0, "my_hitbox");
+        assert_eq!(hitbox.rectangle(), euclid::rect(0, 0, 60, 15));
+    }
+
     #[test]
     fn keeps_track_of_hitboxes_being_resized() {
         let mut d = Document::new("tmp");
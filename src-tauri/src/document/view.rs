@@ -1,4 +1,5 @@
 use euclid::default::*;
+use std::collections::HashSet;
 use std::path::Path;
 use std::time::Duration;
 
@@ -11,6 +12,46 @@ pub enum ListMode {
     Grid4xN,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GuideOrientation {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PanButton {
+    Right,
+    Middle,
+}
+
+impl PanButton {
+    // Mouse buttons which pan the workbench when dragged. Holding space also
+    // allows panning with the left button, like in most image editors.
+    pub fn drag_buttons(&self, space_held: bool) -> Vec<MouseButton> {
+        let mut buttons = vec![match self {
+            PanButton::Right => MouseButton::Right,
+            PanButton::Middle => MouseButton::Middle,
+        }];
+        if space_held {
+            buttons.insert(0, MouseButton::Left);
+        }
+        buttons
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct WorkbenchGuide {
+    pub orientation: GuideOrientation,
+    pub position: i32,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct View {
     pub(super) animations_filter: String,
@@ -18,16 +59,24 @@ pub struct View {
     pub(super) current_animation: Option<String>,
     pub(super) current_sequence: Option<Direction>,
     pub(super) darken_sprites: bool,
+    pub(super) follow_playhead: bool,
     pub(super) frames_filter: String,
     pub(super) frames_grid_offset: u32,
     pub(super) frames_list_mode: ListMode,
     pub(super) frames_list_offset: u32,
+    pub(super) grid_spacing: u32,
+    pub(super) grid_subdivisions: u32,
+    pub(super) hidden_hitboxes: HashSet<String>,
     pub(super) hide_hitboxes: bool,
     pub(super) hide_origin: bool,
     pub(super) hide_sprite: bool,
     pub(super) hitboxes_list_offset: u32,
     pub(super) keyframe_snapping_base_duration: Duration,
     pub(super) lock_hitboxes: bool,
+    pub(super) min_keyframe_duration: Duration,
+    pub(super) origin_offset: Vector2D<i32>,
+    pub(super) pan_button: PanButton,
+    pub(super) playback_clock: Duration,
     pub(super) selection: SelectionState,
     pub(super) snap_keyframe_durations: bool,
     pub(super) snap_keyframes_to_multiples_of_duration: bool,
@@ -35,6 +84,7 @@ pub struct View {
     pub(super) timeline_clock: Duration,
     pub(super) timeline_offset: Duration,
     pub(super) timeline_zoom_amount: f32,
+    pub(super) workbench_guides: Vec<WorkbenchGuide>,
     pub(super) workbench_offset: Vector2D<f32>,
     pub(super) workbench_zoom_factor: u32,
 }
@@ -47,16 +97,24 @@ impl Default for View {
             current_animation: None,
             current_sequence: None,
             darken_sprites: true,
+            follow_playhead: true,
             frames_filter: Default::default(),
             frames_grid_offset: 0,
             frames_list_mode: ListMode::Grid4xN,
             frames_list_offset: 0,
+            grid_spacing: 16,
+            grid_subdivisions: 8,
+            hidden_hitboxes: HashSet::new(),
             hide_hitboxes: false,
             hide_origin: false,
             hide_sprite: false,
             hitboxes_list_offset: 0,
             keyframe_snapping_base_duration: Duration::from_millis(100),
             lock_hitboxes: false,
+            min_keyframe_duration: Duration::from_millis(1),
+            origin_offset: Vector2D::zero(),
+            pan_button: PanButton::Right,
+            playback_clock: Default::default(),
             selection: Default::default(),
             snap_keyframe_durations: true,
             snap_keyframes_to_multiples_of_duration: false,
@@ -64,6 +122,7 @@ impl Default for View {
             timeline_clock: Default::default(),
             timeline_offset: Default::default(),
             timeline_zoom_amount: 0.5,
+            workbench_guides: vec![],
             workbench_offset: Vector2D::<f32>::zero(),
             workbench_zoom_factor: 8,
         }
@@ -83,7 +142,7 @@ impl View {
         let old_zoom_factor = self.workbench_zoom_factor as f32;
         self.zoom_in_workbench();
         let new_zoom_factor = self.workbench_zoom_factor as f32;
-        self.workbench_offset += ((*fixed_point + self.workbench_offset)
+        self.workbench_offset += ((*fixed_point + self.workbench_origin())
             * (old_zoom_factor - new_zoom_factor))
             / new_zoom_factor;
     }
@@ -92,11 +151,28 @@ impl View {
         let old_zoom_factor = self.workbench_zoom_factor as f32;
         self.zoom_out_workbench();
         let new_zoom_factor = self.workbench_zoom_factor as f32;
-        self.workbench_offset += (*fixed_point + self.workbench_offset)
+        self.workbench_offset += (*fixed_point + self.workbench_origin())
             * (old_zoom_factor - new_zoom_factor)
             / new_zoom_factor;
     }
 
+    // Position of the animation origin relative to the center of the workbench, before zoom
+    pub(super) fn workbench_origin(&self) -> Vector2D<f32> {
+        self.workbench_offset + self.origin_offset.to_f32()
+    }
+
+    pub(super) fn set_grid_spacing(&mut self, spacing: u32) {
+        self.grid_spacing = spacing.max(1);
+    }
+
+    pub(super) fn set_grid_subdivisions(&mut self, subdivisions: u32) {
+        self.grid_subdivisions = subdivisions.max(1);
+    }
+
+    pub(super) fn set_origin_offset(&mut self, offset: Vector2D<i32>) {
+        self.origin_offset = offset;
+    }
+
     pub(super) fn set_workbench_zoom_factor(&mut self, zoom_factor: u32) {
         const MIN_WORKBENCH_ZOOM: u32 = 1;
         const MAX_WORKBENCH_ZOOM: u32 = 32;
@@ -111,6 +187,12 @@ impl View {
         self.workbench_offset = Vector2D::zero();
     }
 
+    pub(super) fn fit_workbench(&mut self, bounds: Box2D<f32>, viewport_size: Vector2D<f32>) {
+        let (zoom_factor, offset) = workbench_fit(bounds, viewport_size);
+        self.set_workbench_zoom_factor(zoom_factor);
+        self.workbench_offset = offset - self.origin_offset.to_f32();
+    }
+
     pub(super) fn zoom_in_timeline(&mut self) {
         self.adjust_timeline_zoom_amount(0.2, self.timeline_clock);
     }
@@ -148,6 +230,13 @@ impl View {
         self.timeline_zoom_amount = 0.5;
     }
 
+    pub(super) fn reset(&mut self) {
+        self.reset_workbench_zoom();
+        self.center_workbench();
+        self.reset_timeline_zoom();
+        self.timeline_clock = Duration::ZERO;
+    }
+
     pub(super) fn timeline_zoom_factor(&self) -> f32 {
         const MIN_TIMELINE_ZOOM: f32 = 0.5;
         const MAX_TIMELINE_ZOOM: f32 = 3.0;
@@ -179,10 +268,51 @@ impl View {
         self.timeline_offset = Duration::ZERO;
     }
 
+    pub(super) fn follow_playhead(&mut self, visible_duration: Duration) {
+        if let Some(offset) = timeline_offset_following_playhead(
+            self.timeline_clock,
+            self.timeline_offset,
+            visible_duration,
+        ) {
+            self.timeline_offset = offset;
+        }
+    }
+
     pub(super) fn pan(&mut self, delta: Vector2D<f32>) {
         self.workbench_offset += delta / self.workbench_zoom_factor as f32;
     }
 
+    pub(super) fn add_workbench_guide(&mut self, guide: WorkbenchGuide) {
+        self.workbench_guides.push(guide);
+    }
+
+    pub(super) fn remove_workbench_guide(&mut self, index: usize) {
+        if index < self.workbench_guides.len() {
+            self.workbench_guides.remove(index);
+        }
+    }
+
+    pub(super) fn move_workbench_guide(&mut self, index: usize, position: i32) {
+        if let Some(guide) = self.workbench_guides.get_mut(index) {
+            guide.position = position;
+        }
+    }
+
+    pub(super) fn clear_workbench_guides(&mut self) {
+        self.workbench_guides.clear();
+    }
+
+    pub(super) fn toggle_hitbox_visibility<T: AsRef<str>>(&mut self, name: T) {
+        if !self.hidden_hitboxes.remove(name.as_ref()) {
+            self.hidden_hitboxes.insert(name.as_ref().to_owned());
+        }
+    }
+
+    pub(super) fn show_all_hitboxes(&mut self) {
+        self.hide_hitboxes = false;
+        self.hidden_hitboxes.clear();
+    }
+
     pub(super) fn skip_to_timeline_start(&mut self) {
         self.timeline_clock = Duration::ZERO;
     }
@@ -195,7 +325,59 @@ impl View {
     }
 }
 
+// Only moves the timeline once the playhead leaves the visible range, so that
+// manual panning during playback is not immediately undone.
+fn timeline_offset_following_playhead(
+    clock: Duration,
+    offset: Duration,
+    visible_duration: Duration,
+) -> Option<Duration> {
+    if visible_duration.is_zero() {
+        return None;
+    }
+    if clock < offset || clock > offset + visible_duration {
+        Some(clock)
+    } else {
+        None
+    }
+}
+
+// Largest zoom factor at which `bounds` fits in the viewport with a margin on every
+// side, and the offset which centers `bounds` in the viewport.
+fn workbench_fit(bounds: Box2D<f32>, viewport_size: Vector2D<f32>) -> (u32, Vector2D<f32>) {
+    const MARGIN: f32 = 32.0;
+    let available_size = (viewport_size - Vector2D::splat(2.0 * MARGIN)).max(Vector2D::splat(1.0));
+    let bounds_size = bounds.size().max(Size2D::splat(1.0));
+    let zoom_factor = (available_size.x / bounds_size.width)
+        .min(available_size.y / bounds_size.height)
+        .floor()
+        .max(1.0) as u32;
+    (zoom_factor, -bounds.center().to_vector())
+}
+
 impl Document {
+    pub(super) fn fit_workbench_to_item(
+        &mut self,
+        frame_sizes: &HashMap<PathBuf, Vector2D<u32>>,
+        viewport_size: Vector2D<f32>,
+    ) -> DocumentResult<()> {
+        let (_, sequence) = self.workbench_sequence()?;
+        let bounds = sequence
+            .keyframes_iter()
+            .filter_map(|keyframe| {
+                let size = frame_sizes.get(keyframe.frame())?.to_i32();
+                let top_left = keyframe.offset() - (size.to_f32() / 2.0).floor().to_i32();
+                Some(Box2D::new(
+                    top_left.to_point(),
+                    (top_left + size).to_point(),
+                ))
+            })
+            .reduce(|a, b| a.union(&b))
+            .ok_or(DocumentError::SequenceHasNoKeyframes)?;
+        self.view.fit_workbench(bounds.to_f32(), viewport_size);
+        Ok(())
+    }
+
     pub fn frames_list_mode(&self) -> ListMode {
         self.view.frames_list_mode
     }
@@ -243,6 +425,41 @@ impl Document {
         self.view.workbench_zoom_factor as f32
     }
 
+    pub fn workbench_guides(&self) -> &Vec<WorkbenchGuide> {
+        &self.view.workbench_guides
+    }
+
+    // Distance in screen pixels between the center of the workbench and a guide
+    pub fn workbench_guide_offset(&self, guide: &WorkbenchGuide) -> f32 {
+        let origin = self.view.workbench_origin();
+        let pan = match guide.orientation {
+            GuideOrientation::Horizontal => origin.y,
+            GuideOrientation::Vertical => origin.x,
+        };
+        (guide.position as f32 + pan) * self.workbench_zoom()
+    }
+
+    pub fn grid_spacing(&self) -> u32 {
+        self.view.grid_spacing
+    }
+
+    pub fn pan_button(&self) -> PanButton {
+        self.view.pan_button
+    }
+
+    pub fn grid_subdivisions(&self) -> u32 {
+        self.view.grid_subdivisions
+    }
+
+    // Distance in screen pixels between two consecutive grid lines
+    pub fn workbench_grid_line_spacing(&self) -> f32 {
+        self.view.grid_spacing as f32 * self.workbench_zoom() / self.view.grid_subdivisions as f32
+    }
+
+    pub fn origin_offset(&self) -> Vector2D<i32> {
+        self.view.origin_offset
+    }
+
     pub fn timeline_zoom_amount(&self) -> f32 {
         self.view.timeline_zoom_amount
     }
@@ -259,6 +476,23 @@ impl Document {
         self.view.timeline_clock
     }
 
+    // During playback, no keyframe is current until the animation start delay has elapsed
+    pub fn current_keyframe_index(&self) -> Option<usize> {
+        let (_, animation) = self.workbench_animation().ok()?;
+        let direction = (*self.current_sequence())?;
+        if self.is_timeline_playing() {
+            animation.keyframe_index_at(direction, self.view.playback_clock)
+        } else {
+            animation
+                .sequence(direction)?
+                .keyframe_index_at(self.view.timeline_clock)
+        }
+    }
+
+    pub fn should_follow_playhead(&self) -> bool {
+        self.view.follow_playhead
+    }
+
     pub fn should_snap_keyframe_durations(&self) -> bool {
         self.view.snap_keyframe_durations
     }
@@ -275,6 +509,10 @@ impl Document {
         self.view.keyframe_snapping_base_duration
     }
 
+    pub fn min_keyframe_duration(&self) -> Duration {
+        self.view.min_keyframe_duration
+    }
+
     pub fn should_darken_sprites(&self) -> bool {
         self.view.darken_sprites
     }
@@ -287,6 +525,10 @@ impl Document {
         self.view.hide_hitboxes
     }
 
+    pub fn is_hitbox_hidden<T: AsRef<str>>(&self, name: T) -> bool {
+        self.view.hidden_hitboxes.contains(name.as_ref())
+    }
+
     pub fn is_hiding_origin(&self) -> bool {
         self.view.hide_origin
     }
@@ -308,17 +550,120 @@ impl Document {
     }
 
     pub fn is_animation_filtered_out<T: AsRef<str>>(&self, animation_name: T) -> bool {
-        let filter = self.view.animations_filter.to_lowercase();
-        !filter
-            .split_ascii_whitespace()
-            .all(|search_term| animation_name.as_ref().to_lowercase().contains(search_term))
+        !animation_matches_query(animation_name, &self.view.animations_filter)
     }
 }
 
+pub(super) fn animation_matches_query<T: AsRef<str>, U: AsRef<str>>(
+    animation_name: T,
+    query: U,
+) -> bool {
+    let query = query.as_ref().to_lowercase();
+    query
+        .split_ascii_whitespace()
+        .all(|search_term| animation_name.as_ref().to_lowercase().contains(search_term))
+}
+
 #[cfg(test)]
 mod test {
+    use euclid::{point2, vec2};
+
+    use super::*;
     use crate::mock::TigerAppMock;
 
+    #[test]
+    fn timeline_follows_playhead_only_when_out_of_view() {
+        let ms = Duration::from_millis;
+        assert_eq!(
+            timeline_offset_following_playhead(ms(500), ms(0), ms(1_000)),
+            None
+        );
+        assert_eq!(
+            timeline_offset_following_playhead(ms(1_200), ms(0), ms(1_000)),
+            Some(ms(1_200))
+        );
+        assert_eq!(
+            timeline_offset_following_playhead(ms(100), ms(400), ms(1_000)),
+            Some(ms(100))
+        );
+        assert_eq!(
+            timeline_offset_following_playhead(ms(1_200), ms(0), ms(0)),
+            None
+        );
+    }
+
+    #[test]
+    fn can_reset_view() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk_cycle",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1", "walk_2"])]),
+        );
+        d.process_command(Command::EditAnimation("walk_cycle".to_owned()))
+            .unwrap();
+        d.process_command(Command::SetWorkbenchZoomFactor(8))
+            .unwrap();
+        d.process_command(Command::Pan(vec2(40.0, -20.0))).unwrap();
+        d.process_command(Command::SetTimelineZoomAmount(0.9))
+            .unwrap();
+        d.process_command(Command::ScrubTimeline(Duration::from_millis(150)))
+            .unwrap();
+
+        d.process_command(Command::ResetView).unwrap();
+
+        let default_view = View::default();
+        assert_eq!(
+            d.view.workbench_zoom_factor,
+            default_view.workbench_zoom_factor
+        );
+        assert_eq!(d.view.workbench_offset, default_view.workbench_offset);
+        assert_eq!(
+            d.view.timeline_zoom_amount,
+            default_view.timeline_zoom_amount
+        );
+        assert_eq!(d.view.timeline_clock, default_view.timeline_clock);
+    }
+
+    #[test]
+    fn workbench_fit_centers_and_zooms_on_bounds() {
+        let bounds = Box2D::new(point2(-10.0, -20.0), point2(30.0, 0.0));
+        let (zoom_factor, offset) = workbench_fit(bounds, vec2(464.0, 264.0));
+        assert_eq!(zoom_factor, 10);
+        assert_eq!(offset, vec2(-10.0, 10.0));
+
+        let (zoom_factor, _) = workbench_fit(bounds, vec2(10.0, 10.0));
+        assert_eq!(zoom_factor, 1);
+    }
+
+    #[test]
+    fn timeline_follows_playhead_during_playback() {
+        let mut d = Document::new("tmp");
+        d.sheet.add_test_animation(
+            "walk_cycle",
+            HashMap::from([(Direction::North, vec!["walk_0", "walk_1", "walk_2"])]),
+        );
+        d.process_command(Command::EditAnimation("walk_cycle".to_owned()))
+            .unwrap();
+        d.process_command(Command::SetTimelineViewportWidth(100.0))
+            .unwrap();
+        let visible_duration = Duration::from_secs_f32(100.0 / d.timeline_zoom_factor() / 1_000.0);
+
+        d.process_command(Command::Play).unwrap();
+        d.process_command(Command::Tick(visible_duration / 2))
+            .unwrap();
+        assert_eq!(d.timeline_offset(), Duration::ZERO);
+
+        d.process_command(Command::Tick(visible_duration)).unwrap();
+        assert_eq!(d.timeline_offset(), d.timeline_clock());
+
+        d.process_command(Command::SetFollowPlayhead(false))
+            .unwrap();
+        let offset = d.timeline_offset();
+        d.process_command(Command::Tick(visible_duration * 2))
+            .unwrap();
+        assert_eq!(d.timeline_offset(), offset);
+    }
+
     #[tokio::test]
     async fn can_zoom_workbench_in_out() {
         let app = TigerAppMock::new();
@@ -347,6 +692,108 @@ mod test {
         assert_eq!(app.client_state().documents[0].workbench_offset, (0.0, 0.0));
     }
 
+    #[test]
+    fn pan_button_resolves_drag_buttons() {
+        assert_eq!(
+            PanButton::Right.drag_buttons(false),
+            vec![MouseButton::Right]
+        );
+        assert_eq!(
+            PanButton::Middle.drag_buttons(false),
+            vec![MouseButton::Middle]
+        );
+        assert_eq!(
+            PanButton::Right.drag_buttons(true),
+            vec![MouseButton::Left, MouseButton::Right]
+        );
+        assert_eq!(
+            PanButton::Middle.drag_buttons(true),
+            vec![MouseButton::Left, MouseButton::Middle]
+        );
+
+        let mut d = Document::new("tmp");
+        assert_eq!(d.pan_button(), PanButton::Right);
+        d.process_command(Command::SetPanButton(PanButton::Middle))
+            .unwrap();
+        assert_eq!(d.pan_button(), PanButton::Middle);
+    }
+
+    #[test]
+    fn grid_line_count_follows_grid_spacing() {
+        let mut d = Document::new("tmp");
+        let count_grid_lines = |d: &Document| (512.0 / d.workbench_grid_line_spacing()) as u32;
+        d.process_command(Command::SetWorkbenchZoomFactor(8))
+            .unwrap();
+        assert_eq!(count_grid_lines(&d), 32);
+
+        d.process_command(Command::SetGridSpacing(32)).unwrap();
+        assert_eq!(count_grid_lines(&d), 16);
+
+        d.process_command(Command::SetGridSubdivisions(2)).unwrap();
+        assert_eq!(count_grid_lines(&d), 4);
+    }
+
+    #[test]
+    fn origin_offset_moves_workbench_content() {
+        let mut d = Document::new("tmp");
+        let guide = WorkbenchGuide {
+            orientation: GuideOrientation::Vertical,
+            position: 10,
+        };
+        d.process_command(Command::SetWorkbenchZoomFactor(4))
+            .unwrap();
+        d.process_command(Command::SetOriginOffset(Vector2D::new(-5, 12)))
+            .unwrap();
+        assert_eq!(d.workbench_guide_offset(&guide), 20.0);
+
+        // The point under the cursor stays in place while zooming
+        let fixed_point = Vector2D::new(3.0, 7.0);
+        let screen_position = |d: &Document| {
+            (fixed_point + d.workbench_offset() + d.origin_offset().to_f32()) * d.workbench_zoom()
+        };
+        let before = screen_position(&d);
+        d.process_command(Command::ZoomInWorkbenchAround(fixed_point))
+            .unwrap();
+        assert_eq!(screen_position(&d), before);
+    }
+
+    #[test]
+    fn workbench_guides_follow_pan_and_zoom() {
+        let mut d = Document::new("tmp");
+        let vertical = WorkbenchGuide {
+            orientation: GuideOrientation::Vertical,
+            position: 10,
+        };
+        let horizontal = WorkbenchGuide {
+            orientation: GuideOrientation::Horizontal,
+            position: -4,
+        };
+        d.process_command(Command::AddWorkbenchGuide(vertical))
+            .unwrap();
+        d.process_command(Command::AddWorkbenchGuide(horizontal))
+            .unwrap();
+        assert_eq!(d.workbench_guides(), &vec![vertical, horizontal]);
+
+        d.process_command(Command::SetWorkbenchZoomFactor(4))
+            .unwrap();
+        assert_eq!(d.workbench_guide_offset(&vertical), 40.0);
+        assert_eq!(d.workbench_guide_offset(&horizontal), -16.0);
+
+        d.process_command(Command::Pan(Vector2D::new(8.0, 20.0)))
+            .unwrap();
+        assert_eq!(d.workbench_guide_offset(&vertical), 48.0);
+        assert_eq!(d.workbench_guide_offset(&horizontal), 4.0);
+
+        d.process_command(Command::MoveWorkbenchGuide(0, 12))
+            .unwrap();
+        d.process_command(Command::RemoveWorkbenchGuide(1)).unwrap();
+        assert_eq!(d.workbench_guides()[0].position, 12);
+        assert_eq!(d.workbench_guides().len(), 1);
+
+        d.process_command(Command::ClearWorkbenchGuides).unwrap();
+        assert!(d.workbench_guides().is_empty());
+    }
+
     #[tokio::test]
     async fn can_zoom_timeline_in_out() {
         let app = TigerAppMock::new();
@@ -2,11 +2,13 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 use uuid::Uuid;
 
 use crate::document::{self};
 use crate::sheet::{self, Paths};
 use crate::state;
+use crate::theme;
 
 // Typescript: @/stores/state
 
@@ -16,11 +18,31 @@ pub struct State {
     pub documents: Vec<Document>,
     pub current_document_path: Option<PathBuf>,
     pub recent_document_paths: Vec<RecentDocument>,
+    pub restore_session_on_startup: bool,
+    pub save_on_focus_loss: bool,
+    pub autosave_interval_secs: u64,
+    pub keyframe_colors: KeyframeColors,
     pub clipboard_manifest: Option<ClipboardManifest>,
     pub is_release_build: bool,
     pub error: Option<UserFacingError>,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyframeColors {
+    pub fill: (u8, u8, u8),
+    pub outline: (u8, u8, u8),
+    pub text: (u8, u8, u8),
+    pub selected_fill: (u8, u8, u8),
+    pub selected_outline: (u8, u8, u8),
+    pub selected_text: (u8, u8, u8),
+    // Text colors actually used by the timeline, after adjusting for contrast
+    #[serde(default, skip_deserializing)]
+    pub readable_text: (u8, u8, u8),
+    #[serde(default, skip_deserializing)]
+    pub readable_selected_text: (u8, u8, u8),
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct RecentDocument {
@@ -54,13 +76,18 @@ pub struct Document {
     pub current_keyframe_index: Option<usize>,
     pub current_sequence_direction: Option<Direction>,
     pub darken_sprites: bool,
+    pub default_preserve_aspect_ratio: bool,
     pub export_settings_being_edited: Option<ExportSettings>,
     pub export_settings_validation: Option<ExportSettingsValidation>,
+    pub follow_playhead: bool,
     pub frames_being_relocated: Option<HashMap<PathBuf, PathBuf>>,
     pub frames_being_dragged: HashSet<PathBuf>,
     pub frames_filter: String,
     pub frames_list_mode: ListMode,
     pub frames_list_offset: u32,
+    pub grid_spacing: u32,
+    pub grid_subdivisions: u32,
+    pub has_recoverable_autosave: bool,
     pub has_unsaved_changes: bool,
     pub hide_hitboxes: bool,
     pub hide_origin: bool,
@@ -76,9 +103,13 @@ pub struct Document {
     pub last_interacted_frame: Option<PathBuf>,
     pub last_interacted_hitbox: Option<String>,
     pub lock_hitboxes: bool,
+    pub min_keyframe_duration_millis: u64,
     pub name: String,
+    pub origin_offset: (i32, i32),
+    pub pan_button: PanButton,
     pub path: PathBuf,
     pub preserve_aspect_ratio: bool,
+    pub precise_durations: bool,
     pub redo_effect: Option<String>,
     pub sheet: Sheet,
     pub snap_keyframe_durations: bool,
@@ -91,7 +122,11 @@ pub struct Document {
     pub timeline_zoom_factor: f32,
     pub undo_effect: Option<String>,
     pub was_close_requested: bool,
+    pub workbench_grid_line_spacing: f32,
+    pub workbench_guides: Vec<WorkbenchGuide>,
     pub workbench_offset: (f32, f32),
+    pub workbench_pan_buttons: Vec<MouseButton>,
+    pub workbench_space_pan_buttons: Vec<MouseButton>,
     pub workbench_zoom: f32,
 }
 
@@ -107,9 +142,11 @@ pub struct Sheet {
 pub struct Frame {
     pub path: PathBuf,
     pub name: String,
+    pub ambiguous_name: bool,
     pub selected: bool,
     pub filtered_out: bool,
     pub missing_on_disk: bool,
+    pub normal_map: Option<PathBuf>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -121,6 +158,8 @@ pub struct Animation {
     pub sequences: HashMap<Direction, Sequence>,
     pub direction_preset: Option<DirectionPreset>,
     pub is_looping: bool,
+    pub start_delay_millis: u64,
+    pub origin: (i32, i32),
     pub key: Uuid,
 }
 
@@ -161,8 +200,14 @@ pub struct Keyframe {
     pub selected: bool,
     pub start_time_millis: u64,
     pub duration_millis: u64,
+    pub duration_micros: u64,
+    pub hold: u8,
     pub offset: (i32, i32),
+    pub events: Vec<String>,
     pub hitboxes: Vec<Hitbox>,
+    pub anchors: Vec<Anchor>,
+    pub missing_on_disk: bool,
+    pub thumbnail_size: Option<u32>,
     pub key: Uuid,
 }
 
@@ -171,17 +216,87 @@ pub struct Keyframe {
 pub struct Hitbox {
     pub name: String,
     pub selected: bool,
+    pub hidden: bool,
+    pub kind: HitboxKind,
     pub top_left: (i32, i32),
     pub size: (u32, u32),
     pub key: Uuid,
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum HitboxKind {
+    Generic,
+    Hurtbox,
+    Attack,
+    Grab,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Anchor {
+    pub name: String,
+    pub position: (i32, i32),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameUsage {
+    pub animation_name: String,
+    pub direction: Direction,
+    pub keyframe_index: usize,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyframeSummary {
+    pub direction: Direction,
+    pub frame_path: PathBuf,
+    pub duration_millis: u64,
+    pub offset: (i32, i32),
+    pub hitbox_count: usize,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackingStats {
+    pub used_area: u64,
+    pub atlas_area: u64,
+    pub efficiency: f32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkbenchGuide {
+    pub orientation: GuideOrientation,
+    pub position: i32,
+    pub offset: f32,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum GuideOrientation {
+    Horizontal,
+    Vertical,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum ListMode {
     Linear,
     Grid4xN,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum PanButton {
+    Right,
+    Middle,
+}
+
 #[derive(Clone, Deserialize)]
 pub enum ResizeAxis {
     N,
@@ -194,6 +309,13 @@ pub enum ResizeAxis {
     SW,
 }
 
+#[derive(Clone, Deserialize)]
+pub enum Case {
+    Snake,
+    Kebab,
+    Camel,
+}
+
 #[derive(Clone, Deserialize)]
 pub enum NudgeDirection {
     Up,
@@ -202,6 +324,15 @@ pub enum NudgeDirection {
     Right,
 }
 
+#[derive(Clone, Deserialize)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+    Top,
+    Bottom,
+}
+
 #[derive(Clone, Deserialize)]
 pub enum BrowseDirection {
     Up,
@@ -217,6 +348,57 @@ pub struct ExportSettings {
     pub texture_file: PathBuf,
     pub metadata_file: PathBuf,
     pub metadata_paths_root: PathBuf,
+    pub format: ExportFormat,
+    pub texture_format: TextureFormat,
+    pub texture_scale: u32,
+    pub packing_algorithm: PackingAlgorithm,
+    pub packing_order: PackingOrder,
+    pub path_style: PathStyle,
+    pub deduplicate_frames: bool,
+    pub allow_rotation: bool,
+    pub mip_safe_padding: u32,
+    pub write_manifest: bool,
+    pub additional_targets: Vec<ExportTarget>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportTarget {
+    pub template_file: PathBuf,
+    pub metadata_file: PathBuf,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ExportFormat {
+    Template,
+    GodotSpriteFrames,
+    Css,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum TextureFormat {
+    Png,
+    WebP,
+    Qoi,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PackingAlgorithm {
+    MaxRects,
+    Shelf,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PackingOrder {
+    Sorted,
+    Sheet,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PathStyle {
+    AbsolutePath,
+    RelativeToRoot,
+    BasenameOnly,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
@@ -227,6 +409,9 @@ pub enum ExportSettingsError {
     FileNotFound,
     #[serde(rename = "templateError")]
     TemplateError(String),
+    InvalidTextureScale,
+    InvalidTextureExtension,
+    UnsupportedRotation,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -237,6 +422,16 @@ pub struct ExportSettingsValidation {
     pub texture_file_error: Option<ExportSettingsError>,
     pub metadata_file_error: Option<ExportSettingsError>,
     pub metadata_paths_root_error: Option<ExportSettingsError>,
+    pub texture_scale_error: Option<ExportSettingsError>,
+    pub allow_rotation_error: Option<ExportSettingsError>,
+    pub additional_targets: Vec<ExportTargetValidation>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportTargetValidation {
+    pub template_file_error: Option<ExportSettingsError>,
+    pub metadata_file_error: Option<ExportSettingsError>,
 }
 
 #[derive(Clone, Copy)]
@@ -313,6 +508,10 @@ impl state::State {
                     name: d.to_file_name(),
                 })
                 .collect(),
+            restore_session_on_startup: self.session().restore_on_startup,
+            save_on_focus_loss: self.session().save_on_focus_loss,
+            autosave_interval_secs: self.session().autosave_interval_secs,
+            keyframe_colors: (&self.session().keyframe_colors).into(),
             clipboard_manifest: self.clipboard_manifest().as_ref().map(|m| m.into()),
             is_release_build: !cfg!(debug_assertions),
             error: self.error().map(|e| e.into()),
@@ -320,6 +519,34 @@ impl state::State {
     }
 }
 
+impl From<&theme::KeyframeColors> for KeyframeColors {
+    fn from(colors: &theme::KeyframeColors) -> Self {
+        Self {
+            fill: colors.fill.into(),
+            outline: colors.outline.into(),
+            text: colors.text.into(),
+            selected_fill: colors.selected_fill.into(),
+            selected_outline: colors.selected_outline.into(),
+            selected_text: colors.selected_text.into(),
+            readable_text: colors.readable_text().into(),
+            readable_selected_text: colors.readable_selected_text().into(),
+        }
+    }
+}
+
+impl From<KeyframeColors> for theme::KeyframeColors {
+    fn from(colors: KeyframeColors) -> Self {
+        Self {
+            fill: colors.fill.into(),
+            outline: colors.outline.into(),
+            text: colors.text.into(),
+            selected_fill: colors.selected_fill.into(),
+            selected_outline: colors.selected_outline.into(),
+            selected_text: colors.selected_text.into(),
+        }
+    }
+}
+
 impl From<&state::UserFacingError> for UserFacingError {
     fn from(error: &state::UserFacingError) -> Self {
         Self {
@@ -363,15 +590,19 @@ impl document::Document {
             animation.selected = self.selection().is_animation_selected(&animation.name);
             animation.filtered_out = self.is_animation_filtered_out(&animation.name);
             for (direction, sequence) in animation.sequences.iter_mut() {
-                let mut time_millis = 0;
+                let mut time_micros = 0;
                 for (index, keyframe) in sequence.keyframes.iter_mut().enumerate() {
                     keyframe.selected = self.selection().is_keyframe_selected(
                         &animation.name,
                         (*direction).into(),
                         index,
                     );
-                    keyframe.start_time_millis = time_millis;
-                    time_millis += keyframe.duration_millis;
+                    keyframe.start_time_millis = time_micros / 1_000;
+                    keyframe.missing_on_disk = self.is_frame_missing_on_disk(&keyframe.frame);
+                    keyframe.thumbnail_size = self.keyframe_thumbnail_size(Duration::from_micros(
+                        keyframe.duration_micros * keyframe.hold as u64,
+                    ));
+                    time_micros += keyframe.duration_micros * keyframe.hold as u64;
                     for hitbox in keyframe.hitboxes.iter_mut() {
                         hitbox.selected = self.selection().is_hitbox_selected(
                             &animation.name,
@@ -379,6 +610,7 @@ impl document::Document {
                             index,
                             &hitbox.name,
                         );
+                        hitbox.hidden = self.is_hitbox_hidden(&hitbox.name);
                     }
                 }
             }
@@ -389,19 +621,21 @@ impl document::Document {
             animations_filter: self.animations_filter().to_owned(),
             animations_list_offset: self.animations_list_offset(),
             current_animation_name: self.current_animation().to_owned(),
-            current_keyframe_index: self
-                .workbench_sequence()
-                .ok()
-                .and_then(|(_, s)| s.keyframe_index_at(self.timeline_clock())),
+            current_keyframe_index: self.current_keyframe_index(),
             current_sequence_direction: self.current_sequence().map(|d| d.into()),
             darken_sprites: self.should_darken_sprites(),
+            default_preserve_aspect_ratio: self.preserves_aspect_ratio_by_default(),
             export_settings_being_edited: self.export_settings_edit().ok().map(|s| s.into()),
             export_settings_validation: self.validate_export_settings().ok().map(|s| (&s).into()),
+            follow_playhead: self.should_follow_playhead(),
             frames_being_dragged: self.frames_being_dragged(),
             frames_being_relocated: self.relocate_frames_edit().ok().cloned(),
             frames_filter: self.frames_filter().to_owned(),
             frames_list_mode: self.frames_list_mode().into(),
             frames_list_offset: self.frames_list_offset(),
+            grid_spacing: self.grid_spacing(),
+            grid_subdivisions: self.grid_subdivisions(),
+            has_recoverable_autosave: self.has_recoverable_autosave(),
             has_unsaved_changes: !self.is_saved(),
             hide_hitboxes: self.is_hiding_hitboxes(),
             hide_origin: self.is_hiding_origin(),
@@ -435,9 +669,13 @@ impl document::Document {
                 .as_ref()
                 .map(|(_, _, _, h)| h.to_owned()),
             lock_hitboxes: self.are_hitboxes_locked(),
+            min_keyframe_duration_millis: self.min_keyframe_duration().as_millis() as u64,
             name: self.path().to_file_name(),
+            origin_offset: self.origin_offset().to_tuple(),
+            pan_button: self.pan_button().into(),
             path: self.path().to_owned(),
             preserve_aspect_ratio: self.preserves_aspect_ratio(),
+            precise_durations: self.uses_precise_durations(),
             redo_effect: self.redo_effect(),
             sheet,
             snap_keyframe_durations: self.should_snap_keyframe_durations(),
@@ -451,7 +689,29 @@ impl document::Document {
             timeline_zoom_factor: self.timeline_zoom_factor(),
             undo_effect: self.undo_effect(),
             was_close_requested: self.close_requested(),
+            workbench_grid_line_spacing: self.workbench_grid_line_spacing(),
+            workbench_guides: self
+                .workbench_guides()
+                .iter()
+                .map(|g| WorkbenchGuide {
+                    orientation: g.orientation.into(),
+                    position: g.position,
+                    offset: self.workbench_guide_offset(g),
+                })
+                .collect(),
             workbench_offset: self.workbench_offset().to_tuple(),
+            workbench_pan_buttons: self
+                .pan_button()
+                .drag_buttons(false)
+                .into_iter()
+                .map(|b| b.into())
+                .collect(),
+            workbench_space_pan_buttons: self
+                .pan_button()
+                .drag_buttons(true)
+                .into_iter()
+                .map(|b| b.into())
+                .collect(),
             workbench_zoom: self.workbench_zoom(),
         }
     }
@@ -461,7 +721,15 @@ impl<P: Paths> sheet::Sheet<P> {
     fn to_dto(&self, trim: SheetTrim) -> Sheet {
         Sheet {
             frames: match trim {
-                SheetTrim::Full => self.sorted_frames().into_iter().map(|f| f.into()).collect(),
+                SheetTrim::Full => {
+                    let mut frames = self
+                        .sorted_frames()
+                        .into_iter()
+                        .map(|f| f.into())
+                        .collect::<Vec<Frame>>();
+                    disambiguate_frame_names(&mut frames);
+                    frames
+                }
                 SheetTrim::OnlyAnimation(_) | SheetTrim::Empty => vec![],
             },
             animations: self
@@ -483,14 +751,32 @@ impl<P: Paths> sheet::Sheet<P> {
     }
 }
 
+fn disambiguate_frame_names(frames: &mut [Frame]) {
+    let mut occurrences = HashMap::<String, usize>::new();
+    for frame in frames.iter() {
+        *occurrences.entry(frame.path.to_file_name()).or_default() += 1;
+    }
+    for frame in frames.iter_mut() {
+        if occurrences[&frame.path.to_file_name()] < 2 {
+            continue;
+        }
+        frame.ambiguous_name = true;
+        if let Some(parent) = frame.path.parent().filter(|p| p.file_name().is_some()) {
+            frame.name = format!("{}/{}", parent.to_file_name(), frame.name);
+        }
+    }
+}
+
 impl<P: Paths> From<&sheet::Frame<P>> for Frame {
     fn from(frame: &sheet::Frame<P>) -> Self {
         Self {
             path: frame.source().to_owned(),
             name: frame.source().to_file_stem(),
+            ambiguous_name: false,
             selected: false,
             filtered_out: false,
             missing_on_disk: false,
+            normal_map: frame.normal_source().map(Path::to_path_buf),
         }
     }
 }
@@ -507,6 +793,8 @@ impl<P: Paths> sheet::Animation<P> {
                 .collect(),
             direction_preset: self.direction_preset().map(|p| p.into()),
             is_looping: self.looping(),
+            start_delay_millis: self.start_delay().as_millis() as u64,
+            origin: self.origin().to_tuple(),
             key: self.key(),
         }
     }
@@ -585,12 +873,24 @@ impl<P: Paths> From<&sheet::Keyframe<P>> for Keyframe {
             selected: false,
             start_time_millis: 0,
             duration_millis: keyframe.duration_millis(),
+            duration_micros: keyframe.duration_micros(),
+            hold: keyframe.hold(),
             offset: keyframe.offset().to_tuple(),
+            events: keyframe.events_iter().cloned().collect(),
             hitboxes: keyframe
                 .sorted_hitboxes()
                 .into_iter()
                 .map(|(n, h)| (n.clone(), h).into())
                 .collect(),
+            anchors: keyframe
+                .anchors_iter()
+                .map(|(name, position)| Anchor {
+                    name: name.clone(),
+                    position: position.to_tuple(),
+                })
+                .collect(),
+            missing_on_disk: false,
+            thumbnail_size: None,
             key: keyframe.key(),
         }
     }
@@ -601,6 +901,8 @@ impl From<(String, &sheet::Hitbox)> for Hitbox {
         Self {
             name,
             selected: false,
+            hidden: false,
+            kind: hitbox.kind().into(),
             top_left: hitbox.position().to_tuple(),
             size: hitbox.size().to_tuple(),
             key: hitbox.key(),
@@ -608,6 +910,56 @@ impl From<(String, &sheet::Hitbox)> for Hitbox {
     }
 }
 
+impl From<sheet::HitboxKind> for HitboxKind {
+    fn from(kind: sheet::HitboxKind) -> Self {
+        match kind {
+            sheet::HitboxKind::Generic => HitboxKind::Generic,
+            sheet::HitboxKind::Hurtbox => HitboxKind::Hurtbox,
+            sheet::HitboxKind::Attack => HitboxKind::Attack,
+            sheet::HitboxKind::Grab => HitboxKind::Grab,
+        }
+    }
+}
+
+impl From<HitboxKind> for sheet::HitboxKind {
+    fn from(kind: HitboxKind) -> Self {
+        match kind {
+            HitboxKind::Generic => sheet::HitboxKind::Generic,
+            HitboxKind::Hurtbox => sheet::HitboxKind::Hurtbox,
+            HitboxKind::Attack => sheet::HitboxKind::Attack,
+            HitboxKind::Grab => sheet::HitboxKind::Grab,
+        }
+    }
+}
+
+impl From<document::MouseButton> for MouseButton {
+    fn from(button: document::MouseButton) -> Self {
+        match button {
+            document::MouseButton::Left => MouseButton::Left,
+            document::MouseButton::Middle => MouseButton::Middle,
+            document::MouseButton::Right => MouseButton::Right,
+        }
+    }
+}
+
+impl From<PanButton> for document::PanButton {
+    fn from(button: PanButton) -> Self {
+        match button {
+            PanButton::Right => document::PanButton::Right,
+            PanButton::Middle => document::PanButton::Middle,
+        }
+    }
+}
+
+impl From<document::PanButton> for PanButton {
+    fn from(button: document::PanButton) -> Self {
+        match button {
+            document::PanButton::Right => PanButton::Right,
+            document::PanButton::Middle => PanButton::Middle,
+        }
+    }
+}
+
 impl From<ListMode> for document::ListMode {
     fn from(list_mode: ListMode) -> Self {
         match list_mode {
@@ -626,6 +978,24 @@ impl From<document::ListMode> for ListMode {
     }
 }
 
+impl From<GuideOrientation> for document::GuideOrientation {
+    fn from(orientation: GuideOrientation) -> Self {
+        match orientation {
+            GuideOrientation::Horizontal => document::GuideOrientation::Horizontal,
+            GuideOrientation::Vertical => document::GuideOrientation::Vertical,
+        }
+    }
+}
+
+impl From<document::GuideOrientation> for GuideOrientation {
+    fn from(orientation: document::GuideOrientation) -> Self {
+        match orientation {
+            document::GuideOrientation::Horizontal => GuideOrientation::Horizontal,
+            document::GuideOrientation::Vertical => GuideOrientation::Vertical,
+        }
+    }
+}
+
 impl From<ResizeAxis> for document::ResizeAxis {
     fn from(resize_axis: ResizeAxis) -> Self {
         match resize_axis {
@@ -652,6 +1022,28 @@ impl From<NudgeDirection> for document::NudgeDirection {
     }
 }
 
+impl From<Align> for document::Align {
+    fn from(align: Align) -> Self {
+        match align {
+            Align::Left => document::Align::Left,
+            Align::Center => document::Align::Center,
+            Align::Right => document::Align::Right,
+            Align::Top => document::Align::Top,
+            Align::Bottom => document::Align::Bottom,
+        }
+    }
+}
+
+impl From<Case> for document::Case {
+    fn from(case: Case) -> Self {
+        match case {
+            Case::Snake => document::Case::Snake,
+            Case::Kebab => document::Case::Kebab,
+            Case::Camel => document::Case::Camel,
+        }
+    }
+}
+
 impl From<BrowseDirection> for document::BrowseDirection {
     fn from(direction: BrowseDirection) -> Self {
         match direction {
@@ -671,20 +1063,171 @@ impl<P: Paths> From<&sheet::ExportSettings<P>> for ExportSettings {
                 texture_file: template_settings.texture_file().to_owned(),
                 metadata_file: template_settings.metadata_file().to_owned(),
                 metadata_paths_root: template_settings.metadata_paths_root().to_owned(),
+                format: template_settings.format().into(),
+                texture_format: template_settings.texture_format().into(),
+                texture_scale: template_settings.texture_scale(),
+                packing_algorithm: template_settings.packing_algorithm().into(),
+                packing_order: template_settings.packing_order().into(),
+                path_style: template_settings.path_style().into(),
+                deduplicate_frames: template_settings.deduplicate_frames(),
+                allow_rotation: template_settings.allow_rotation(),
+                mip_safe_padding: template_settings.mip_safe_padding(),
+                write_manifest: template_settings.write_manifest(),
+                additional_targets: template_settings
+                    .additional_targets()
+                    .iter()
+                    .map(|t| ExportTarget {
+                        template_file: t.template_file().to_owned(),
+                        metadata_file: t.metadata_file().to_owned(),
+                    })
+                    .collect(),
             },
         }
     }
 }
 
+impl From<ExportSettings> for sheet::ExportSettings<sheet::Any> {
+    fn from(settings: ExportSettings) -> Self {
+        let mut template_settings = sheet::TemplateExportSettings::<sheet::Any>::default();
+        template_settings.set_template_file(settings.template_file);
+        template_settings.set_texture_file(settings.texture_file);
+        template_settings.set_metadata_file(settings.metadata_file);
+        template_settings.set_metadata_paths_root(settings.metadata_paths_root);
+        template_settings.set_format(settings.format.into());
+        template_settings.set_texture_format(settings.texture_format.into());
+        template_settings.set_texture_scale(settings.texture_scale);
+        template_settings.set_packing_algorithm(settings.packing_algorithm.into());
+        template_settings.set_packing_order(settings.packing_order.into());
+        template_settings.set_path_style(settings.path_style.into());
+        template_settings.set_deduplicate_frames(settings.deduplicate_frames);
+        template_settings.set_allow_rotation(settings.allow_rotation);
+        template_settings.set_mip_safe_padding(settings.mip_safe_padding);
+        template_settings.set_write_manifest(settings.write_manifest);
+        for (index, target) in settings.additional_targets.into_iter().enumerate() {
+            template_settings.add_target();
+            let new_target = template_settings.target_mut(index).unwrap();
+            new_target.set_template_file(target.template_file);
+            new_target.set_metadata_file(target.metadata_file);
+        }
+        sheet::ExportSettings::Template(template_settings)
+    }
+}
+
+impl From<sheet::ExportFormat> for ExportFormat {
+    fn from(format: sheet::ExportFormat) -> Self {
+        match format {
+            sheet::ExportFormat::Template => ExportFormat::Template,
+            sheet::ExportFormat::GodotSpriteFrames => ExportFormat::GodotSpriteFrames,
+            sheet::ExportFormat::Css => ExportFormat::Css,
+        }
+    }
+}
+
+impl From<ExportFormat> for sheet::ExportFormat {
+    fn from(format: ExportFormat) -> Self {
+        match format {
+            ExportFormat::Template => sheet::ExportFormat::Template,
+            ExportFormat::GodotSpriteFrames => sheet::ExportFormat::GodotSpriteFrames,
+            ExportFormat::Css => sheet::ExportFormat::Css,
+        }
+    }
+}
+
+impl From<sheet::TextureFormat> for TextureFormat {
+    fn from(format: sheet::TextureFormat) -> Self {
+        match format {
+            sheet::TextureFormat::Png => TextureFormat::Png,
+            sheet::TextureFormat::WebP => TextureFormat::WebP,
+            sheet::TextureFormat::Qoi => TextureFormat::Qoi,
+        }
+    }
+}
+
+impl From<TextureFormat> for sheet::TextureFormat {
+    fn from(format: TextureFormat) -> Self {
+        match format {
+            TextureFormat::Png => sheet::TextureFormat::Png,
+            TextureFormat::WebP => sheet::TextureFormat::WebP,
+            TextureFormat::Qoi => sheet::TextureFormat::Qoi,
+        }
+    }
+}
+
+impl From<sheet::PackingAlgorithm> for PackingAlgorithm {
+    fn from(algorithm: sheet::PackingAlgorithm) -> Self {
+        match algorithm {
+            sheet::PackingAlgorithm::MaxRects => PackingAlgorithm::MaxRects,
+            sheet::PackingAlgorithm::Shelf => PackingAlgorithm::Shelf,
+        }
+    }
+}
+
+impl From<PackingAlgorithm> for sheet::PackingAlgorithm {
+    fn from(algorithm: PackingAlgorithm) -> Self {
+        match algorithm {
+            PackingAlgorithm::MaxRects => sheet::PackingAlgorithm::MaxRects,
+            PackingAlgorithm::Shelf => sheet::PackingAlgorithm::Shelf,
+        }
+    }
+}
+
+impl From<sheet::PackingOrder> for PackingOrder {
+    fn from(order: sheet::PackingOrder) -> Self {
+        match order {
+            sheet::PackingOrder::Sorted => PackingOrder::Sorted,
+            sheet::PackingOrder::Sheet => PackingOrder::Sheet,
+        }
+    }
+}
+
+impl From<PackingOrder> for sheet::PackingOrder {
+    fn from(order: PackingOrder) -> Self {
+        match order {
+            PackingOrder::Sorted => sheet::PackingOrder::Sorted,
+            PackingOrder::Sheet => sheet::PackingOrder::Sheet,
+        }
+    }
+}
+
+impl From<sheet::PathStyle> for PathStyle {
+    fn from(style: sheet::PathStyle) -> Self {
+        match style {
+            sheet::PathStyle::AbsolutePath => PathStyle::AbsolutePath,
+            sheet::PathStyle::RelativeToRoot => PathStyle::RelativeToRoot,
+            sheet::PathStyle::BasenameOnly => PathStyle::BasenameOnly,
+        }
+    }
+}
+
+impl From<PathStyle> for sheet::PathStyle {
+    fn from(style: PathStyle) -> Self {
+        match style {
+            PathStyle::AbsolutePath => sheet::PathStyle::AbsolutePath,
+            PathStyle::RelativeToRoot => sheet::PathStyle::RelativeToRoot,
+            PathStyle::BasenameOnly => sheet::PathStyle::BasenameOnly,
+        }
+    }
+}
+
 impl From<&document::ExportSettingsValidation> for ExportSettingsValidation {
     fn from(validation: &document::ExportSettingsValidation) -> Self {
         match validation {
             document::ExportSettingsValidation::Template(s) => Self {
-                valid_settings: *s == document::TemplateExportSettingsValidation::default(),
+                valid_settings: s.is_valid(),
                 template_file_error: s.template_file_error().map(|e| e.into()),
                 texture_file_error: s.texture_file_error().map(|e| e.into()),
                 metadata_file_error: s.metadata_file_error().map(|e| e.into()),
                 metadata_paths_root_error: s.metadata_paths_root_error().map(|e| e.into()),
+                texture_scale_error: s.texture_scale_error().map(|e| e.into()),
+                allow_rotation_error: s.allow_rotation_error().map(|e| e.into()),
+                additional_targets: s
+                    .additional_targets()
+                    .iter()
+                    .map(|t| ExportTargetValidation {
+                        template_file_error: t.template_file_error().map(|e| e.into()),
+                        metadata_file_error: t.metadata_file_error().map(|e| e.into()),
+                    })
+                    .collect(),
             },
         }
     }
@@ -704,6 +1247,15 @@ impl From<&document::ExportSettingsError> for ExportSettingsError {
             document::ExportSettingsError::TemplateError(details) => {
                 ExportSettingsError::TemplateError(details.clone())
             }
+            document::ExportSettingsError::InvalidTextureScale => {
+                ExportSettingsError::InvalidTextureScale
+            }
+            document::ExportSettingsError::InvalidTextureExtension => {
+                ExportSettingsError::InvalidTextureExtension
+            }
+            document::ExportSettingsError::UnsupportedRotation => {
+                ExportSettingsError::UnsupportedRotation
+            }
         }
     }
 }
@@ -725,6 +1277,68 @@ mod test {
         assert!(!dto.documents[1].sheet.animations.is_empty());
     }
 
+    #[test]
+    fn disambiguates_frames_with_identical_file_names() {
+        let mut document = document::Document::new("tmp");
+        document
+            .process_command(document::Command::ImportFrames(vec![
+                PathBuf::from("run/0.png"),
+                PathBuf::from("walk/0.png"),
+                PathBuf::from("walk/1.png"),
+            ]))
+            .unwrap();
+
+        let dto = document.to_dto(DocumentTrim::Full);
+        let frames = dto
+            .sheet
+            .frames
+            .iter()
+            .map(|f| (f.name.as_str(), f.ambiguous_name))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            frames,
+            vec![("run/0", true), ("walk/0", true), ("1", false)]
+        );
+    }
+
+    #[test]
+    fn can_hide_individual_hitboxes() {
+        let mut document = document::Document::open("test-data/flame.tiger").unwrap();
+        document
+            .process_command(document::Command::EditAnimation("idle".to_owned()))
+            .unwrap();
+        let is_hit_hidden = |document: &document::Document| {
+            let dto = document.to_dto(DocumentTrim::Full);
+            let animation = dto.sheet.animations.iter().find(|a| a.name == "idle");
+            let keyframe = &animation.unwrap().sequences[&Direction::North].keyframes[0];
+            keyframe
+                .hitboxes
+                .iter()
+                .find(|h| h.name == "hit")
+                .unwrap()
+                .hidden
+        };
+        assert!(!is_hit_hidden(&document));
+
+        document
+            .process_command(document::Command::ToggleHitboxVisibility("hit".to_owned()))
+            .unwrap();
+        assert!(is_hit_hidden(&document));
+
+        document
+            .process_command(document::Command::ToggleHitboxVisibility("hit".to_owned()))
+            .unwrap();
+        assert!(!is_hit_hidden(&document));
+
+        document
+            .process_command(document::Command::ToggleHitboxVisibility("hit".to_owned()))
+            .unwrap();
+        document
+            .process_command(document::Command::ShowAllHitboxes)
+            .unwrap();
+        assert!(!is_hit_hidden(&document));
+    }
+
     #[test]
     fn can_trim_inactive_documents() {
         let mut state = state::State::default();
@@ -1,24 +1,39 @@
-use image::ImageError;
+use image::{DynamicImage, ImageError};
 use std::{
+    collections::{HashMap, HashSet},
     fs::{create_dir_all, File},
-    io::Write,
-    path::{Path, PathBuf},
+    io::{Cursor, Write},
+    path::{Component, Path, PathBuf},
 };
 use thiserror::Error;
 
 use crate::features::texture_cache;
 use crate::sheet::*;
 
+mod contact_sheet;
+mod css;
+mod godot;
+mod manifest;
 mod metadata;
+mod packing;
 mod texture;
 
+pub use manifest::*;
 pub use metadata::*;
 pub use texture::*;
 
+type TextureLayout = HashMap<PathBuf, PackedFrame>;
+
 #[derive(Error, Debug)]
 pub enum ExportError {
     #[error("Missing export settings")]
     NoExportSettings,
+    #[error("Spritesheet has no animations")]
+    NoAnimations,
+    #[error("Texture scale must be at least 1")]
+    InvalidTextureScale,
+    #[error("Could not read frame `{0}`")]
+    FrameReadError(PathBuf),
     #[error("Filesystem error for `{0}`: `{1}`")]
     IoError(PathBuf, std::io::Error),
     #[error("{0}")]
@@ -40,28 +55,14 @@ pub fn export_sheet(
 
     match export_settings {
         ExportSettings::Template(template_settings) => {
-            let packed_sheet = pack_sheet(sheet, texture_cache)?;
-            let metadata = generate_sheet_metadata(sheet, export_settings, packed_sheet.layout())?;
-
-            {
-                let path = template_settings.metadata_file();
-                if let Some(directory) = path.parent() {
-                    create_dir(directory)?;
-                }
-                let mut file = create_file(path)?;
-                file.write_all(&metadata.into_bytes())
-                    .map_err(|e| ExportError::IoError(path.to_owned(), e))?;
+            let (outputs, manifest) = render_template_export(sheet, texture_cache)?;
+            for (path, content) in outputs {
+                write_file(&path, &content)?;
             }
-
-            {
-                let path = template_settings.texture_file();
-                if let Some(directory) = path.parent() {
-                    create_dir(directory)?;
-                }
-                let mut file = create_file(path)?;
-                packed_sheet
-                    .texture()
-                    .write_to(&mut file, image::ImageFormat::Png)?;
+            if template_settings.write_manifest() {
+                let content = serde_json::to_vec_pretty(&manifest)
+                    .expect("Manifests should always be serializable");
+                write_file(&template_settings.manifest_file(), &content)?;
             }
         }
     }
@@ -69,6 +70,227 @@ pub fn export_sheet(
     Ok(())
 }
 
+// Compares what an export would produce against the manifest written by the previous export.
+// Sheets without a manifest on disk are always considered changed.
+pub fn was_export_changed(
+    sheet: &Sheet<Absolute>,
+    texture_cache: texture_cache::Handle,
+) -> Result<bool, ExportError> {
+    let export_settings = sheet
+        .export_settings()
+        .as_ref()
+        .ok_or(ExportError::NoExportSettings)?;
+
+    match export_settings {
+        ExportSettings::Template(template_settings) => {
+            let (_, manifest) = render_template_export(sheet, texture_cache)?;
+            let previous_manifest = Manifest::read(template_settings.manifest_file());
+            Ok(previous_manifest != Some(manifest))
+        }
+    }
+}
+
+fn render_template_export(
+    sheet: &Sheet<Absolute>,
+    texture_cache: texture_cache::Handle,
+) -> Result<(Vec<(PathBuf, Vec<u8>)>, Manifest), ExportError> {
+    let export_settings = sheet
+        .export_settings()
+        .as_ref()
+        .ok_or(ExportError::NoExportSettings)?;
+    let ExportSettings::Template(template_settings) = export_settings;
+    validate_texture_scale(template_settings)?;
+
+    let packed_sheet = pack_sheet(sheet, texture_cache, template_settings)?;
+
+    let mut outputs = vec![];
+    let mut texture_hash = ContentHash::default();
+    let mut metadata_hash = ContentHash::default();
+
+    // Built-in formats replace the main template, additional targets are always templates
+    let mut targets = template_settings.targets();
+    let layout = packed_sheet.layout();
+    let built_in_metadata = match template_settings.format() {
+        ExportFormat::Template => None,
+        ExportFormat::GodotSpriteFrames => Some(godot::generate_godot_sprite_frames(
+            sheet,
+            template_settings,
+            layout,
+        )?),
+        ExportFormat::Css => Some(css::generate_css_sprites(sheet, template_settings, layout)?),
+    };
+    if let Some(metadata) = built_in_metadata {
+        let metadata = metadata.into_bytes();
+        metadata_hash.update(&metadata);
+        outputs.push((template_settings.metadata_file().to_owned(), metadata));
+        targets.remove(0);
+    }
+
+    for (template_file, path) in targets {
+        let metadata = generate_sheet_metadata(
+            sheet,
+            export_settings,
+            template_file,
+            packed_sheet.layout(),
+            packed_sheet.algorithm(),
+            packed_sheet.order(),
+        )?
+        .into_bytes();
+        metadata_hash.update(&metadata);
+        outputs.push((path.to_owned(), metadata));
+    }
+
+    let texture_format = template_settings.texture_format();
+    let texture = encode_texture(packed_sheet.texture(), texture_format)?;
+    texture_hash.update(&texture);
+    outputs.push((template_settings.texture_file().to_owned(), texture));
+
+    if let Some(normal_map) = packed_sheet.normal_map() {
+        let normal_map = encode_texture(normal_map, texture_format)?;
+        texture_hash.update(&normal_map);
+        outputs.push((template_settings.normal_map_file(), normal_map));
+    }
+
+    Ok((outputs, Manifest::new(texture_hash, metadata_hash)))
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PackingStats {
+    pub used_area: u64,
+    pub atlas_area: u64,
+}
+
+impl PackingStats {
+    pub fn efficiency(&self) -> f32 {
+        match self.atlas_area {
+            0 => 0.0,
+            atlas_area => 100.0 * self.used_area as f32 / atlas_area as f32,
+        }
+    }
+}
+
+// Packs the sheet like an export would, without writing any file.
+// Sheets which have no export settings yet are packed using the default settings.
+pub fn packing_stats(
+    sheet: &Sheet<Absolute>,
+    texture_cache: texture_cache::Handle,
+) -> Result<PackingStats, ExportError> {
+    let default_settings = TemplateExportSettings::<Absolute>::default();
+    let template_settings = match sheet.export_settings() {
+        Some(ExportSettings::Template(template_settings)) => template_settings,
+        None => &default_settings,
+    };
+
+    let packed_sheet = pack_sheet(sheet, texture_cache, template_settings)?;
+
+    // Deduplicated frames share their rectangle, which should only be counted once
+    let rectangles = packed_sheet
+        .layout()
+        .values()
+        .map(|frame| (frame.position_in_sheet, frame.size_in_sheet))
+        .collect::<HashSet<_>>();
+    let texture = packed_sheet.texture();
+    Ok(PackingStats {
+        used_area: rectangles
+            .iter()
+            .map(|(_, (width, height))| *width as u64 * *height as u64)
+            .sum(),
+        atlas_area: texture.width() as u64 * texture.height() as u64,
+    })
+}
+
+pub fn export_contact_sheet<P: AsRef<Path>>(
+    sheet: &Sheet<Absolute>,
+    texture_cache: texture_cache::Handle,
+    path: P,
+) -> Result<(), ExportError> {
+    let contact_sheet = contact_sheet::render_contact_sheet(sheet, texture_cache)?;
+    let path = path.as_ref();
+    if let Some(directory) = path.parent() {
+        create_dir(directory)?;
+    }
+    let mut file = create_file(path)?;
+    contact_sheet.write_to(&mut file, image::ImageFormat::Png)?;
+    Ok(())
+}
+
+pub fn preview_template_context(
+    sheet: &Sheet<Absolute>,
+    texture_cache: texture_cache::Handle,
+) -> Result<String, ExportError> {
+    let export_settings = sheet
+        .export_settings()
+        .as_ref()
+        .ok_or(ExportError::NoExportSettings)?;
+
+    match export_settings {
+        ExportSettings::Template(template_settings) => {
+            validate_texture_scale(template_settings)?;
+            let packed_sheet = pack_sheet(sheet, texture_cache, template_settings)?;
+            Ok(generate_sheet_context_preview(
+                sheet,
+                export_settings,
+                packed_sheet.layout(),
+                packed_sheet.algorithm(),
+                packed_sheet.order(),
+            )?)
+        }
+    }
+}
+
+// Metadata coordinates are divided by the texture scale, so it cannot be zero
+fn validate_texture_scale(settings: &TemplateExportSettings<Absolute>) -> Result<(), ExportError> {
+    match settings.texture_scale() {
+        0 => Err(ExportError::InvalidTextureScale),
+        _ => Ok(()),
+    }
+}
+
+// Path of the texture file relative to the directory of the metadata file, using forward slashes
+fn texture_path_from_metadata(
+    settings: &TemplateExportSettings<Absolute>,
+) -> Result<String, MetadataError> {
+    let texture_file = settings.texture_file();
+    let relative_to = settings.metadata_file().parent().unwrap_or(Path::new(""));
+    let relative_path = pathdiff::diff_paths(texture_file, relative_to).ok_or_else(|| {
+        MetadataError::AbsoluteToRelativePath(texture_file.to_owned(), relative_to.to_owned())
+    })?;
+    Ok(relative_path
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(s) => Some(s.to_string_lossy().into_owned()),
+            Component::ParentDir => Some("..".to_owned()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+// Escapes a string to be written between double quotes
+fn escape(string: &str) -> String {
+    string.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn encode_texture(image: &DynamicImage, format: TextureFormat) -> Result<Vec<u8>, ExportError> {
+    let image_format = match format {
+        TextureFormat::Png => image::ImageFormat::Png,
+        TextureFormat::WebP => image::ImageFormat::WebP,
+        TextureFormat::Qoi => image::ImageFormat::Qoi,
+    };
+    let mut bytes = Cursor::new(Vec::new());
+    image.write_to(&mut bytes, image_format)?;
+    Ok(bytes.into_inner())
+}
+
+fn write_file(path: &Path, content: &[u8]) -> Result<(), ExportError> {
+    if let Some(directory) = path.parent() {
+        create_dir(directory)?;
+    }
+    let mut file = create_file(path)?;
+    file.write_all(content)
+        .map_err(|e| ExportError::IoError(path.to_owned(), e))
+}
+
 fn create_file(path: &Path) -> Result<File, ExportError> {
     File::create(path).map_err(|e| ExportError::IoError(path.to_owned(), e))
 }
@@ -80,9 +302,24 @@ fn create_dir(path: &Path) -> Result<(), ExportError> {
 #[cfg(test)]
 mod test {
 
+    use sugar_path::SugarPath;
+
     use super::*;
+    use crate::document::Document;
     use crate::{mock::TigerAppMock, TigerApp};
 
+    // Settings exporting `test-output/{stem}.png` and `test-output/{stem}.export` with the test
+    // template. Metadata paths are relative to the directory containing these files.
+    fn test_export_settings(stem: &str) -> TemplateExportSettings<Any> {
+        let texture_file = PathBuf::from(format!("test-output/{stem}.png")).resolve();
+        let mut settings = TemplateExportSettings::<Any>::default();
+        settings.set_template_file(PathBuf::from("test-data/export.template").resolve());
+        settings.set_texture_file(&texture_file);
+        settings.set_metadata_file(texture_file.with_extension("export"));
+        settings.set_metadata_paths_root(texture_file.parent().unwrap());
+        settings
+    }
+
     #[tokio::test]
     async fn export_matches_known_output() {
         let app = TigerAppMock::new();
@@ -115,4 +352,663 @@ mod test {
             std::fs::read("test-data/samurai.png").unwrap()
         );
     }
+
+    #[test]
+    fn can_pack_sheet_at_integer_scale() {
+        let document = Document::open("test-data/samurai.tiger").unwrap();
+        let frame = PathBuf::from("test-data/samurai-dead-all.png").resolve();
+        let mut settings = TemplateExportSettings::<Any>::default();
+        settings.set_texture_scale(2);
+        let packed_sheet = pack_sheet(
+            document.sheet(),
+            texture_cache::Handle::default(),
+            &settings,
+        )
+        .unwrap();
+        let packed_frame = packed_sheet.layout().get(&frame).unwrap();
+        assert_eq!(packed_frame.size_in_sheet, (32, 32));
+    }
+
+    #[test]
+    fn normal_map_atlas_matches_texture_layout() {
+        use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+        use std::collections::HashMap;
+
+        let solid = |width, height, color| {
+            DynamicImage::ImageRgba8(RgbaImage::from_pixel(width, height, Rgba(color)))
+        };
+        let texture_cache = texture_cache::Handle::default();
+        {
+            let mut cache = texture_cache.lock();
+            cache.insert("a".into(), solid(8, 8, [255, 0, 0, 255]));
+            cache.insert("b".into(), solid(16, 4, [0, 255, 0, 255]));
+            cache.insert("c".into(), solid(4, 12, [0, 0, 255, 255]));
+            cache.insert("a_normal".into(), solid(8, 8, [100, 150, 200, 255]));
+            cache.insert("b_normal".into(), solid(16, 4, [50, 60, 70, 255]));
+        }
+
+        let mut sheet = Sheet::<Absolute>::default();
+        sheet.add_frames(&vec!["a", "b", "c"]);
+        sheet.set_frame_normal_map("a", Some("a_normal")).unwrap();
+        sheet.set_frame_normal_map("b", Some("b_normal")).unwrap();
+
+        let settings = TemplateExportSettings::<Any>::default();
+        let packed_sheet = pack_sheet(&sheet, texture_cache, &settings).unwrap();
+        let texture = packed_sheet.texture();
+        let normal_map = packed_sheet.normal_map().unwrap();
+        assert_eq!(normal_map.dimensions(), texture.dimensions());
+
+        let expected = HashMap::from([
+            ("a", ([255, 0, 0, 255], [100, 150, 200, 255])),
+            ("b", ([0, 255, 0, 255], [50, 60, 70, 255])),
+            ("c", ([0, 0, 255, 255], [128, 128, 255, 255])),
+        ]);
+        for (frame, (color, normal)) in expected {
+            let packed_frame = packed_sheet.layout().get(Path::new(frame)).unwrap();
+            let (x, y) = packed_frame.position_in_sheet;
+            let (width, height) = packed_frame.size_in_sheet;
+            for (px, py) in [(x, y), (x + width - 1, y + height - 1)] {
+                assert_eq!(texture.get_pixel(px, py), Rgba(color));
+                assert_eq!(normal_map.get_pixel(px, py), Rgba(normal));
+            }
+        }
+    }
+
+    #[test]
+    fn packing_layout_does_not_depend_on_frame_order() {
+        use image::{DynamicImage, RgbaImage};
+        use std::collections::HashMap;
+
+        let texture_cache = texture_cache::Handle::default();
+        {
+            let mut cache = texture_cache.lock();
+            for (frame, (width, height)) in
+                [("a", (8, 8)), ("b", (16, 4)), ("c", (4, 12)), ("d", (8, 8))]
+            {
+                cache.insert(
+                    frame.into(),
+                    DynamicImage::ImageRgba8(RgbaImage::new(width, height)),
+                );
+            }
+        }
+
+        let pack = |frames: Vec<&str>, algorithm: PackingAlgorithm| {
+            let mut sheet = Sheet::<Absolute>::default();
+            sheet.add_frames(&frames);
+            let mut settings = TemplateExportSettings::<Any>::default();
+            settings.set_packing_algorithm(algorithm);
+            settings.set_packing_order(PackingOrder::Sorted);
+            let packed_sheet = pack_sheet(&sheet, texture_cache.clone(), &settings).unwrap();
+            packed_sheet
+                .layout()
+                .iter()
+                .map(|(path, frame)| (path.clone(), (frame.position_in_sheet, frame.size_in_sheet)))
+                .collect::<HashMap<_, _>>()
+        };
+
+        for algorithm in [PackingAlgorithm::MaxRects, PackingAlgorithm::Shelf] {
+            assert_eq!(
+                pack(vec!["a", "b", "c", "d"], algorithm),
+                pack(vec!["d", "c", "b", "a"], algorithm)
+            );
+        }
+    }
+
+    #[test]
+    fn identical_frames_share_atlas_rect() {
+        use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+
+        let (red, green) = (Rgba([255, 0, 0, 255]), Rgba([0, 255, 0, 255]));
+        let frames =
+            ["a", "b", "c"].map(|frame| PathBuf::from(format!("test-data/{frame}.png")).resolve());
+        let texture_cache = texture_cache::Handle::default();
+        {
+            let mut cache = texture_cache.lock();
+            for (frame, color) in frames.iter().zip([red, red, green]) {
+                cache.insert(
+                    frame.clone(),
+                    DynamicImage::ImageRgba8(RgbaImage::from_pixel(8, 8, color)),
+                );
+            }
+        }
+
+        let mut settings = test_export_settings("sheet");
+        settings.set_deduplicate_frames(true);
+        let settings = settings.with_absolute_paths().unwrap();
+
+        let mut sheet = Sheet::<Absolute>::default();
+        sheet.add_frames(&frames.to_vec());
+        sheet.set_export_settings(ExportSettings::Template(settings.clone()));
+
+        let packed_sheet = pack_sheet(&sheet, texture_cache.clone(), &settings).unwrap();
+        let red_pixels = packed_sheet
+            .texture()
+            .pixels()
+            .filter(|(_, _, color)| *color == red)
+            .count();
+        assert_eq!(red_pixels, 64);
+
+        let preview = preview_template_context(&sheet, texture_cache).unwrap();
+        let context: serde_json::Value = serde_json::from_str(&preview).unwrap();
+        let rect = |frame: &serde_json::Value| {
+            ["x", "y", "width", "height"].map(|field| frame[field].as_i64().unwrap())
+        };
+        let metadata_frames = context["frames"].as_array().unwrap();
+        assert_eq!(metadata_frames.len(), 3);
+        assert_eq!(rect(&metadata_frames[0]), rect(&metadata_frames[1]));
+        assert_ne!(rect(&metadata_frames[0]), rect(&metadata_frames[2]));
+    }
+
+    #[test]
+    fn rotation_packs_mixed_orientations_tighter() {
+        use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+
+        let red = Rgba([255, 0, 0, 255]);
+        let texture_cache = texture_cache::Handle::default();
+        {
+            let mut cache = texture_cache.lock();
+            for (frame, (width, height)) in [
+                ("wide_a", (64, 16)),
+                ("wide_b", (64, 16)),
+                ("tall_a", (16, 64)),
+                ("tall_b", (16, 64)),
+            ] {
+                let mut bitmap = RgbaImage::new(width, height);
+                bitmap.put_pixel(width - 1, 0, red);
+                cache.insert(frame.into(), DynamicImage::ImageRgba8(bitmap));
+            }
+        }
+
+        let mut sheet = Sheet::<Absolute>::default();
+        sheet.add_frames(&vec!["wide_a", "wide_b", "tall_a", "tall_b"]);
+
+        let pack = |allow_rotation| {
+            let mut settings = TemplateExportSettings::<Any>::default();
+            settings.set_packing_algorithm(PackingAlgorithm::MaxRects);
+            settings.set_packing_order(PackingOrder::Sorted);
+            settings.set_allow_rotation(allow_rotation);
+            pack_sheet(&sheet, texture_cache.clone(), &settings).unwrap()
+        };
+        let area = |packed_sheet: &PackedSheet| {
+            let (width, height) = packed_sheet.texture().dimensions();
+            width * height
+        };
+
+        let upright = pack(false);
+        assert!(upright.layout().values().all(|frame| !frame.rotated));
+
+        let rotated = pack(true);
+        assert!(area(&rotated) < area(&upright));
+        assert!(rotated.layout().values().any(|frame| frame.rotated));
+
+        for packed_frame in rotated.layout().values() {
+            let (width, height) = packed_frame.frame_size();
+            let (x, y) = packed_frame.position_in_sheet;
+            match packed_frame.rotated {
+                true => {
+                    assert_eq!(packed_frame.size_in_sheet, (height, width));
+                    // Top-right corner ends up bottom-right after a clockwise rotation
+                    assert_eq!(
+                        rotated.texture().get_pixel(x + height - 1, y + width - 1),
+                        red
+                    );
+                }
+                false => assert_eq!(rotated.texture().get_pixel(x + width - 1, y), red),
+            }
+        }
+    }
+
+    #[test]
+    fn mip_safe_padding_separates_frames() {
+        use image::{DynamicImage, RgbaImage};
+
+        let texture_cache = texture_cache::Handle::default();
+        let frames = [("a", (10, 7)), ("b", (5, 5)), ("c", (12, 3)), ("d", (1, 9))];
+        for (frame, (width, height)) in frames {
+            texture_cache.lock().insert(
+                frame.into(),
+                DynamicImage::ImageRgba8(RgbaImage::new(width, height)),
+            );
+        }
+        let mut sheet = Sheet::<Absolute>::default();
+        sheet.add_frames(&frames.iter().map(|(f, _)| *f).collect::<Vec<_>>());
+
+        for algorithm in [PackingAlgorithm::MaxRects, PackingAlgorithm::Shelf] {
+            // Padding is rounded up to the next power of two
+            for (padding, expected_gap) in [(3, 4), (4, 4), (8, 8)] {
+                let mut settings = TemplateExportSettings::<Any>::default();
+                settings.set_packing_algorithm(algorithm);
+                settings.set_packing_order(PackingOrder::Sorted);
+                settings.set_mip_safe_padding(padding);
+                let packed_sheet = pack_sheet(&sheet, texture_cache.clone(), &settings).unwrap();
+                let rects = packed_sheet
+                    .layout()
+                    .values()
+                    .map(|f| (f.position_in_sheet, f.size_in_sheet))
+                    .collect::<Vec<_>>();
+                for ((x, y), _) in &rects {
+                    assert_eq!(x % expected_gap, 0);
+                    assert_eq!(y % expected_gap, 0);
+                }
+                for (i, ((ax, ay), (aw, ah))) in rects.iter().enumerate() {
+                    for ((bx, by), (bw, bh)) in rects.iter().skip(i + 1) {
+                        assert!(
+                            ax + aw + expected_gap <= *bx
+                                || bx + bw + expected_gap <= *ax
+                                || ay + ah + expected_gap <= *by
+                                || by + bh + expected_gap <= *ay
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn packing_stats_reflect_wasted_space() {
+        use image::{DynamicImage, RgbaImage};
+
+        let stats = |sizes: &[(u32, u32)]| {
+            let texture_cache = texture_cache::Handle::default();
+            let mut sheet = Sheet::<Absolute>::default();
+            for (index, (width, height)) in sizes.iter().enumerate() {
+                let frame = PathBuf::from(format!("test-data/stats_{index}.png")).resolve();
+                texture_cache.lock().insert(
+                    frame.clone(),
+                    DynamicImage::ImageRgba8(RgbaImage::new(*width, *height)),
+                );
+                sheet.add_frames(&vec![frame]);
+            }
+            packing_stats(&sheet, texture_cache).unwrap()
+        };
+
+        let tight = stats(&[(8, 8), (8, 8), (8, 8), (8, 8)]);
+        assert_eq!(tight.used_area, 256);
+        assert_eq!(tight.atlas_area, 256);
+        assert_eq!(tight.efficiency(), 100.0);
+
+        let sparse = stats(&[(9, 9)]);
+        assert_eq!(sparse.used_area, 81);
+        assert!(sparse.efficiency() < 50.0);
+    }
+
+    #[test]
+    fn unchanged_export_produces_identical_manifest() {
+        let document = Document::open("test-data/samurai.tiger").unwrap();
+        let mut sheet = document.sheet().clone();
+
+        let mut settings = test_export_settings("manifest/sheet");
+        settings.set_write_manifest(true);
+        let manifest_file = settings.manifest_file();
+        sheet.set_export_settings(ExportSettings::Template(
+            settings.with_absolute_paths().unwrap(),
+        ));
+
+        let texture_cache = texture_cache::Handle::default();
+        std::fs::remove_file(&manifest_file).ok();
+        assert!(was_export_changed(&sheet, texture_cache.clone()).unwrap());
+
+        export_sheet(&sheet, texture_cache.clone()).unwrap();
+        let manifest = Manifest::read(&manifest_file).unwrap();
+        assert!(!was_export_changed(&sheet, texture_cache.clone()).unwrap());
+
+        export_sheet(&sheet, texture_cache.clone()).unwrap();
+        assert_eq!(Manifest::read(&manifest_file).unwrap(), manifest);
+
+        let animation = sheet.animation_mut("idle").unwrap();
+        animation.set_looping(!animation.looping());
+        assert!(was_export_changed(&sheet, texture_cache.clone()).unwrap());
+        export_sheet(&sheet, texture_cache).unwrap();
+        let new_manifest = Manifest::read(&manifest_file).unwrap();
+        assert_eq!(new_manifest.texture_hash(), manifest.texture_hash());
+        assert_ne!(new_manifest.metadata_hash(), manifest.metadata_hash());
+    }
+
+    #[tokio::test]
+    async fn can_preview_template_context() {
+        let app = TigerAppMock::new();
+        app.open_documents(vec!["test-data/samurai.tiger"]).await;
+
+        let preview = app.get_template_context_preview().await.unwrap();
+        let context: serde_json::Value = serde_json::from_str(&preview).unwrap();
+        assert!(!context["frames"].as_array().unwrap().is_empty());
+        assert!(!context["animations"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn template_context_includes_hitbox_kind() {
+        let app = TigerAppMock::new();
+        app.open_documents(vec!["test-data/samurai.tiger"]).await;
+        app.edit_animation("idle");
+        app.select_hitbox("weak", false, false);
+        app.set_hitbox_kind(crate::dto::HitboxKind::Attack);
+
+        let preview = app.get_template_context_preview().await.unwrap();
+        let context: serde_json::Value = serde_json::from_str(&preview).unwrap();
+        let animation = context["animations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|a| a["name"] == "idle")
+            .unwrap();
+        let kinds = animation["sequences"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["keyframes"][0]["hitboxes"][0]["kind"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(kinds.len(), 4);
+        assert_eq!(kinds.iter().filter(|k| **k == "Attack").count(), 1);
+        assert_eq!(kinds.iter().filter(|k| **k == "Generic").count(), 3);
+    }
+
+    #[test]
+    fn template_can_iterate_keyframe_hitboxes() {
+        use euclid::vec2;
+        use image::{DynamicImage, RgbaImage};
+        use std::collections::HashMap;
+
+        let directory =
+            PathBuf::from("test-output/template_can_iterate_keyframe_hitboxes").resolve();
+        std::fs::create_dir_all(&directory).unwrap();
+        let template_file = directory.join("hitboxes.template");
+        std::fs::write(
+            &template_file,
+            "{{#each animations}}{{#each sequences}}{{#each keyframes}}{{#each hitboxes}}\
+            [{{name}}] {{kind}} x={{x}} y={{y}} w={{width}} h={{height}}\n\
+            {{/each}}{{/each}}{{/each}}{{/each}}",
+        )
+        .unwrap();
+
+        let frame = directory.join("frame.png");
+        let texture_cache = texture_cache::Handle::default();
+        texture_cache.lock().insert(
+            frame.clone(),
+            DynamicImage::ImageRgba8(RgbaImage::new(8, 8)),
+        );
+
+        let mut sheet = Sheet::<Absolute>::default();
+        sheet.add_test_animation("idle", HashMap::from([(Direction::North, vec![&frame])]));
+        let keyframe = sheet.keyframe_mut("idle", Direction::North, 0);
+        let (_, hitbox) = keyframe.create_hitbox("hurtbox");
+        hitbox.set_position(vec2(-3, 5));
+        hitbox.set_size(vec2(10, 12));
+        hitbox.set_kind(HitboxKind::Hurtbox);
+
+        let mut settings = test_export_settings("template_can_iterate_keyframe_hitboxes/sheet");
+        settings.set_template_file(template_file);
+        let metadata_file = settings.metadata_file().to_owned();
+        sheet.set_export_settings(ExportSettings::Template(
+            settings.with_absolute_paths().unwrap(),
+        ));
+
+        export_sheet(&sheet, texture_cache).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(metadata_file).unwrap(),
+            "[hurtbox] Hurtbox x=-3 y=5 w=10 h=12\n"
+        );
+    }
+
+    #[test]
+    fn exports_held_precise_keyframe_duration() {
+        use image::{DynamicImage, RgbaImage};
+        use std::collections::HashMap;
+
+        let directory =
+            PathBuf::from("test-output/exports_held_precise_keyframe_duration").resolve();
+        std::fs::create_dir_all(&directory).unwrap();
+        let template_file = directory.join("durations.template");
+        std::fs::write(
+            &template_file,
+            "{{#each animations}}{{#each sequences}}{{#each keyframes}}\
+            {{duration}}\n\
+            {{/each}}{{/each}}{{/each}}",
+        )
+        .unwrap();
+
+        let frame = directory.join("frame.png");
+        let texture_cache = texture_cache::Handle::default();
+        texture_cache.lock().insert(
+            frame.clone(),
+            DynamicImage::ImageRgba8(RgbaImage::new(8, 8)),
+        );
+
+        let mut sheet = Sheet::<Absolute>::default();
+        sheet.add_test_animation("idle", HashMap::from([(Direction::North, vec![&frame])]));
+        let keyframe = sheet.keyframe_mut("idle", Direction::North, 0);
+        keyframe.set_duration_micros(16_600);
+        keyframe.set_hold(2);
+
+        let mut settings = test_export_settings("exports_held_precise_keyframe_duration/sheet");
+        settings.set_template_file(template_file);
+        let metadata_file = settings.metadata_file().to_owned();
+        sheet.set_export_settings(ExportSettings::Template(
+            settings.with_absolute_paths().unwrap(),
+        ));
+
+        export_sheet(&sheet, texture_cache).unwrap();
+        assert_eq!(std::fs::read_to_string(metadata_file).unwrap(), "33\n");
+    }
+
+    #[test]
+    fn basename_path_style_omits_directories() {
+        use image::{DynamicImage, RgbaImage};
+
+        let frame = PathBuf::from("test-data/frames/0.png").resolve();
+        let texture_cache = texture_cache::Handle::default();
+        texture_cache.lock().insert(
+            frame.clone(),
+            DynamicImage::ImageRgba8(RgbaImage::new(8, 8)),
+        );
+
+        let mut settings = test_export_settings("sheet");
+        settings.set_path_style(PathStyle::BasenameOnly);
+
+        let mut sheet = Sheet::<Absolute>::default();
+        sheet.add_frames(&vec![&frame]);
+        sheet.set_export_settings(ExportSettings::Template(
+            settings.with_absolute_paths().unwrap(),
+        ));
+
+        let preview = preview_template_context(&sheet, texture_cache).unwrap();
+        let context: serde_json::Value = serde_json::from_str(&preview).unwrap();
+        assert_eq!(context["frames"][0]["source"], "0.png");
+        assert_eq!(context["sheet_image"], "sheet.png");
+    }
+
+    #[tokio::test]
+    async fn export_and_reveal_only_reveals_on_success() {
+        let app = TigerAppMock::new();
+        app.new_document("test-output/export_and_reveal_only_reveals_on_success.tiger");
+        app.export_and_reveal().await;
+        assert!(app.client_state().error.is_some());
+        assert!(app.revealed_paths().is_empty());
+
+        app.open_documents(vec!["test-data/flame.tiger"]).await;
+        app.export_and_reveal().await;
+        assert_eq!(
+            app.revealed_paths(),
+            vec![PathBuf::from("test-output/flame.png").resolve()]
+        );
+    }
+
+    #[test]
+    fn can_export_to_multiple_targets() {
+        use crate::document::Command;
+
+        let mut document = Document::open("test-data/samurai.tiger").unwrap();
+        let template_file = PathBuf::from("test-data/export.template").resolve();
+        let texture_file = PathBuf::from("test-output/multiple_targets.png").resolve();
+        let metadata_file = PathBuf::from("test-output/multiple_targets.export").resolve();
+        let additional_metadata_file =
+            PathBuf::from("test-output/multiple_targets_additional.export").resolve();
+        for path in [&texture_file, &metadata_file, &additional_metadata_file] {
+            std::fs::remove_file(path).ok();
+        }
+
+        for command in [
+            Command::BeginExportAs,
+            Command::SetExportTextureFile(texture_file.clone()),
+            Command::SetExportMetadataFile(metadata_file.clone()),
+            Command::AddExportTarget,
+            Command::SetExportTargetTemplateFile(0, template_file),
+            Command::SetExportTargetMetadataFile(0, additional_metadata_file.clone()),
+            Command::EndExportAs,
+        ] {
+            document.process_command(command).unwrap();
+        }
+
+        export_sheet(document.sheet(), texture_cache::Handle::default()).unwrap();
+
+        assert!(texture_file.exists());
+        let metadata = std::fs::read_to_string(&metadata_file).unwrap();
+        let additional_metadata = std::fs::read_to_string(&additional_metadata_file).unwrap();
+        assert!(!metadata.is_empty());
+        assert_eq!(metadata, additional_metadata);
+    }
+
+    #[test]
+    fn can_export_godot_sprite_frames() {
+        use image::{DynamicImage, RgbaImage};
+        use std::collections::HashMap;
+
+        let directory = PathBuf::from("test-output/can_export_godot_sprite_frames").resolve();
+        let frames = ["idle.png", "walk_0.png", "walk_1.png"].map(|f| directory.join(f));
+        let texture_cache = texture_cache::Handle::default();
+        for frame in &frames {
+            texture_cache.lock().insert(
+                frame.clone(),
+                DynamicImage::ImageRgba8(RgbaImage::new(8, 8)),
+            );
+        }
+
+        let mut sheet = Sheet::<Absolute>::default();
+        sheet.add_test_animation(
+            "idle",
+            HashMap::from([(Direction::North, vec![&frames[0]])]),
+        );
+        sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec![&frames[1], &frames[2]])]),
+        );
+
+        let mut settings = test_export_settings("can_export_godot_sprite_frames/sheet");
+        settings.set_format(ExportFormat::GodotSpriteFrames);
+        settings.set_metadata_file(directory.join("sheet.tres"));
+        let metadata_file = settings.metadata_file().to_owned();
+        sheet.set_export_settings(ExportSettings::Template(
+            settings.with_absolute_paths().unwrap(),
+        ));
+
+        export_sheet(&sheet, texture_cache).unwrap();
+        let resource = std::fs::read_to_string(metadata_file).unwrap();
+        assert!(resource.starts_with("[gd_resource type=\"SpriteFrames\""));
+        assert!(resource.contains("path=\"sheet.png\""));
+        assert!(resource.contains("\"name\": &\"idle\""));
+        assert!(resource.contains("\"name\": &\"walk\""));
+        assert_eq!(
+            resource
+                .matches("[sub_resource type=\"AtlasTexture\"")
+                .count(),
+            3
+        );
+        assert_eq!(resource.matches("\"texture\": SubResource(").count(), 3);
+    }
+
+    #[test]
+    fn can_export_css_sprite_sheet() {
+        use image::{DynamicImage, RgbaImage};
+        use std::collections::HashMap;
+
+        let directory = PathBuf::from("test-output/can_export_css_sprite_sheet").resolve();
+        let frames = ["idle.png", "walk_0.png", "walk_1.png"].map(|f| directory.join(f));
+        let texture_cache = texture_cache::Handle::default();
+        for (index, frame) in frames.iter().enumerate() {
+            let size = 8 * (index as u32 + 1);
+            texture_cache.lock().insert(
+                frame.clone(),
+                DynamicImage::ImageRgba8(RgbaImage::new(size, size / 2)),
+            );
+        }
+
+        let mut sheet = Sheet::<Absolute>::default();
+        sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, frames.iter().collect())]),
+        );
+
+        let mut settings = test_export_settings("can_export_css_sprite_sheet/sheet");
+        settings.set_format(ExportFormat::Css);
+        settings.set_metadata_file(directory.join("sheet.css"));
+        let metadata_file = settings.metadata_file().to_owned();
+        let settings = settings.with_absolute_paths().unwrap();
+        sheet.set_export_settings(ExportSettings::Template(settings.clone()));
+
+        export_sheet(&sheet, texture_cache.clone()).unwrap();
+        let stylesheet = std::fs::read_to_string(metadata_file).unwrap();
+        assert_eq!(
+            stylesheet
+                .matches("background-image: url(\"sheet.png\");")
+                .count(),
+            3
+        );
+
+        let packed_sheet = pack_sheet(&sheet, texture_cache, &settings).unwrap();
+        for (name, frame) in ["idle", "walk_0", "walk_1"].iter().zip(&frames) {
+            let packed_frame = &packed_sheet.layout()[frame];
+            let (x, y) = packed_frame.position_in_sheet;
+            let (width, height) = packed_frame.size_in_sheet;
+            let rule = format!(
+                ".frame-{name} {{\n\
+                 \tbackground-image: url(\"sheet.png\");\n\
+                 \tbackground-position: {}px {}px;\n\
+                 \twidth: {width}px;\n\
+                 \theight: {height}px;\n\
+                 }}",
+                -(x as i64),
+                -(y as i64),
+            );
+            assert!(stylesheet.contains(&rule));
+        }
+    }
+
+    #[test]
+    fn can_export_qoi_texture() {
+        let mut sheet = Sheet::<Absolute>::default();
+        sheet.add_frames(&vec![
+            PathBuf::from("test-data/flame-idle-0.png").resolve(),
+            PathBuf::from("test-data/flame-idle-1.png").resolve(),
+        ]);
+
+        let texture_file = PathBuf::from("test-output/can_export_qoi_texture.qoi").resolve();
+        let mut settings = test_export_settings("can_export_qoi_texture");
+        settings.set_texture_file(&texture_file);
+        settings.set_texture_format(TextureFormat::Qoi);
+        sheet.set_export_settings(ExportSettings::Template(
+            settings.with_absolute_paths().unwrap(),
+        ));
+
+        export_sheet(&sheet, texture_cache::Handle::default()).unwrap();
+        let bytes = std::fs::read(texture_file).unwrap();
+        assert!(bytes.starts_with(b"qoif"));
+        let texture = image::load_from_memory_with_format(&bytes, image::ImageFormat::Qoi).unwrap();
+        assert!(texture.width() > 0 && texture.height() > 0);
+    }
+
+    #[test]
+    fn export_rejects_zero_texture_scale() {
+        let mut sheet = Sheet::<Absolute>::default();
+        sheet.add_frames(&vec![PathBuf::from("test-data/flame-idle-0.png").resolve()]);
+
+        let mut settings = test_export_settings("zero_scale");
+        settings.set_texture_scale(0);
+        sheet.set_export_settings(ExportSettings::Template(
+            settings.with_absolute_paths().unwrap(),
+        ));
+
+        assert!(matches!(
+            export_sheet(&sheet, texture_cache::Handle::default()),
+            Err(ExportError::InvalidTextureScale)
+        ));
+    }
 }
@@ -0,0 +1,241 @@
+use euclid::default::{Rect, Vector2D};
+use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::export::ExportError;
+use crate::features::texture_cache;
+use crate::sheet::{Absolute, Animation, Keyframe, Sheet};
+
+const PADDING: u32 = 8;
+const GLYPH_WIDTH: u32 = 3;
+const GLYPH_HEIGHT: u32 = 5;
+const LABEL_SCALE: u32 = 2;
+const BACKGROUND_COLOR: Rgba<u8> = Rgba([32, 32, 36, 255]);
+const LABEL_COLOR: Rgba<u8> = Rgba([220, 220, 220, 255]);
+
+struct Cell<'a> {
+    name: &'a str,
+    keyframe: Option<&'a Keyframe<Absolute>>,
+    bounding_box: Option<Rect<i32>>,
+}
+
+// Renders the first keyframe of every animation in a grid, with animation names underneath.
+pub(super) fn render_contact_sheet(
+    sheet: &Sheet<Absolute>,
+    texture_cache: texture_cache::Handle,
+) -> Result<DynamicImage, ExportError> {
+    let first_keyframes = sheet
+        .sorted_animations()
+        .into_iter()
+        .map(|(name, animation)| (name, animation, first_keyframe(animation)))
+        .collect::<Vec<_>>();
+    if first_keyframes.is_empty() {
+        return Err(ExportError::NoAnimations);
+    }
+
+    let mut textures = HashMap::<PathBuf, DynamicImage>::new();
+    {
+        let cache = texture_cache.lock();
+        for keyframe in first_keyframes.iter().filter_map(|(_, _, k)| *k) {
+            let texture = cache
+                .get(keyframe.frame())
+                .cloned()
+                .or_else(|| image::open(keyframe.frame()).ok())
+                .ok_or_else(|| ExportError::FrameReadError(keyframe.frame().to_owned()))?;
+            textures.insert(keyframe.frame().to_owned(), texture);
+        }
+    }
+
+    let mut frame_sizes = texture_cache.texture_sizes();
+    for (path, texture) in &textures {
+        frame_sizes.insert(path.clone(), texture.dimensions().into());
+    }
+
+    let cells = first_keyframes
+        .into_iter()
+        .map(|(name, animation, keyframe)| Cell {
+            name,
+            keyframe,
+            bounding_box: animation.bounding_box(&frame_sizes),
+        })
+        .collect::<Vec<_>>();
+
+    let frame_area = cells
+        .iter()
+        .filter_map(|c| c.bounding_box)
+        .fold(Vector2D::<u32>::zero(), |area, b| {
+            area.max(vec_from_size(b.width(), b.height()))
+        });
+    let content_width = cells
+        .iter()
+        .map(|c| label_width(c.name))
+        .fold(frame_area.x, u32::max);
+    let cell_width = content_width + 2 * PADDING;
+    let cell_height = frame_area.y + label_height() + 3 * PADDING;
+
+    let num_columns = (cells.len() as f32).sqrt().ceil() as u32;
+    let num_rows = (cells.len() as u32 + num_columns - 1) / num_columns;
+    let mut canvas = RgbaImage::from_pixel(
+        num_columns * cell_width,
+        num_rows * cell_height,
+        BACKGROUND_COLOR,
+    );
+
+    for (index, cell) in cells.iter().enumerate() {
+        let cell_x = (index as u32 % num_columns) * cell_width;
+        let cell_y = (index as u32 / num_columns) * cell_height;
+
+        if let (Some(keyframe), Some(bounding_box)) = (cell.keyframe, cell.bounding_box) {
+            if let Some(texture) = textures.get(keyframe.frame()) {
+                let size: Vector2D<u32> = texture.dimensions().into();
+                let top_left = keyframe.offset() - (size.to_f32() / 2.0).floor().to_i32();
+                let position_in_box = top_left - bounding_box.origin.to_vector();
+                let box_x = cell_x + PADDING + (content_width - bounding_box.width() as u32) / 2;
+                let box_y = cell_y + PADDING + (frame_area.y - bounding_box.height() as u32) / 2;
+                image::imageops::overlay(
+                    &mut canvas,
+                    &texture.to_rgba8(),
+                    box_x as i64 + position_in_box.x as i64,
+                    box_y as i64 + position_in_box.y as i64,
+                );
+            }
+        }
+
+        let label_x = cell_x + PADDING + (content_width - label_width(cell.name)) / 2;
+        let label_y = cell_y + 2 * PADDING + frame_area.y;
+        draw_label(&mut canvas, cell.name, label_x, label_y);
+    }
+
+    Ok(DynamicImage::ImageRgba8(canvas))
+}
+
+fn first_keyframe(animation: &Animation<Absolute>) -> Option<&Keyframe<Absolute>> {
+    animation
+        .sequences_iter()
+        .find_map(|(_, sequence)| sequence.keyframes_iter().next())
+}
+
+fn vec_from_size(width: i32, height: i32) -> Vector2D<u32> {
+    Vector2D::new(width.max(0) as u32, height.max(0) as u32)
+}
+
+fn label_width(text: &str) -> u32 {
+    let num_glyphs = text.chars().count() as u32;
+    if num_glyphs == 0 {
+        return 0;
+    }
+    (num_glyphs * (GLYPH_WIDTH + 1) - 1) * LABEL_SCALE
+}
+
+fn label_height() -> u32 {
+    GLYPH_HEIGHT * LABEL_SCALE
+}
+
+fn draw_label(canvas: &mut RgbaImage, text: &str, x: u32, y: u32) {
+    for (index, character) in text.chars().enumerate() {
+        let glyph_x = x + index as u32 * (GLYPH_WIDTH + 1) * LABEL_SCALE;
+        for (row, bits) in glyph(character).iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - column)) == 0 {
+                    continue;
+                }
+                for dy in 0..LABEL_SCALE {
+                    for dx in 0..LABEL_SCALE {
+                        let pixel_x = glyph_x + column * LABEL_SCALE + dx;
+                        let pixel_y = y + row as u32 * LABEL_SCALE + dy;
+                        if pixel_x < canvas.width() && pixel_y < canvas.height() {
+                            canvas.put_pixel(pixel_x, pixel_y, LABEL_COLOR);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 3x5 pixel font, one row per entry with the most significant bit on the left
+fn glyph(character: char) -> [u8; 5] {
+    match character.to_ascii_uppercase() {
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b110, 0b001, 0b010, 0b100, 0b111],
+        '3' => [0b110, 0b001, 0b010, 0b001, 0b110],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b110, 0b001, 0b110],
+        '6' => [0b011, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b110],
+        '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        _ => [0b110, 0b001, 0b010, 0b000, 0b010],
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::sheet::Direction;
+
+    #[test]
+    fn can_render_contact_sheet() {
+        let texture_cache = texture_cache::Handle::default();
+        {
+            let mut cache = texture_cache.lock();
+            cache.insert(
+                PathBuf::from("idle_0"),
+                DynamicImage::ImageRgba8(RgbaImage::from_pixel(16, 16, Rgba([255, 0, 0, 255]))),
+            );
+            cache.insert(
+                PathBuf::from("walk_0"),
+                DynamicImage::ImageRgba8(RgbaImage::from_pixel(32, 8, Rgba([0, 255, 0, 255]))),
+            );
+        }
+
+        let mut sheet = Sheet::<Absolute>::default();
+        sheet.add_test_animation("idle", HashMap::from([(Direction::East, vec!["idle_0"])]));
+        sheet.add_test_animation("walk", HashMap::from([(Direction::East, vec!["walk_0"])]));
+
+        let contact_sheet = render_contact_sheet(&sheet, texture_cache).unwrap();
+
+        // Cells are 32px (widest frame) + 2 * 8px padding wide, and
+        // 16px (tallest frame) + 10px (label) + 3 * 8px padding tall.
+        assert_eq!(contact_sheet.dimensions(), (96, 50));
+        assert!(contact_sheet
+            .pixels()
+            .any(|(_, _, p)| p == Rgba([255, 0, 0, 255])));
+        assert!(contact_sheet
+            .pixels()
+            .any(|(_, _, p)| p == Rgba([0, 255, 0, 255])));
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashSet;
+
+use crate::export::{escape, texture_path_from_metadata, MetadataError, TextureLayout};
+use crate::sheet::{self, Absolute};
+
+// Writes a stylesheet with one class per frame of the sheet. Each class displays its frame by
+// offsetting the packed texture as a background image.
+pub(super) fn generate_css_sprites(
+    sheet: &sheet::Sheet<Absolute>,
+    settings: &sheet::TemplateExportSettings<Absolute>,
+    texture_layout: &TextureLayout,
+) -> Result<String, MetadataError> {
+    let texture_path = escape(&texture_path_from_metadata(settings)?);
+    let mut class_names = HashSet::new();
+    let mut stylesheet = String::new();
+
+    for frame in sheet.sorted_frames() {
+        let packed_frame = texture_layout
+            .get(frame.source())
+            .ok_or(MetadataError::FrameWasNotPacked)?;
+        let (x, y) = packed_frame.position_in_sheet;
+        let (width, height) = packed_frame.size_in_sheet;
+
+        let stem = frame
+            .source()
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let base_name = format!("frame-{}", sanitize(&stem));
+        let mut class_name = base_name.clone();
+        let mut suffix = 2;
+        while !class_names.insert(class_name.clone()) {
+            class_name = format!("{base_name}-{suffix}");
+            suffix += 1;
+        }
+
+        stylesheet.push_str(&format!(
+            ".{class_name} {{\n\
+             \tbackground-image: url(\"{texture_path}\");\n\
+             \tbackground-position: {}px {}px;\n\
+             \twidth: {width}px;\n\
+             \theight: {height}px;\n\
+             }}\n\n",
+            -(x as i64),
+            -(y as i64),
+        ));
+    }
+
+    Ok(stylesheet)
+}
+
+// Class names may only contain letters, digits, hyphens and underscores
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
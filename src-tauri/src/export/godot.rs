@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use crate::export::{escape, texture_path_from_metadata, MetadataError, TextureLayout};
+use crate::sheet::{self, Absolute, Direction};
+
+// Godot's default playback speed for animations without frames
+const DEFAULT_SPEED: f64 = 5.0;
+
+// Writes a Godot 4 `SpriteFrames` resource with one `AtlasTexture` per frame of the sheet.
+// Godot animations play at a fixed speed and scale it with a relative duration per frame,
+// so the speed of each animation is derived from its shortest keyframe.
+pub(super) fn generate_godot_sprite_frames(
+    sheet: &sheet::Sheet<Absolute>,
+    settings: &sheet::TemplateExportSettings<Absolute>,
+    texture_layout: &TextureLayout,
+) -> Result<String, MetadataError> {
+    let frames = sheet.sorted_frames();
+    let mut atlas_ids = HashMap::new();
+
+    let mut resource = format!(
+        "[gd_resource type=\"SpriteFrames\" load_steps={} format=3]\n\n",
+        frames.len() + 2
+    );
+    resource.push_str(&format!(
+        "[ext_resource type=\"Texture2D\" path=\"{}\" id=\"1\"]\n",
+        escape(&texture_path_from_metadata(settings)?)
+    ));
+
+    for (index, frame) in frames.iter().enumerate() {
+        let packed_frame = texture_layout
+            .get(frame.source())
+            .ok_or(MetadataError::FrameWasNotPacked)?;
+        let id = format!("AtlasTexture_{index}");
+        let (x, y) = packed_frame.position_in_sheet;
+        let (width, height) = packed_frame.size_in_sheet;
+        resource.push_str(&format!(
+            "\n[sub_resource type=\"AtlasTexture\" id=\"{id}\"]\n\
+             atlas = ExtResource(\"1\")\n\
+             region = Rect2({x}, {y}, {width}, {height})\n"
+        ));
+        atlas_ids.insert(frame.source(), id);
+    }
+
+    let mut animations = Vec::new();
+    for (animation_name, animation) in sheet.sorted_animations() {
+        let is_directional = animation.sequences_iter().count() > 1;
+        for (direction, sequence) in animation.sequences_iter() {
+            let name = match is_directional {
+                true => format!("{animation_name}_{}", direction_suffix(*direction)),
+                false => animation_name.clone(),
+            };
+
+            let shortest_duration = sequence
+                .keyframes_iter()
+                .map(|k| k.effective_duration_micros())
+                .filter(|d| *d > 0)
+                .min();
+
+            let mut keyframes = Vec::new();
+            for keyframe in sequence.keyframes_iter() {
+                let id = atlas_ids
+                    .get(keyframe.frame())
+                    .ok_or(MetadataError::InvalidFrameReference)?;
+                let duration = match shortest_duration {
+                    Some(d) => keyframe.effective_duration_micros() as f64 / d as f64,
+                    None => 1.0,
+                };
+                keyframes.push(format!(
+                    "{{\n\"duration\": {duration:?},\n\"texture\": SubResource(\"{id}\")\n}}"
+                ));
+            }
+
+            let speed = match shortest_duration {
+                Some(d) => 1_000_000.0 / d as f64,
+                None => DEFAULT_SPEED,
+            };
+            animations.push(format!(
+                "{{\n\"frames\": [{}],\n\"loop\": {},\n\"name\": &\"{}\",\n\"speed\": {:?}\n}}",
+                keyframes.join(", "),
+                animation.looping(),
+                escape(&name),
+                speed,
+            ));
+        }
+    }
+
+    resource.push_str(&format!(
+        "\n[resource]\nanimations = [{}]\n",
+        animations.join(", ")
+    ));
+
+    Ok(resource)
+}
+
+fn direction_suffix(direction: Direction) -> &'static str {
+    match direction {
+        Direction::East => "east",
+        Direction::NorthEast => "north_east",
+        Direction::North => "north",
+        Direction::NorthWest => "north_west",
+        Direction::West => "west",
+        Direction::SouthWest => "south_west",
+        Direction::South => "south",
+        Direction::SouthEast => "south_east",
+    }
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use crate::sheet::current_version_name;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Manifest {
+    sheet_version: String,
+    texture_hash: String,
+    metadata_hash: String,
+}
+
+impl Manifest {
+    pub fn new(texture_hash: ContentHash, metadata_hash: ContentHash) -> Self {
+        Self {
+            sheet_version: current_version_name().to_owned(),
+            texture_hash: texture_hash.to_string(),
+            metadata_hash: metadata_hash.to_string(),
+        }
+    }
+
+    pub fn read<T: AsRef<Path>>(path: T) -> Option<Self> {
+        let file = File::open(path.as_ref()).ok()?;
+        serde_json::from_reader(BufReader::new(file)).ok()
+    }
+
+    pub fn texture_hash(&self) -> &str {
+        &self.texture_hash
+    }
+
+    pub fn metadata_hash(&self) -> &str {
+        &self.metadata_hash
+    }
+}
+
+// 64-bit FNV-1a, which (unlike std's `DefaultHasher`) is guaranteed to produce
+// the same values across platforms and Rust versions.
+#[derive(Clone, Copy, Debug)]
+pub struct ContentHash(u64);
+
+impl Default for ContentHash {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl ContentHash {
+    pub fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+impl std::fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn content_hash_matches_reference_values() {
+        assert_eq!(ContentHash::default().to_string(), "cbf29ce484222325");
+        let mut hash = ContentHash::default();
+        hash.update(b"a");
+        assert_eq!(hash.to_string(), "af63dc4c8601ec8c");
+        let mut hash = ContentHash::default();
+        hash.update(b"foobar");
+        assert_eq!(hash.to_string(), "85944171f73967e8");
+    }
+}
@@ -5,11 +5,9 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
-use crate::export::PackedFrame;
+use crate::export::TextureLayout;
 use crate::sheet::{self, Absolute};
 
-type TextureLayout = HashMap<PathBuf, PackedFrame>;
-
 #[derive(Error, Debug)]
 pub enum MetadataError {
     #[error("Template parsing error\n\n{0}")]
@@ -22,6 +20,8 @@ pub enum MetadataError {
     FrameWasNotPacked,
     #[error("Error converting an absolute path to a relative path\nAbsolute path: `{0}`\nRelative path root: `{1}`")]
     AbsoluteToRelativePath(PathBuf, PathBuf),
+    #[error("Template context serialization error\n\n{0}")]
+    ContextSerializationError(#[from] serde_json::Error),
 }
 
 handlebars_helper!(add: |a:f64, b:f64| a + b);
@@ -59,6 +59,7 @@ impl<'a> Template<'a> {
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 struct Hitbox {
     name: String,
+    kind: sheet::HitboxKind,
     x: i32,
     y: i32,
     width: i32,
@@ -66,29 +67,56 @@ struct Hitbox {
 }
 
 impl Hitbox {
-    fn new(hitbox_name: String, hitbox: &sheet::Hitbox) -> Result<Hitbox, MetadataError> {
+    fn new(
+        hitbox_name: String,
+        hitbox: &sheet::Hitbox,
+        scale: u32,
+    ) -> Result<Hitbox, MetadataError> {
+        let scale = scale as i32;
         Ok(Self {
             name: hitbox_name,
-            x: hitbox.position().x,
-            y: hitbox.position().y,
-            width: hitbox.size().x as i32,
-            height: hitbox.size().y as i32,
+            kind: hitbox.kind(),
+            x: hitbox.position().x * scale,
+            y: hitbox.position().y * scale,
+            width: hitbox.size().x as i32 * scale,
+            height: hitbox.size().y as i32 * scale,
         })
     }
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+struct Anchor {
+    name: String,
+    x: i32,
+    y: i32,
+}
+
+impl Anchor {
+    fn new(anchor_name: String, position: Vector2D<i32>, scale: u32) -> Anchor {
+        let scale = scale as i32;
+        Self {
+            name: anchor_name,
+            x: position.x * scale,
+            y: position.y * scale,
+        }
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 struct Frame {
     index: i32,
+    source: String,
     x: i32,
     y: i32,
     width: i32,
     height: i32,
+    rotated: bool,
 }
 impl Frame {
     fn new(
         sheet: &sheet::Sheet<Absolute>,
         frame: &sheet::Frame<Absolute>,
+        settings: &sheet::TemplateExportSettings<Absolute>,
         texture_layout: &TextureLayout,
     ) -> Result<Self, MetadataError> {
         let index = sheet
@@ -103,10 +131,12 @@ impl Frame {
 
         Ok(Self {
             index: index as i32,
+            source: format_path(frame.source(), settings)?,
             x: frame_layout.position_in_sheet.0 as i32,
             y: frame_layout.position_in_sheet.1 as i32,
             width: frame_layout.size_in_sheet.0 as i32,
             height: frame_layout.size_in_sheet.1 as i32,
+            rotated: frame_layout.rotated,
         })
     }
 }
@@ -118,37 +148,47 @@ struct Keyframe {
     y: i32,
     frame: Frame,
     hitboxes: Vec<Hitbox>,
+    anchors: Vec<Anchor>,
+    events: Vec<String>,
 }
 
 impl Keyframe {
     fn new(
         sheet: &sheet::Sheet<Absolute>,
         keyframe: &sheet::Keyframe<Absolute>,
+        settings: &sheet::TemplateExportSettings<Absolute>,
         texture_layout: &TextureLayout,
+        scale: u32,
     ) -> Result<Self, MetadataError> {
         let packed_frame = texture_layout
             .get(keyframe.frame())
             .ok_or(MetadataError::FrameWasNotPacked)?;
 
-        let frame_size: Vector2D<u32> = packed_frame.size_in_sheet.into();
-        let position = keyframe.offset() - (frame_size.to_f32() / 2.0).floor().to_i32();
+        let frame_size: Vector2D<u32> = Vector2D::from(packed_frame.frame_size()) / scale;
+        let position =
+            (keyframe.offset() - (frame_size.to_f32() / 2.0).floor().to_i32()) * scale as i32;
 
         let frame = sheet
             .frame(keyframe.frame())
             .ok_or(MetadataError::InvalidFrameReference)?;
-        let frame_data = Frame::new(sheet, frame, texture_layout)?;
+        let frame_data = Frame::new(sheet, frame, settings, texture_layout)?;
 
         let mut hitboxes = Vec::new();
         for (hitbox_name, hitbox) in keyframe.sorted_hitboxes() {
-            hitboxes.push(Hitbox::new(hitbox_name.clone(), hitbox)?);
+            hitboxes.push(Hitbox::new(hitbox_name.clone(), hitbox, scale)?);
         }
 
         Ok(Keyframe {
-            duration: keyframe.duration_millis() as i32,
+            duration: keyframe.effective_duration_millis() as i32,
             x: position.x,
             y: position.y,
             frame: frame_data,
             hitboxes,
+            anchors: keyframe
+                .anchors_iter()
+                .map(|(name, position)| Anchor::new(name.clone(), position, scale))
+                .collect(),
+            events: keyframe.events_iter().cloned().collect(),
         })
     }
 }
@@ -164,11 +204,13 @@ impl Sequence {
         sheet: &sheet::Sheet<Absolute>,
         direction: sheet::Direction,
         sequence: &sheet::Sequence<Absolute>,
+        settings: &sheet::TemplateExportSettings<Absolute>,
         texture_layout: &TextureLayout,
+        scale: u32,
     ) -> Result<Self, MetadataError> {
         let mut keyframes = Vec::new();
         for keyframe in sequence.keyframes_iter() {
-            let frame = Keyframe::new(sheet, keyframe, texture_layout)?;
+            let frame = Keyframe::new(sheet, keyframe, settings, texture_layout, scale)?;
             keyframes.push(frame);
         }
 
@@ -179,10 +221,33 @@ impl Sequence {
     }
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+struct BoundingBox {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl From<Rect<i32>> for BoundingBox {
+    fn from(rect: Rect<i32>) -> Self {
+        Self {
+            x: rect.min_x(),
+            y: rect.min_y(),
+            width: rect.width(),
+            height: rect.height(),
+        }
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 struct Animation {
     name: String,
     is_looping: bool,
+    start_delay: u64,
+    origin_x: i32,
+    origin_y: i32,
+    bounding_box: Option<BoundingBox>,
     sequences: Vec<Sequence>,
 }
 
@@ -191,17 +256,34 @@ impl Animation {
         sheet: &sheet::Sheet<Absolute>,
         animation_name: String,
         animation: &sheet::Animation<Absolute>,
+        settings: &sheet::TemplateExportSettings<Absolute>,
         texture_layout: &TextureLayout,
+        scale: u32,
     ) -> Result<Self, MetadataError> {
         let mut sequences = Vec::new();
         for (direction, sequence) in animation.sequences_iter() {
-            let sequence = Sequence::new(sheet, *direction, sequence, texture_layout)?;
+            let sequence =
+                Sequence::new(sheet, *direction, sequence, settings, texture_layout, scale)?;
             sequences.push(sequence);
         }
 
+        let frame_sizes: HashMap<PathBuf, Vector2D<u32>> = texture_layout
+            .iter()
+            .map(|(path, packed_frame)| {
+                let size: Vector2D<u32> = packed_frame.frame_size().into();
+                (path.clone(), size / scale)
+            })
+            .collect();
+
         Ok(Self {
             name: animation_name,
             is_looping: animation.looping(),
+            start_delay: animation.start_delay().as_millis() as u64,
+            origin_x: animation.origin().x * scale as i32,
+            origin_y: animation.origin().y * scale as i32,
+            bounding_box: animation
+                .bounding_box(&frame_sizes)
+                .map(|b| b.scale(scale as i32, scale as i32).into()),
             sequences,
         })
     }
@@ -212,6 +294,9 @@ struct Sheet {
     frames: Vec<Frame>,
     animations: Vec<Animation>,
     sheet_image: String,
+    normal_map_image: Option<String>,
+    packing_algorithm: sheet::PackingAlgorithm,
+    packing_order: sheet::PackingOrder,
 }
 
 impl Sheet {
@@ -219,11 +304,13 @@ impl Sheet {
         sheet: &sheet::Sheet<Absolute>,
         settings: &sheet::TemplateExportSettings<Absolute>,
         texture_layout: &TextureLayout,
+        packing_algorithm: sheet::PackingAlgorithm,
+        packing_order: sheet::PackingOrder,
     ) -> Result<Self, MetadataError> {
         let frames = {
             let mut frames = Vec::new();
             for frame in sheet.sorted_frames() {
-                frames.push(Frame::new(sheet, frame, texture_layout)?);
+                frames.push(Frame::new(sheet, frame, settings, texture_layout)?);
             }
             frames
         };
@@ -231,42 +318,107 @@ impl Sheet {
         let animations = {
             let mut animations = Vec::new();
             for (animation_name, animation) in sheet.sorted_animations() {
-                let animation_data =
-                    Animation::new(sheet, animation_name.clone(), animation, texture_layout)?;
+                let animation_data = Animation::new(
+                    sheet,
+                    animation_name.clone(),
+                    animation,
+                    settings,
+                    texture_layout,
+                    settings.texture_scale(),
+                )?;
                 animations.push(animation_data);
             }
             animations
         };
 
-        let sheet_image = {
-            let relative_to = settings.metadata_paths_root();
-            let image_path = diff_paths(settings.texture_file(), relative_to).ok_or_else(|| {
-                MetadataError::AbsoluteToRelativePath(
-                    settings.texture_file().to_owned(),
-                    relative_to.to_owned(),
-                )
-            })?;
-            image_path.to_string_lossy().into_owned()
+        let sheet_image = format_path(settings.texture_file(), settings)?;
+        let normal_map_image = match sheet.has_normal_maps() {
+            true => Some(format_path(&settings.normal_map_file(), settings)?),
+            false => None,
         };
 
         Ok(Self {
             frames,
             animations,
             sheet_image,
+            normal_map_image,
+            packing_algorithm,
+            packing_order,
         })
     }
 }
 
-pub(super) fn generate_sheet_metadata(
+fn format_path(
+    path: &Path,
+    settings: &sheet::TemplateExportSettings<Absolute>,
+) -> Result<String, MetadataError> {
+    match settings.path_style() {
+        sheet::PathStyle::AbsolutePath => Ok(path.to_string_lossy().into_owned()),
+        sheet::PathStyle::RelativeToRoot => {
+            let relative_to = settings.metadata_paths_root();
+            let relative_path = diff_paths(path, relative_to).ok_or_else(|| {
+                MetadataError::AbsoluteToRelativePath(path.to_owned(), relative_to.to_owned())
+            })?;
+            Ok(relative_path.to_string_lossy().into_owned())
+        }
+        sheet::PathStyle::BasenameOnly => Ok(path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned()),
+    }
+}
+
+fn generate_sheet_context(
     sheet: &sheet::Sheet<Absolute>,
     export_settings: &sheet::ExportSettings<Absolute>,
     texture_layout: &TextureLayout,
-) -> Result<String, MetadataError> {
+    packing_algorithm: sheet::PackingAlgorithm,
+    packing_order: sheet::PackingOrder,
+) -> Result<Sheet, MetadataError> {
     match export_settings {
-        sheet::ExportSettings::Template(template_settings) => {
-            let template = Template::new(template_settings.template_file())?;
-            let globals = Sheet::new(sheet, template_settings, texture_layout)?;
-            template.render(&globals)
-        }
+        sheet::ExportSettings::Template(template_settings) => Sheet::new(
+            sheet,
+            template_settings,
+            texture_layout,
+            packing_algorithm,
+            packing_order,
+        ),
     }
 }
+
+pub(super) fn generate_sheet_metadata(
+    sheet: &sheet::Sheet<Absolute>,
+    export_settings: &sheet::ExportSettings<Absolute>,
+    template_file: &Path,
+    texture_layout: &TextureLayout,
+    packing_algorithm: sheet::PackingAlgorithm,
+    packing_order: sheet::PackingOrder,
+) -> Result<String, MetadataError> {
+    let template = Template::new(template_file)?;
+    let globals = generate_sheet_context(
+        sheet,
+        export_settings,
+        texture_layout,
+        packing_algorithm,
+        packing_order,
+    )?;
+    template.render(&globals)
+}
+
+pub(super) fn generate_sheet_context_preview(
+    sheet: &sheet::Sheet<Absolute>,
+    export_settings: &sheet::ExportSettings<Absolute>,
+    texture_layout: &TextureLayout,
+    packing_algorithm: sheet::PackingAlgorithm,
+    packing_order: sheet::PackingOrder,
+) -> Result<String, MetadataError> {
+    let globals = generate_sheet_context(
+        sheet,
+        export_settings,
+        texture_layout,
+        packing_algorithm,
+        packing_order,
+    )?;
+    Ok(serde_json::to_string_pretty(&globals)?)
+}
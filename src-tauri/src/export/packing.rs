@@ -0,0 +1,171 @@
+use std::cmp::Reverse;
+
+use crate::sheet::PackingAlgorithm;
+
+pub(super) struct Packing {
+    pub size: (u32, u32),
+    pub positions: Vec<(u32, u32)>,
+    // Rotated items are turned 90° clockwise and occupy a (height, width) rectangle
+    pub rotations: Vec<bool>,
+}
+
+pub(super) trait Packer {
+    fn pack(&self, sizes: &[(u32, u32)], max_size: u32, allow_rotation: bool) -> Option<Packing>;
+}
+
+struct MaxRectsPacker;
+struct ShelfPacker;
+
+pub(super) fn packer(algorithm: PackingAlgorithm) -> &'static dyn Packer {
+    match algorithm {
+        PackingAlgorithm::MaxRects => &MaxRectsPacker,
+        PackingAlgorithm::Shelf => &ShelfPacker,
+    }
+}
+
+impl Packer for MaxRectsPacker {
+    fn pack(&self, sizes: &[(u32, u32)], max_size: u32, allow_rotation: bool) -> Option<Packing> {
+        let items = sizes
+            .iter()
+            .enumerate()
+            .map(|(index, (w, h))| crunch::Item {
+                data: index,
+                w: *w as usize,
+                h: *h as usize,
+                rot: match allow_rotation {
+                    true => crunch::Rotation::Allowed,
+                    false => crunch::Rotation::None,
+                },
+            })
+            .collect::<Vec<_>>();
+
+        let (width, height, packed) = crunch::pack_into_po2(max_size as usize, items).ok()?;
+        let mut positions = vec![(0, 0); sizes.len()];
+        let mut rotations = vec![false; sizes.len()];
+        for (rect, index) in packed {
+            positions[index] = (rect.x as u32, rect.y as u32);
+            rotations[index] = rect.w as u32 != sizes[index].0;
+        }
+
+        Some(Packing {
+            size: (width as u32, height as u32),
+            positions,
+            rotations,
+        })
+    }
+}
+
+impl Packer for ShelfPacker {
+    fn pack(&self, sizes: &[(u32, u32)], max_size: u32, allow_rotation: bool) -> Option<Packing> {
+        // Laying tall items on their side keeps shelves short
+        let rotations = sizes
+            .iter()
+            .map(|(w, h)| allow_rotation && h > w)
+            .collect::<Vec<_>>();
+        let sizes = sizes
+            .iter()
+            .zip(&rotations)
+            .map(|(&(w, h), &rotated)| if rotated { (h, w) } else { (w, h) })
+            .collect::<Vec<_>>();
+
+        let mut order = (0..sizes.len()).collect::<Vec<_>>();
+        order.sort_by_key(|i| (Reverse(sizes[*i].1), Reverse(sizes[*i].0)));
+
+        let min_area = sizes.iter().map(|(w, h)| *w as u64 * *h as u64).sum();
+        power_of_two_candidates(min_area, max_size)
+            .into_iter()
+            .find_map(|size| {
+                pack_shelves(&sizes, &order, size).map(|positions| Packing {
+                    size,
+                    positions,
+                    rotations: rotations.clone(),
+                })
+            })
+    }
+}
+
+// Mirrors the container sizes attempted by `crunch::pack_into_po2`
+fn power_of_two_candidates(min_area: u64, max_size: u32) -> Vec<(u32, u32)> {
+    let mut candidates = Vec::new();
+    let mut size: u32 = 2;
+    while (size as u64 * size as u64 * 2) < min_area {
+        size *= 2;
+    }
+    while size <= max_size {
+        candidates.push((size, size));
+        if size * 2 <= max_size {
+            candidates.push((size * 2, size));
+            candidates.push((size, size * 2));
+        }
+        size *= 2;
+    }
+    candidates
+}
+
+fn pack_shelves(
+    sizes: &[(u32, u32)],
+    order: &[usize],
+    (width, height): (u32, u32),
+) -> Option<Vec<(u32, u32)>> {
+    let mut positions = vec![(0, 0); sizes.len()];
+    let (mut x, mut y, mut shelf_height) = (0, 0, 0);
+    for &index in order {
+        let (w, h) = sizes[index];
+        if w > width {
+            return None;
+        }
+        if x + w > width {
+            x = 0;
+            y += shelf_height;
+            shelf_height = 0;
+        }
+        if y + h > height {
+            return None;
+        }
+        positions[index] = (x, y);
+        x += w;
+        shelf_height = shelf_height.max(h);
+    }
+    Some(positions)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn assert_valid_packing(sizes: &[(u32, u32)], packing: &Packing) {
+        let rects = sizes
+            .iter()
+            .zip(&packing.positions)
+            .zip(&packing.rotations)
+            .map(|(((w, h), (x, y)), rotated)| match rotated {
+                true => (*x, *y, *x + *h, *y + *w),
+                false => (*x, *y, *x + *w, *y + *h),
+            })
+            .collect::<Vec<_>>();
+        for (i, a) in rects.iter().enumerate() {
+            assert!(a.2 <= packing.size.0 && a.3 <= packing.size.1);
+            for b in &rects[(i + 1)..] {
+                assert!(a.2 <= b.0 || b.2 <= a.0 || a.3 <= b.1 || b.3 <= a.1);
+            }
+        }
+    }
+
+    #[test]
+    fn max_rects_packs_at_least_as_tightly_as_shelf() {
+        let sizes = vec![(64, 64), (32, 32), (32, 32), (32, 32), (32, 32)];
+
+        let max_rects = packer(PackingAlgorithm::MaxRects)
+            .pack(&sizes, 8_192, false)
+            .unwrap();
+        let shelf = packer(PackingAlgorithm::Shelf)
+            .pack(&sizes, 8_192, false)
+            .unwrap();
+        assert_valid_packing(&sizes, &max_rects);
+        assert_valid_packing(&sizes, &shelf);
+
+        let area = |p: &Packing| p.size.0 * p.size.1;
+        assert!(area(&max_rects) <= area(&shelf));
+    }
+}
@@ -1,10 +1,15 @@
-use image::{DynamicImage, GenericImage};
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImage, GenericImageView, Rgba, RgbaImage};
+use std::cmp::Reverse;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use crate::export::packing::packer;
 use crate::features::texture_cache;
-use crate::sheet::{Absolute, Sheet};
+use crate::sheet::{
+    Absolute, PackingAlgorithm, PackingOrder, Paths, Sheet, TemplateExportSettings,
+};
 
 #[derive(Error, Debug)]
 pub enum PackError {
@@ -12,16 +17,40 @@ pub enum PackError {
     FrameRead,
     #[error("Error while packing textures")]
     Packing,
+    #[error("Error while reading normal map `{0}`")]
+    NormalMapRead(PathBuf),
+    #[error("Normal map `{0}` does not have the same dimensions as its frame")]
+    NormalMapSizeMismatch(PathBuf),
 }
 
+// Frames without a normal map are filled with a flat surface facing the viewer
+const FLAT_NORMAL: Rgba<u8> = Rgba([128, 128, 255, 255]);
+
+#[derive(Clone)]
 pub(super) struct PackedFrame {
     pub position_in_sheet: (u32, u32),
     pub size_in_sheet: (u32, u32),
+    // Rotated frames are stored turned 90° clockwise, so their size in the sheet is (height, width)
+    pub rotated: bool,
+}
+
+impl PackedFrame {
+    // Size of the frame as it appears in game, regardless of how it is stored in the sheet
+    pub fn frame_size(&self) -> (u32, u32) {
+        let (width, height) = self.size_in_sheet;
+        match self.rotated {
+            true => (height, width),
+            false => (width, height),
+        }
+    }
 }
 
 pub(super) struct PackedSheet {
     texture: DynamicImage,
+    normal_map: Option<DynamicImage>,
     layout: HashMap<PathBuf, PackedFrame>,
+    algorithm: PackingAlgorithm,
+    order: PackingOrder,
 }
 
 impl PackedSheet {
@@ -29,62 +58,186 @@ impl PackedSheet {
         &self.texture
     }
 
+    pub fn normal_map(&self) -> Option<&DynamicImage> {
+        self.normal_map.as_ref()
+    }
+
     pub fn layout(&self) -> &HashMap<PathBuf, PackedFrame> {
         &self.layout
     }
+
+    pub fn algorithm(&self) -> PackingAlgorithm {
+        self.algorithm
+    }
+
+    pub fn order(&self) -> PackingOrder {
+        self.order
+    }
 }
 
-pub(super) fn pack_sheet(
+pub(super) fn pack_sheet<P: Paths>(
     sheet: &Sheet<Absolute>,
     texture_cache: texture_cache::Handle,
+    settings: &TemplateExportSettings<P>,
 ) -> Result<PackedSheet, PackError> {
+    let scale = settings.texture_scale();
+    let algorithm = settings.packing_algorithm();
+    let order = settings.packing_order();
+    let deduplicate = settings.deduplicate_frames();
+    let allow_rotation = settings.allow_rotation();
+    let mip_safe_padding = settings.mip_safe_padding();
+
     let mut bitmaps = HashMap::new();
+    let mut normal_bitmaps = HashMap::new();
     {
         let cache = texture_cache.lock();
+        let read = |path: &Path| cache.get(path).cloned().or_else(|| image::open(path).ok());
         for frame in sheet.frames_iter() {
-            bitmaps.insert(
-                frame.source(),
-                cache
-                    .get(frame.source())
-                    .cloned()
-                    .or_else(|| image::open(frame.source()).ok())
-                    .ok_or(PackError::FrameRead)?,
+            let bitmap = read(frame.source()).ok_or(PackError::FrameRead)?;
+            if let Some(normal_source) = frame.normal_source() {
+                let normal_bitmap = read(normal_source)
+                    .ok_or_else(|| PackError::NormalMapRead(normal_source.to_owned()))?;
+                if normal_bitmap.dimensions() != bitmap.dimensions() {
+                    return Err(PackError::NormalMapSizeMismatch(normal_source.to_owned()));
+                }
+                normal_bitmaps.insert(frame.source(), normal_bitmap);
+            }
+            bitmaps.insert(frame.source(), bitmap);
+        }
+    }
+
+    // Frames with identical pixels (and normal maps) are packed once and share a rectangle in the atlas
+    let mut duplicates = HashMap::new();
+    if deduplicate {
+        let contents = |bitmap: &DynamicImage| (bitmap.dimensions(), bitmap.to_rgba8().into_raw());
+        let mut sources = bitmaps.keys().copied().collect::<Vec<_>>();
+        sources.sort();
+        let mut originals = HashMap::new();
+        for source in sources {
+            let key = (
+                contents(&bitmaps[source]),
+                normal_bitmaps.get(source).map(contents),
             );
+            match originals.get(&key) {
+                Some(original) => {
+                    duplicates.insert(source, *original);
+                }
+                None => {
+                    originals.insert(key, source);
+                }
+            }
+        }
+        for duplicate in duplicates.keys() {
+            bitmaps.remove(duplicate);
+            normal_bitmaps.remove(duplicate);
         }
     }
 
-    let mut items = bitmaps
+    if scale > 1 {
+        for bitmap in bitmaps.values_mut().chain(normal_bitmaps.values_mut()) {
+            let (width, height) = (bitmap.width() * scale, bitmap.height() * scale);
+            *bitmap = bitmap.resize_exact(width, height, FilterType::Nearest);
+        }
+    }
+
+    // Sorting makes the layout independent of the order in which frames were added to the sheet
+    let mut frames = sheet
+        .frames_iter()
+        .filter_map(|frame| bitmaps.get_key_value(frame.source()))
+        .collect::<Vec<_>>();
+    if order == PackingOrder::Sorted {
+        frames.sort_by_key(|(path, bitmap)| {
+            let area = bitmap.width() as u64 * bitmap.height() as u64;
+            (Reverse(area), *path)
+        });
+    }
+    let sizes = frames
+        .iter()
+        .map(|(_, bitmap)| (bitmap.width(), bitmap.height()))
+        .collect::<Vec<_>>();
+
+    // With mip-safe padding, frames are packed on a grid of power-of-two blocks and each frame is
+    // followed by a gutter of one empty block. Frames then never share a texel at mip levels where
+    // a block is at least one texel wide.
+    let block_size = mip_safe_padding.max(1).next_power_of_two();
+    let block_sizes = sizes
         .iter()
-        .map(|(path, bitmap)| crunch::Item {
-            data: path,
-            w: bitmap.width() as usize,
-            h: bitmap.height() as usize,
-            rot: crunch::Rotation::None,
+        .map(|(w, h)| match mip_safe_padding {
+            0 => (*w, *h),
+            _ => (
+                (w + block_size - 1) / block_size + 1,
+                (h + block_size - 1) / block_size + 1,
+            ),
         })
         .collect::<Vec<_>>();
-    items.sort_by_key(|i| i.data);
 
-    let (width, height, layout) =
-        crunch::pack_into_po2(8_192, items).map_err(|_| PackError::Packing)?;
-    let layout = layout
-        .into_iter()
-        .map(|(r, p)| {
+    let packing = packer(algorithm)
+        .pack(&block_sizes, 8_192 / block_size, allow_rotation)
+        .ok_or(PackError::Packing)?;
+    let (width, height) = (packing.size.0 * block_size, packing.size.1 * block_size);
+    let mut layout = frames
+        .iter()
+        .zip(sizes)
+        .zip(packing.positions)
+        .zip(packing.rotations)
+        .map(|((((path, _), (w, h)), (x, y)), rotated)| {
             (
-                p.to_path_buf(),
+                path.to_path_buf(),
                 PackedFrame {
-                    position_in_sheet: (r.x as u32, r.y as u32),
-                    size_in_sheet: (r.w as u32, r.h as u32),
+                    position_in_sheet: (x * block_size, y * block_size),
+                    size_in_sheet: if rotated { (h, w) } else { (w, h) },
+                    rotated,
                 },
             )
         })
         .collect::<HashMap<_, _>>();
 
-    let mut texture = DynamicImage::new_rgba8(width as u32, height as u32);
+    for (path, frame) in &layout {
+        if frame.rotated {
+            let path = path.as_path();
+            if let Some(bitmap) = bitmaps.get_mut(path) {
+                *bitmap = bitmap.rotate90();
+            }
+            if let Some(normal_bitmap) = normal_bitmaps.get_mut(path) {
+                *normal_bitmap = normal_bitmap.rotate90();
+            }
+        }
+    }
+
+    let mut texture = DynamicImage::new_rgba8(width, height);
     layout.iter().for_each(|(path, frame)| {
         let bitmap = bitmaps.get(path.as_path()).unwrap();
         let (x, y) = (frame.position_in_sheet.0, frame.position_in_sheet.1);
         texture.copy_from(bitmap, x, y).unwrap();
     });
 
-    Ok(PackedSheet { texture, layout })
+    // Normal maps share the layout of the main texture so both atlases can be sampled with the same coordinates
+    let normal_map = (!normal_bitmaps.is_empty()).then(|| {
+        let mut normal_map = DynamicImage::ImageRgba8(RgbaImage::new(width, height));
+        layout.iter().for_each(|(path, frame)| {
+            let (x, y) = (frame.position_in_sheet.0, frame.position_in_sheet.1);
+            match normal_bitmaps.get(path.as_path()) {
+                Some(normal_bitmap) => normal_map.copy_from(normal_bitmap, x, y).unwrap(),
+                None => {
+                    let (w, h) = frame.size_in_sheet;
+                    let flat = DynamicImage::ImageRgba8(RgbaImage::from_pixel(w, h, FLAT_NORMAL));
+                    normal_map.copy_from(&flat, x, y).unwrap();
+                }
+            }
+        });
+        normal_map
+    });
+
+    for (duplicate, original) in duplicates {
+        let packed_frame = layout[original].clone();
+        layout.insert(duplicate.to_owned(), packed_frame);
+    }
+
+    Ok(PackedSheet {
+        texture,
+        normal_map,
+        layout,
+        algorithm,
+        order,
+    })
 }
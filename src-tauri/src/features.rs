@@ -1,6 +1,10 @@
+pub mod autosave;
 pub mod clipboard_analysis;
 pub mod missing_textures;
 pub mod recent_documents;
+pub mod save_on_focus_loss;
+pub mod session;
 pub mod template_hot_reload;
 pub mod texture_cache;
 pub mod texture_hot_reload;
+pub mod thumbnail_cache;
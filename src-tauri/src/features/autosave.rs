@@ -0,0 +1,194 @@
+use log::error;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::sheet::{Absolute, Any, Sheet};
+use crate::TigerApp;
+
+// The autosave interval is read from the session on every tick, so changes apply immediately
+pub fn init<A: TigerApp + Send + Clone + 'static>(app: A, period: Duration) {
+    std::thread::spawn(move || {
+        let mut autosaved_versions = HashMap::new();
+        let mut last_autosave = Instant::now();
+        loop {
+            std::thread::sleep(period);
+            let interval = app.state().lock().autosave_interval();
+            if last_autosave.elapsed() >= interval {
+                autosave(&app, &mut autosaved_versions);
+                last_autosave = Instant::now();
+            }
+        }
+    });
+}
+
+pub fn autosave_path<T: AsRef<Path>>(path: T) -> PathBuf {
+    let mut file_name = path.as_ref().as_os_str().to_owned();
+    file_name.push(".autosave");
+    PathBuf::from(file_name)
+}
+
+pub fn recover<T: AsRef<Path>>(path: T) -> Option<Sheet<Absolute>> {
+    let autosave = autosave_path(&path);
+    let modified = |p: &Path| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+    if modified(&autosave)? < modified(path.as_ref())? {
+        return None;
+    }
+
+    let mut directory = path.as_ref().to_owned();
+    directory.pop();
+    match Sheet::<Any>::read(&autosave).and_then(|s| s.with_relative_paths(directory)) {
+        Ok(sheet) => Some(sheet.with_absolute_paths()),
+        Err(e) => {
+            error!(
+                "Error while reading autosave `{0}`: {e}",
+                autosave.to_string_lossy()
+            );
+            None
+        }
+    }
+}
+
+pub fn discard<T: AsRef<Path>>(path: T) {
+    let autosave = autosave_path(path);
+    if autosave.exists() {
+        if let Err(e) = std::fs::remove_file(&autosave) {
+            error!(
+                "Error while deleting autosave `{0}`: {e}",
+                autosave.to_string_lossy()
+            );
+        }
+    }
+}
+
+fn autosave<A: TigerApp>(app: &A, autosaved_versions: &mut HashMap<PathBuf, i32>) {
+    let (unsaved_documents, obsolete_autosaves) = {
+        let state_handle = app.state();
+        let state = state_handle.lock();
+        let unsaved_documents = state
+            .documents_iter()
+            .filter(|d| !d.is_saved() && d.path().exists())
+            .filter(|d| autosaved_versions.get(d.path()) != Some(&d.version()))
+            .map(|d| (d.path().to_owned(), d.version(), d.sheet().clone()))
+            .collect::<Vec<_>>();
+        let obsolete_autosaves = autosaved_versions
+            .keys()
+            .filter(|p| {
+                state
+                    .documents_iter()
+                    .all(|d| d.path() != *p || d.is_saved())
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        (unsaved_documents, obsolete_autosaves)
+    };
+
+    for path in obsolete_autosaves {
+        discard(&path);
+        autosaved_versions.remove(&path);
+    }
+
+    for (path, version, sheet) in unsaved_documents {
+        let destination = autosave_path(&path);
+        match sheet.write(&destination) {
+            Ok(_) => {
+                autosaved_versions.insert(path, version);
+            }
+            Err(e) => error!(
+                "Error while writing autosave `{0}`: {e}",
+                destination.to_string_lossy()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::{mock::TigerAppMock, TigerApp};
+
+    #[tokio::test]
+    async fn writes_autosave_for_unsaved_documents() {
+        std::fs::create_dir_all("test-output").unwrap();
+        let path = PathBuf::from("test-output/writes_autosave_for_unsaved_documents.tiger");
+        std::fs::copy("test-data/samurai.tiger", &path).unwrap();
+        discard(&path);
+
+        let app = TigerAppMock::new();
+        app.open_documents(vec![path.clone()]).await;
+        let mut autosaved_versions = HashMap::new();
+
+        autosave(&app, &mut autosaved_versions);
+        assert!(!autosave_path(&path).exists());
+
+        app.create_animation();
+        autosave(&app, &mut autosaved_versions);
+        assert!(autosave_path(&path).exists());
+        assert!(Sheet::<Any>::read(autosave_path(&path)).is_ok());
+
+        app.state().lock().close_document(&path);
+        autosave(&app, &mut autosaved_versions);
+        assert!(!autosave_path(&path).exists());
+    }
+
+    async fn open_document_with_autosave(path: &Path) -> (TigerAppMock, usize) {
+        std::fs::create_dir_all("test-output").unwrap();
+        std::fs::copy("test-data/samurai.tiger", path).unwrap();
+        discard(path);
+
+        let app = TigerAppMock::new();
+        app.open_documents(vec![path.to_owned()]).await;
+        app.create_animation();
+        autosave(&app, &mut HashMap::new());
+        let recovered_animations = count_animations(&app);
+
+        let app = TigerAppMock::new();
+        app.open_documents(vec![path.to_owned()]).await;
+        (app, recovered_animations)
+    }
+
+    fn count_animations(app: &TigerAppMock) -> usize {
+        let state_handle = app.state();
+        let state = state_handle.lock();
+        let document = state.current_document().unwrap();
+        document.sheet().animations_iter().count()
+    }
+
+    #[tokio::test]
+    async fn offers_autosave_recovery_when_opening_document() {
+        let path =
+            PathBuf::from("test-output/offers_autosave_recovery_when_opening_document.tiger");
+        let (app, recovered_animations) = open_document_with_autosave(&path).await;
+        assert!(app.client_state().documents[0].has_recoverable_autosave);
+        assert_eq!(count_animations(&app), recovered_animations - 1);
+
+        app.recover_autosave();
+        assert!(!app.client_state().documents[0].has_recoverable_autosave);
+        assert!(app.client_state().documents[0].has_unsaved_changes);
+        assert_eq!(count_animations(&app), recovered_animations);
+    }
+
+    #[tokio::test]
+    async fn can_discard_autosave_when_opening_document() {
+        let path = PathBuf::from("test-output/can_discard_autosave_when_opening_document.tiger");
+        let (app, recovered_animations) = open_document_with_autosave(&path).await;
+
+        app.discard_autosave();
+        assert!(!app.client_state().documents[0].has_recoverable_autosave);
+        assert!(!app.client_state().documents[0].has_unsaved_changes);
+        assert_eq!(count_animations(&app), recovered_animations - 1);
+        assert!(!autosave_path(&path).exists());
+    }
+
+    #[tokio::test]
+    async fn can_change_autosave_interval() {
+        let app = TigerAppMock::new();
+        app.set_autosave_interval(300);
+        assert_eq!(app.client_state().autosave_interval_secs, 300);
+        assert_eq!(
+            app.state().lock().autosave_interval(),
+            Duration::from_secs(300)
+        );
+    }
+}
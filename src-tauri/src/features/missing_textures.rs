@@ -47,6 +47,7 @@ mod test {
 
     use std::{fs::File, path::PathBuf};
 
+    use crate::dto;
     use crate::mock::TigerAppMock;
 
     #[test]
@@ -69,4 +70,37 @@ mod test {
         std::fs::remove_file(filename).ok();
         app.assert_eventually(|| is_missing(&app));
     }
+
+    #[test]
+    fn detects_keyframes_with_missing_frames() {
+        std::fs::create_dir_all("test-output").unwrap();
+        let dir = std::env::current_dir().unwrap();
+        let frame = dir.join("test-output/detects_keyframes_with_missing_frames.png");
+        std::fs::copy(dir.join("test-data/samurai-dead-all.png"), &frame).unwrap();
+
+        let app = TigerAppMock::new();
+        app.new_document("tmp.tiger");
+        app.import_frames(vec![frame.clone()]);
+        app.create_animation();
+        app.begin_drag_and_drop_frame(frame.clone());
+        app.drop_frame_on_timeline(dto::Direction::North, 0);
+        let animation_name = app.client_state().documents[0]
+            .current_animation_name
+            .clone()
+            .unwrap();
+
+        app.wait_for_periodic_scans();
+        assert!(app.get_missing_keyframe_frames().is_empty());
+
+        std::fs::remove_file(&frame).unwrap();
+        app.assert_eventually(|| {
+            app.get_missing_keyframe_frames()
+                == vec![(animation_name.clone(), dto::Direction::North, 0)]
+        });
+        app.assert_eventually(|| {
+            let state = app.client_state();
+            let animation = &state.documents[0].sheet.animations[0];
+            animation.sequences[&dto::Direction::North].keyframes[0].missing_on_disk
+        });
+    }
 }
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use crate::dto::{StateTrim, ToFileName};
+use crate::features::autosave;
+use crate::TigerApp;
+
+pub fn init<A: TigerApp + Send + Clone + 'static>(app: A, period: Duration) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(period);
+
+        let documents_to_save = {
+            let state_handle = app.state();
+            let mut state = state_handle.lock();
+            let paths = state.take_documents_losing_focus();
+            state
+                .documents_iter()
+                .filter(|d| paths.iter().any(|p| p == d.path()))
+                .filter(|d| !d.is_saved() && d.path().exists())
+                .map(|d| (d.path().to_owned(), d.version(), d.sheet().clone()))
+                .collect::<Vec<_>>()
+        };
+
+        if documents_to_save.is_empty() {
+            continue;
+        }
+
+        let results = documents_to_save
+            .into_iter()
+            .map(|(path, version, sheet)| {
+                let result = sheet.write(&path);
+                (path, version, result)
+            })
+            .collect::<Vec<_>>();
+
+        app.patch_state(StateTrim::Full, |state| {
+            for (path, version, result) in results {
+                match result {
+                    Ok(_) => {
+                        autosave::discard(&path);
+                        if let Some(d) = state.document_mut(&path) {
+                            d.mark_as_saved(version);
+                        }
+                    }
+                    Err(e) => state.show_error_message(
+                        "Error".to_owned(),
+                        format!(
+                            "An error occured while trying to save `{}`",
+                            path.to_file_name()
+                        ),
+                        e.to_string(),
+                    ),
+                }
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::path::PathBuf;
+
+    use crate::mock::TigerAppMock;
+    use crate::sheet::{Any, Sheet};
+
+    #[tokio::test]
+    async fn saves_document_when_focus_moves_away() {
+        let path_a = PathBuf::from("test-output/saves_document_when_focus_moves_away_a.tiger");
+        let path_b = PathBuf::from("test-output/saves_document_when_focus_moves_away_b.tiger");
+        std::fs::copy("test-data/samurai.tiger", &path_a).unwrap();
+        std::fs::copy("test-data/flame.tiger", &path_b).unwrap();
+        let count_animations =
+            |path: &PathBuf| Sheet::<Any>::read(path).unwrap().animations_iter().count();
+        let original_animations = count_animations(&path_a);
+
+        let app = TigerAppMock::new();
+        app.set_save_on_focus_loss(true);
+        app.open_documents(vec![path_a.clone(), path_b.clone()])
+            .await;
+        app.focus_document(&path_a);
+        app.create_animation();
+        app.focus_document(&path_b);
+
+        app.assert_eventually(|| count_animations(&path_a) == original_animations + 1);
+        app.assert_eventually(|| {
+            app.client_state()
+                .documents
+                .iter()
+                .all(|d| !d.has_unsaved_changes)
+        });
+    }
+}
@@ -0,0 +1,125 @@
+use log::error;
+use squeak::Response;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+
+use crate::api::Api;
+use crate::dto::{StateTrim, ToFileName};
+use crate::state::Session;
+use crate::TigerApp;
+
+pub fn init<A: TigerApp + Send + Sync + Clone + 'static>(app: A) {
+    let session_file = app.paths().lock().session_file.clone();
+
+    let session = match read_from_disk(&session_file) {
+        Ok(session) => session,
+        Err(e) => {
+            error!("Error while reading previous session: {e}");
+            Session::default()
+        }
+    };
+
+    {
+        let state_handle = app.state();
+        let mut state = state_handle.lock();
+        state.set_session(session.clone());
+
+        let (tx, rx) = channel();
+        state.session_delegate().subscribe(move |session| {
+            tx.send(session.clone()).ok();
+            Response::StaySubscribed
+        });
+
+        std::thread::spawn(move || loop {
+            let Ok(session) = rx.recv() else { break };
+            if let Err(e) = write_to_disk(&session, &session_file) {
+                error!("Error while saving session: {e}");
+            }
+        });
+    }
+
+    if session.restore_on_startup && !session.documents.is_empty() {
+        std::thread::spawn(move || restore(&app, session));
+    }
+}
+
+fn restore<A: TigerApp + Sync>(app: &A, session: Session) {
+    let (documents, missing_documents): (Vec<PathBuf>, Vec<PathBuf>) =
+        session.documents.into_iter().partition(|d| d.exists());
+
+    tauri::async_runtime::block_on(app.open_documents(documents)).ok();
+
+    app.patch_state(StateTrim::Full, |state| {
+        if let Some(current_document) = &session.current_document {
+            state.focus_document(current_document).ok();
+        }
+        if !missing_documents.is_empty() {
+            state.show_error_message(
+                "Previous Session".to_owned(),
+                "Some spritesheets from your previous session could not be reopened.".to_owned(),
+                missing_documents
+                    .iter()
+                    .map(|d| format!("`{}` no longer exists.", d.to_file_name()))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+        }
+    });
+    app.replace_state();
+}
+
+fn write_to_disk(session: &Session, destination: &Path) -> Result<(), std::io::Error> {
+    let file = File::create(destination)?;
+    serde_json::to_writer_pretty(file, session)?;
+    Ok(())
+}
+
+fn read_from_disk(source: &Path) -> Result<Session, std::io::Error> {
+    if !source.exists() {
+        return Ok(Session::default());
+    }
+    let file = File::open(source)?;
+    let session: Session = serde_json::from_reader(file)?;
+    Ok(session)
+}
+
+#[cfg(test)]
+mod test {
+
+    use sugar_path::SugarPath;
+
+    use super::*;
+    use crate::mock::TigerAppMock;
+
+    #[tokio::test]
+    async fn restores_documents_from_previous_session() {
+        let samurai_file = PathBuf::from("test-data/samurai.tiger").resolve();
+        let flame_file = PathBuf::from("test-data/flame.tiger").resolve();
+
+        let app = TigerAppMock::new();
+        let session_file = app.paths().lock().session_file.clone();
+        app.set_restore_session_on_startup(true);
+        app.open_documents(vec![&samurai_file, &flame_file]).await;
+        app.focus_document(&samurai_file);
+        app.request_exit();
+        app.assert_eventually(|| {
+            read_from_disk(&session_file)
+                .map(|s| s.documents.len() == 2)
+                .unwrap_or_default()
+        });
+
+        let app = TigerAppMock::new_uninitialized();
+        app.paths().lock().session_file = session_file;
+        app.init();
+        app.assert_eventually(|| {
+            let state = app.client_state();
+            state.documents.len() == 2 && state.current_document_path == Some(samurai_file.clone())
+        });
+        assert!(app
+            .client_state()
+            .documents
+            .iter()
+            .any(|d| d.path == flame_file));
+    }
+}
@@ -13,8 +13,12 @@ pub fn init<A: TigerApp + Send + Clone + 'static>(app: A, period: Duration) {
             state
                 .documents_iter()
                 .flat_map(|d| d.export_settings_edit())
-                .map(|s| match s {
-                    sheet::ExportSettings::Template(s) => s.template_file().to_owned(),
+                .flat_map(|s| match s {
+                    sheet::ExportSettings::Template(s) => s
+                        .targets()
+                        .into_iter()
+                        .map(|(template_file, _)| template_file.to_owned())
+                        .collect::<Vec<_>>(),
                 })
                 .collect::<HashSet<_>>()
         }
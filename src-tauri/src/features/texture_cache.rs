@@ -1,4 +1,5 @@
-use image::DynamicImage;
+use euclid::default::Vector2D;
+use image::{DynamicImage, GenericImageView};
 use log::error;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -65,6 +66,30 @@ impl Handle {
             }
         });
     }
+
+    pub fn preload<P: AsRef<Path>>(&self, textures: &HashSet<P>) {
+        add(textures, self);
+    }
+
+    pub fn texture_sizes(&self) -> HashMap<PathBuf, Vector2D<u32>> {
+        let cache = self.lock();
+        cache
+            .iter()
+            .map(|(path, texture)| (path.to_owned(), texture.dimensions().into()))
+            .collect()
+    }
+
+    // Textures are scanned one at a time from a copy, so the cache is only locked while copying
+    pub fn transparent_textures(&self) -> HashSet<PathBuf> {
+        let paths: Vec<PathBuf> = self.lock().keys().cloned().collect();
+        paths
+            .into_iter()
+            .filter(|path| {
+                let texture = self.lock().get(path).cloned();
+                texture.is_some_and(|t| t.pixels().all(|(_, _, p)| p[3] == 0))
+            })
+            .collect()
+    }
 }
 
 fn remove<P: AsRef<Path>>(textures: &HashSet<P>, texture_cache: &Handle) {
@@ -152,4 +177,21 @@ mod test {
             &image::open(after_frame).unwrap()
         );
     }
+
+    #[tokio::test]
+    async fn can_list_transparent_textures() {
+        let dir = std::env::current_dir().unwrap();
+        let frame = dir.join("test-output/can_list_transparent_textures.png");
+        let opaque_frame = dir.join("test-data/samurai-dead-all.png");
+        image::RgbaImage::new(16, 16).save(&frame).unwrap();
+
+        let app = TigerAppMock::new();
+        app.open_documents(vec!["test-data/samurai.tiger"]).await;
+        app.import_frames(vec![frame.clone()]);
+
+        app.wait_for_periodic_scans();
+        let transparent_textures = app.texture_cache().transparent_textures();
+        assert!(transparent_textures.contains(&frame));
+        assert!(!transparent_textures.contains(&opaque_frame));
+    }
 }
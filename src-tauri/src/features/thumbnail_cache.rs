@@ -0,0 +1,151 @@
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView, ImageOutputFormat, RgbaImage};
+use log::error;
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::utils::handle;
+use crate::TigerApp;
+
+const THUMBNAIL_SIZE: u32 = 64;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ThumbnailKey {
+    pub document: PathBuf,
+    pub animation: String,
+    pub frame: PathBuf,
+    pub version: i32,
+}
+
+#[derive(Default)]
+pub struct ThumbnailCache {
+    thumbnails: HashMap<ThumbnailKey, Vec<u8>>,
+    requests: HashSet<ThumbnailKey>,
+}
+
+impl ThumbnailCache {
+    // Drops requests superseded by a newer version of the same animation, and returns the others
+    fn prune_requests(&mut self) -> HashSet<ThumbnailKey> {
+        let mut latest_versions = HashMap::<(&PathBuf, &String), i32>::new();
+        for key in &self.requests {
+            let version = latest_versions
+                .entry((&key.document, &key.animation))
+                .or_insert(key.version);
+            *version = (*version).max(key.version);
+        }
+        let stale_requests = self
+            .requests
+            .iter()
+            .filter(|k| latest_versions[&(&k.document, &k.animation)] != k.version)
+            .cloned()
+            .collect::<Vec<_>>();
+        for key in stale_requests {
+            self.requests.remove(&key);
+        }
+        self.requests.clone()
+    }
+}
+
+pub type Handle = handle::Handle<ThumbnailCache>;
+
+impl Handle {
+    pub fn init<A: TigerApp + Send + Clone + 'static>(&self, app: A, period: Duration) {
+        std::thread::spawn({
+            let thumbnail_cache = self.clone();
+            move || loop {
+                std::thread::sleep(period);
+                let requests = thumbnail_cache.lock().prune_requests();
+                for key in requests {
+                    let texture = {
+                        let texture_cache = app.texture_cache();
+                        let textures = texture_cache.lock();
+                        textures.get(&key.frame).cloned()
+                    };
+                    // Textures which are not loaded yet are retried on the next pass
+                    let Some(texture) = texture else {
+                        if !key.frame.exists() {
+                            thumbnail_cache.lock().requests.remove(&key);
+                        }
+                        continue;
+                    };
+                    let texture =
+                        texture.resize(THUMBNAIL_SIZE, THUMBNAIL_SIZE, FilterType::Nearest);
+                    let thumbnail = match render(&texture) {
+                        Ok(t) => t,
+                        Err(e) => {
+                            error!("Error while rendering animation thumbnail: {e}");
+                            Vec::new()
+                        }
+                    };
+                    let mut cache = thumbnail_cache.lock();
+                    cache.requests.remove(&key);
+                    cache
+                        .thumbnails
+                        .retain(|k, _| k.document != key.document || k.animation != key.animation);
+                    cache.thumbnails.insert(key, thumbnail);
+                }
+            }
+        });
+    }
+
+    // Returns the thumbnail if it is ready, and otherwise queues it for generation.
+    pub fn request(&self, key: ThumbnailKey) -> Option<Vec<u8>> {
+        let mut cache = self.lock();
+        if let Some(thumbnail) = cache.thumbnails.get(&key) {
+            return Some(thumbnail.clone());
+        }
+        cache.requests.insert(key);
+        None
+    }
+}
+
+fn render(texture: &DynamicImage) -> Result<Vec<u8>, image::ImageError> {
+    let (width, height) = texture.dimensions();
+    let mut thumbnail = RgbaImage::new(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+    image::imageops::overlay(
+        &mut thumbnail,
+        &texture.to_rgba8(),
+        (THUMBNAIL_SIZE.saturating_sub(width) / 2) as i64,
+        (THUMBNAIL_SIZE.saturating_sub(height) / 2) as i64,
+    );
+    let mut bytes = Vec::new();
+    DynamicImage::ImageRgba8(thumbnail)
+        .write_to(&mut Cursor::new(&mut bytes), ImageOutputFormat::Png)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::mock::TigerAppMock;
+
+    #[test]
+    fn prunes_outdated_thumbnail_requests() {
+        let key = |version| ThumbnailKey {
+            document: "sheet.tiger".into(),
+            animation: "walk".to_owned(),
+            frame: "walk_0.png".into(),
+            version,
+        };
+        let mut cache = ThumbnailCache::default();
+        cache.requests.insert(key(1));
+        cache.requests.insert(key(2));
+        assert_eq!(cache.prune_requests(), HashSet::from([key(2)]));
+        assert_eq!(cache.requests, HashSet::from([key(2)]));
+    }
+
+    #[tokio::test]
+    async fn can_generate_animation_thumbnail() {
+        let app = TigerAppMock::new();
+        app.open_documents(vec!["test-data/samurai.tiger"]).await;
+
+        app.assert_eventually(|| {
+            app.get_animation_thumbnail("attack")
+                .map(|t| !t.is_empty())
+                .unwrap_or_default()
+        });
+    }
+}
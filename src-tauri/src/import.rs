@@ -0,0 +1,424 @@
+use image::{imageops, DynamicImage, GenericImageView, ImageError, RgbaImage};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs::{create_dir_all, File},
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("Filesystem error for `{0}`: `{1}`")]
+    IoError(PathBuf, std::io::Error),
+    #[error("Could not parse `{0}`: `{1}`")]
+    ParseError(PathBuf, serde_json::Error),
+    #[error("Frames must be exported as an array rather than a hash")]
+    UnorderedFrames,
+    #[error("Rotated frames are not supported (`{0}`)")]
+    RotatedFrame(String),
+    #[error("Frame `{0}` lies outside of the atlas image")]
+    FrameOutOfBounds(String),
+    #[error("Animation `{0}` refers to frame {1} which does not exist")]
+    InvalidFrameIndex(String, usize),
+    #[error("Frame size must be greater than zero")]
+    InvalidFrameSize,
+    #[error("Image is too small to contain {0} frames of this size")]
+    NotEnoughFrames(u32),
+    #[error("{0}")]
+    ImageError(#[from] ImageError),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportedAnimation {
+    pub name: String,
+    pub keyframes: Vec<(PathBuf, u64)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportedSheet {
+    pub frames: Vec<PathBuf>,
+    pub animations: Vec<ImportedAnimation>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct AtlasRegion {
+    pub name: String,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Deserialize)]
+struct AsepriteSheet {
+    frames: AsepriteFrames,
+    meta: AsepriteMeta,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AsepriteFrames {
+    Array(Vec<AsepriteFrame>),
+    Hash(HashMap<String, AsepriteFrame>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AsepriteFrame {
+    #[serde(default)]
+    filename: String,
+    frame: AsepriteRect,
+    #[serde(default)]
+    rotated: bool,
+    sprite_source_size: AsepriteRect,
+    source_size: AsepriteSize,
+    duration: u64,
+}
+
+#[derive(Deserialize)]
+struct AsepriteRect {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize)]
+struct AsepriteSize {
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AsepriteMeta {
+    image: PathBuf,
+    #[serde(default)]
+    frame_tags: Vec<AsepriteTag>,
+}
+
+#[derive(Deserialize)]
+struct AsepriteTag {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: AsepriteTagDirection,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum AsepriteTagDirection {
+    #[default]
+    Forward,
+    Reverse,
+    Pingpong,
+}
+
+// Tiger frames are whole image files, so every frame of the Aseprite atlas is sliced
+// into its own PNG inside `output_directory`. Trimmed frames are padded back to their
+// untrimmed size so that all frames of an animation share the same origin.
+pub fn import_aseprite<T: AsRef<Path>, U: AsRef<Path>>(
+    json_file: T,
+    output_directory: U,
+) -> Result<ImportedSheet, ImportError> {
+    let json_file = json_file.as_ref();
+    let output_directory = output_directory.as_ref();
+
+    let file = File::open(json_file).map_err(|e| ImportError::IoError(json_file.to_owned(), e))?;
+    let aseprite_sheet: AsepriteSheet = serde_json::from_reader(BufReader::new(file))
+        .map_err(|e| ImportError::ParseError(json_file.to_owned(), e))?;
+    let aseprite_frames = match aseprite_sheet.frames {
+        AsepriteFrames::Array(frames) => frames,
+        AsepriteFrames::Hash(_) => return Err(ImportError::UnorderedFrames),
+    };
+
+    let atlas_file = json_file
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(&aseprite_sheet.meta.image);
+    let atlas = image::open(atlas_file)?;
+
+    create_dir_all(output_directory)
+        .map_err(|e| ImportError::IoError(output_directory.to_owned(), e))?;
+
+    let stem = json_file
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut frames = Vec::new();
+    for (index, aseprite_frame) in aseprite_frames.iter().enumerate() {
+        let path = output_directory.join(format!("{stem}_{index}.png"));
+        slice_frame(&atlas, aseprite_frame)?.save(&path)?;
+        frames.push(path);
+    }
+
+    let keyframe = |index: usize| (frames[index].clone(), aseprite_frames[index].duration);
+    let animations = if aseprite_sheet.meta.frame_tags.is_empty() {
+        vec![ImportedAnimation {
+            name: stem.clone(),
+            keyframes: (0..frames.len()).map(keyframe).collect(),
+        }]
+    } else {
+        aseprite_sheet
+            .meta
+            .frame_tags
+            .iter()
+            .map(|tag| {
+                if tag.from > tag.to || tag.to >= frames.len() {
+                    return Err(ImportError::InvalidFrameIndex(
+                        tag.name.clone(),
+                        tag.from.max(tag.to),
+                    ));
+                }
+                Ok(ImportedAnimation {
+                    name: tag.name.clone(),
+                    keyframes: tag_frame_indices(tag).into_iter().map(keyframe).collect(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    Ok(ImportedSheet { frames, animations })
+}
+
+// Frames are read left to right and top to bottom, so horizontal strips, vertical strips
+// and grids are all supported. Each frame is saved as a PNG file next to the source image.
+pub fn import_sprite_strip<T: AsRef<Path>>(
+    image_file: T,
+    frame_width: u32,
+    frame_height: u32,
+    count: u32,
+) -> Result<Vec<PathBuf>, ImportError> {
+    let image_file = image_file.as_ref();
+    if frame_width == 0 || frame_height == 0 {
+        return Err(ImportError::InvalidFrameSize);
+    }
+
+    let strip = image::open(image_file)?;
+    let columns = strip.width() / frame_width;
+    let rows = strip.height() / frame_height;
+    if columns * rows < count {
+        return Err(ImportError::NotEnoughFrames(count));
+    }
+
+    let directory = image_file.parent().unwrap_or(Path::new(""));
+    let stem = image_file
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut frames = Vec::new();
+    for index in 0..count {
+        let (x, y) = (
+            (index % columns) * frame_width,
+            (index / columns) * frame_height,
+        );
+        let path = directory.join(format!("{stem}_{index}.png"));
+        strip
+            .view(x, y, frame_width, frame_height)
+            .to_image()
+            .save(&path)?;
+        frames.push(path);
+    }
+    Ok(frames)
+}
+
+// Unlike sprite strips, regions can have any size and position within the atlas. Each
+// region is saved as a PNG file named after the region, next to the atlas image.
+pub fn import_atlas_regions<T: AsRef<Path>>(
+    atlas_file: T,
+    regions: &[AtlasRegion],
+) -> Result<Vec<PathBuf>, ImportError> {
+    let atlas_file = atlas_file.as_ref();
+    let atlas = image::open(atlas_file)?;
+    for region in regions {
+        if region.width == 0 || region.height == 0 {
+            return Err(ImportError::InvalidFrameSize);
+        }
+        if region.x + region.width > atlas.width() || region.y + region.height > atlas.height() {
+            return Err(ImportError::FrameOutOfBounds(region.name.clone()));
+        }
+    }
+
+    let directory = atlas_file.parent().unwrap_or(Path::new(""));
+    let mut frames = Vec::new();
+    for region in regions {
+        let path = directory.join(format!("{}.png", region.name));
+        atlas
+            .view(region.x, region.y, region.width, region.height)
+            .to_image()
+            .save(&path)?;
+        frames.push(path);
+    }
+    Ok(frames)
+}
+
+fn slice_frame(
+    atlas: &DynamicImage,
+    aseprite_frame: &AsepriteFrame,
+) -> Result<RgbaImage, ImportError> {
+    if aseprite_frame.rotated {
+        return Err(ImportError::RotatedFrame(aseprite_frame.filename.clone()));
+    }
+    let rect = &aseprite_frame.frame;
+    if rect.x + rect.w > atlas.width() || rect.y + rect.h > atlas.height() {
+        return Err(ImportError::FrameOutOfBounds(
+            aseprite_frame.filename.clone(),
+        ));
+    }
+    let mut image = RgbaImage::new(aseprite_frame.source_size.w, aseprite_frame.source_size.h);
+    imageops::replace(
+        &mut image,
+        &atlas.view(rect.x, rect.y, rect.w, rect.h).to_image(),
+        aseprite_frame.sprite_source_size.x as i64,
+        aseprite_frame.sprite_source_size.y as i64,
+    );
+    Ok(image)
+}
+
+fn tag_frame_indices(tag: &AsepriteTag) -> Vec<usize> {
+    match tag.direction {
+        AsepriteTagDirection::Forward => (tag.from..=tag.to).collect(),
+        AsepriteTagDirection::Reverse => (tag.from..=tag.to).rev().collect(),
+        AsepriteTagDirection::Pingpong => (tag.from..=tag.to)
+            .chain((tag.from + 1..tag.to).rev())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::document::{Command, Document};
+    use crate::sheet::Direction;
+    use crate::{mock::TigerAppMock, TigerApp};
+
+    #[test]
+    fn can_import_aseprite_sheet() {
+        let output_directory = "test-output/can_import_aseprite_sheet";
+        let imported =
+            import_aseprite("test-data/samurai-aseprite.json", output_directory).unwrap();
+
+        assert_eq!(imported.frames.len(), 5);
+        assert!(imported.frames.iter().all(|f| f.exists()));
+        let trimmed_frame = image::open(&imported.frames[4]).unwrap();
+        assert_eq!(trimmed_frame.dimensions(), (32, 32));
+
+        let keyframe_counts = imported
+            .animations
+            .iter()
+            .map(|a| (a.name.as_str(), a.keyframes.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(keyframe_counts, vec![("idle", 2), ("walk", 4), ("dead", 1)]);
+        assert_eq!(imported.animations[0].keyframes[1].1, 150);
+
+        let mut d = Document::new("tmp");
+        d.process_command(Command::ImportAseprite(imported.clone()))
+            .unwrap();
+        assert_eq!(d.sheet().frames_iter().count(), 5);
+        let walk = d.sheet().animation("walk").unwrap();
+        let sequence = walk.sequence(Direction::North).unwrap();
+        assert_eq!(sequence.num_keyframes(), 4);
+        assert_eq!(
+            sequence
+                .keyframes_iter()
+                .map(|k| k.duration_millis())
+                .collect::<Vec<_>>(),
+            vec![150, 80, 80, 80]
+        );
+    }
+
+    #[tokio::test]
+    async fn can_import_sprite_strip() {
+        let directory = PathBuf::from("test-output/can_import_sprite_strip");
+        std::fs::create_dir_all(&directory).unwrap();
+        let strip_file = directory.join("strip.png");
+        RgbaImage::from_fn(64, 16, |x, _| image::Rgba([(x / 16 * 60) as u8, 0, 0, 255]))
+            .save(&strip_file)
+            .unwrap();
+
+        let app = TigerAppMock::new();
+        app.new_document(directory.join("strip.tiger"));
+        app.import_sprite_strip(&strip_file, 16, 16, 4).await;
+
+        let frames = (0..4)
+            .map(|i| directory.join(format!("strip_{i}.png")))
+            .collect::<Vec<_>>();
+        {
+            let state_handle = app.state();
+            let state = state_handle.lock();
+            let sheet = state.current_document().unwrap().sheet();
+            assert_eq!(sheet.frames_iter().count(), 4);
+            assert!(frames.iter().all(|f| sheet.has_frame(f)));
+        }
+        let last_frame = image::open(&frames[3]).unwrap();
+        assert_eq!(last_frame.dimensions(), (16, 16));
+        assert_eq!(last_frame.get_pixel(0, 0)[0], 180);
+
+        app.import_sprite_strip(&strip_file, 16, 16, 5).await;
+        assert!(app.client_state().error.is_some());
+    }
+
+    #[tokio::test]
+    async fn can_import_atlas_regions() {
+        let directory = PathBuf::from("test-output/can_import_atlas_regions");
+        std::fs::create_dir_all(&directory).unwrap();
+        let atlas_file = directory.join("atlas.png");
+        RgbaImage::from_fn(48, 32, |x, _| {
+            image::Rgba([if x < 16 { 255 } else { 0 }, 0, 0, 255])
+        })
+        .save(&atlas_file)
+        .unwrap();
+
+        let region = |name: &str, x, y, width, height| AtlasRegion {
+            name: name.to_owned(),
+            x,
+            y,
+            width,
+            height,
+        };
+        let app = TigerAppMock::new();
+        app.new_document(directory.join("atlas.tiger"));
+        app.import_atlas_regions(
+            &atlas_file,
+            vec![
+                region("sword", 0, 0, 16, 32),
+                region("shield", 16, 8, 32, 24),
+            ],
+        )
+        .await;
+
+        {
+            let state_handle = app.state();
+            let state = state_handle.lock();
+            let sheet = state.current_document().unwrap().sheet();
+            assert_eq!(sheet.frames_iter().count(), 2);
+            assert!(sheet.has_frame(directory.join("sword.png")));
+            assert!(sheet.has_frame(directory.join("shield.png")));
+        }
+        let mut names = app.client_state().documents[0]
+            .sheet
+            .frames
+            .iter()
+            .map(|f| f.name.clone())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["shield".to_owned(), "sword".to_owned()]);
+
+        let sword = image::open(directory.join("sword.png")).unwrap();
+        assert_eq!(sword.dimensions(), (16, 32));
+        assert_eq!(sword.get_pixel(0, 0)[0], 255);
+        let shield = image::open(directory.join("shield.png")).unwrap();
+        assert_eq!(shield.dimensions(), (32, 24));
+        assert_eq!(shield.get_pixel(0, 0)[0], 0);
+
+        app.import_atlas_regions(&atlas_file, vec![region("oob", 40, 0, 16, 16)])
+            .await;
+        assert!(app.client_state().error.is_some());
+    }
+}
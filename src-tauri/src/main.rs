@@ -6,23 +6,27 @@
 use log::{error, LevelFilter};
 use serde::Serialize;
 use simplelog::{ColorChoice, CombinedLogger, Config, TermLogger, TerminalMode, WriteLogger};
+use std::path::Path;
 use std::time::Duration;
 use tauri::{ClipboardManager, Manager};
 
 use dto::StateTrim;
-use features::texture_cache;
+use features::{texture_cache, thumbnail_cache};
 use state::State;
 use utils::paths;
 
 mod api;
+mod cli;
 mod document;
 mod dto;
 mod export;
 mod features;
+mod import;
 #[cfg(test)]
 mod mock;
 mod sheet;
 mod state;
+mod theme;
 mod utils;
 
 static EVENT_PATCH_STATE: &str = "patch-state";
@@ -46,17 +50,28 @@ fn main() {
     ])
     .unwrap();
 
+    if let Some(exit_code) = cli::run(std::env::args().skip(1)) {
+        std::process::exit(exit_code);
+    }
+
     tauri::Builder::default()
         .manage(state::Handle::default())
         .manage(texture_cache::Handle::default())
+        .manage(thumbnail_cache::Handle::default())
         .manage(paths::Handle::new(paths))
         .setup(|tauri_app| {
             init_window_shadow(tauri_app);
             tauri_app
                 .texture_cache()
                 .init(tauri_app.handle(), Duration::from_millis(1_000));
+            tauri_app
+                .thumbnail_cache()
+                .init(tauri_app.handle(), Duration::from_millis(100));
+            features::autosave::init(tauri_app.handle(), Duration::from_millis(1_000));
             features::missing_textures::init(tauri_app.handle(), Duration::from_millis(500));
             features::recent_documents::init(tauri_app.handle());
+            features::save_on_focus_loss::init(tauri_app.handle(), Duration::from_millis(100));
+            features::session::init(tauri_app.handle());
             features::template_hot_reload::init(tauri_app.handle(), Duration::from_millis(1_000));
             features::texture_hot_reload::init(tauri_app.handle(), Duration::from_millis(1_000));
             features::clipboard_analysis::init(tauri_app.handle(), Duration::from_millis(100));
@@ -79,11 +94,16 @@ fn main() {
             api::save_all,
             api::show_error_message,
             // Document
+            api::add_export_target,
+            api::add_keyframe_event,
+            api::add_workbench_guide,
+            api::align_keyframe_offsets,
             api::apply_direction_preset,
             api::begin_drag_and_drop_frame,
             api::begin_drag_and_drop_keyframe,
             api::begin_drag_keyframe_duration,
             api::begin_export_as,
+            api::begin_nudge_anchor,
             api::begin_nudge_hitbox,
             api::begin_nudge_keyframe,
             api::begin_rename_animation,
@@ -96,58 +116,122 @@ fn main() {
             api::cancel_export_as,
             api::cancel_rename,
             api::center_workbench,
+            api::clear_history,
             api::clear_selection,
+            api::clear_workbench_guides,
             api::close_without_saving,
             api::copy,
+            api::copy_keyframe_hitboxes,
+            api::create_anchor,
             api::create_animation,
+            api::create_animation_from_selected_frames,
             api::create_hitbox,
             api::cut,
+            api::delete_all_hitboxes_on_keyframe,
+            api::delete_anchor,
             api::delete_animation,
+            api::delete_animations_not_matching,
             api::delete_frame,
             api::delete_hitbox,
+            api::delete_hitbox_from_all_keyframes,
             api::delete_selected_animations,
             api::delete_selected_frames,
             api::delete_selected_hitboxes,
             api::delete_selected_keyframes,
             api::delete_selection,
             api::disable_sprite_darkening,
+            api::discard_autosave,
             api::drop_frame_on_timeline,
             api::drop_keyframe_on_timeline,
+            api::duplicate_selected_hitboxes,
             api::edit_animation,
             api::enable_sprite_darkening,
             api::end_drag_and_drop_frame,
             api::end_drag_and_drop_keyframe,
             api::end_drag_keyframe_duration,
             api::end_export_as,
+            api::end_nudge_anchor,
             api::end_nudge_hitbox,
             api::end_nudge_keyframe,
             api::end_rename_animation,
             api::end_rename_hitbox,
             api::end_resize_hitbox,
             api::export,
+            api::export_and_reveal,
+            api::export_command_history,
+            api::export_contact_sheet,
+            api::extract_common_offset,
             api::filter_animations,
             api::filter_frames,
+            api::find_frame_usages,
+            api::fit_workbench_to_item,
+            api::flip_hitboxes_horizontally,
+            api::get_animation_keyframes,
+            api::get_animation_thumbnail,
+            api::get_current_keyframe_index,
+            api::get_degenerate_animations,
+            api::get_hitbox_rect,
+            api::get_keyframe_durations_in_frames,
+            api::get_keyframe_offset,
+            api::get_missing_keyframe_frames,
+            api::get_packing_stats,
+            api::get_template_context_preview,
+            api::get_timeline_clock,
+            api::get_unsaved_documents,
             api::hide_hitboxes,
             api::hide_origin,
             api::hide_sprite,
+            api::import_aseprite,
+            api::import_atlas_regions,
             api::import_frames,
+            api::import_frames_into_animation,
+            api::import_sprite_strip,
+            api::invert_hitbox_selection,
             api::jump_to_animation_end,
             api::jump_to_animation_start,
+            api::jump_to_keyframe,
             api::jump_to_next_frame,
             api::jump_to_previous_frame,
             api::lock_hitboxes,
+            api::match_hitbox_sizes,
+            api::merge_adjacent_duplicate_keyframes,
+            api::move_keyframes_earlier,
+            api::move_keyframes_later,
+            api::move_workbench_guide,
+            api::normalize_animation_names,
+            api::nudge_animation_origin,
             api::nudge_selection,
             api::pan_timeline,
             api::pan,
             api::paste,
+            api::paste_after_selection,
+            api::paste_keyframe_hitboxes,
+            api::paste_properties,
             api::pause,
             api::play,
+            api::prefix_hitbox_names,
+            api::quantize_keyframe_durations,
+            api::recover_autosave,
             api::redo,
             api::begin_relocate_frames,
             api::relocate_frame,
             api::end_relocate_frames,
             api::cancel_relocate_frames,
+            api::relocate_frames_root,
+            api::remove_export_target,
+            api::remove_keyframe_event,
+            api::remove_workbench_guide,
+            api::rename_anchor,
+            api::rename_frame_file,
+            api::reopen_last_closed_document,
+            api::reorder_animations,
+            api::repair_sheet,
+            api::replace_frame_source,
+            api::reset_keyframe_colors,
+            api::reset_keyframe_duration,
+            api::reset_keyframe_offsets,
             api::reset_timeline_zoom,
+            api::reset_view,
             api::reset_workbench_zoom,
             api::save_as,
             api::save,
@@ -158,40 +242,89 @@ fn main() {
             api::select_frame,
             api::select_hitbox,
             api::select_keyframe,
+            api::select_keyframes_in_range,
+            api::select_next_animation,
+            api::select_previous_animation,
+            api::set_anchor_position,
+            api::set_animation_frame,
             api::set_animation_looping,
+            api::set_animation_origin,
+            api::set_animation_start_delay,
             api::set_animations_list_offset,
+            api::set_autosave_interval,
+            api::set_default_preserve_aspect_ratio,
+            api::set_export_allow_rotation,
+            api::set_export_deduplicate_frames,
+            api::set_export_format,
             api::set_export_metadata_file,
             api::set_export_metadata_paths_root,
+            api::set_export_mip_safe_padding,
+            api::set_export_path_style,
+            api::set_export_settings,
+            api::set_export_target_metadata_file,
+            api::set_export_target_template_file,
             api::set_export_template_file,
             api::set_export_texture_file,
+            api::set_export_texture_format,
+            api::set_export_texture_scale,
+            api::set_export_write_manifest,
+            api::set_follow_playhead,
+            api::set_frame_normal_map,
             api::set_frames_list_mode,
             api::set_frames_list_offset,
+            api::set_grid_spacing,
+            api::set_grid_subdivisions,
             api::set_hitbox_height,
+            api::set_hitbox_kind,
             api::set_hitbox_position_x,
             api::set_hitbox_position_y,
             api::set_hitbox_width,
             api::set_hitboxes_list_offset,
+            api::set_keyframe_colors,
             api::set_keyframe_duration,
+            api::set_keyframe_duration_in_frames,
+            api::set_keyframe_hold,
             api::set_keyframe_offset_x,
             api::set_keyframe_offset_y,
             api::set_keyframe_snapping_base_duration,
+            api::set_min_keyframe_duration,
+            api::set_origin_offset,
+            api::set_packing_algorithm,
+            api::set_packing_order,
+            api::set_pan_button,
+            api::set_restore_session_on_startup,
+            api::set_save_on_focus_loss,
+            api::set_selected_animations_looping,
             api::set_snap_keyframe_durations,
             api::set_snap_keyframes_to_multiples_of_duration,
             api::set_snap_keyframes_to_other_keyframes,
             api::set_timeline_offset,
+            api::set_timeline_viewport_width,
             api::set_timeline_zoom_amount,
+            api::set_uniform_keyframe_duration,
             api::set_workbench_zoom_factor,
+            api::show_all_hitboxes,
             api::show_hitboxes,
             api::show_origin,
             api::show_sprite,
+            api::subdivide_hitbox,
+            api::suffix_hitbox_names,
+            api::swap_animation_names,
             api::tick,
+            api::toggle_hitbox_visibility,
+            api::toggle_playback_all_documents,
+            api::toggle_precise_durations,
             api::toggle_preserve_aspect_ratio,
+            api::translate_selected_keyframes,
+            api::trim_animation_ends,
             api::undo,
             api::unlock_hitboxes,
             api::update_drag_keyframe_duration,
+            api::update_nudge_anchor,
             api::update_nudge_hitbox,
             api::update_nudge_keyframe,
             api::update_resize_hitbox,
+            api::was_export_changed,
             api::zoom_in_timeline_around,
             api::zoom_in_timeline,
             api::zoom_in_workbench_around,
@@ -232,6 +365,7 @@ fn init_window_shadow(tauri_app: &mut tauri::App) {
 pub trait TigerApp {
     fn state(&self) -> state::Handle;
     fn texture_cache(&self) -> texture_cache::Handle;
+    fn thumbnail_cache(&self) -> thumbnail_cache::Handle;
     fn paths(&self) -> paths::Handle;
     fn patch_state<F: FnOnce(&mut State)>(&self, state_trim: StateTrim, operation: F);
     fn replace_state(&self);
@@ -239,6 +373,7 @@ pub trait TigerApp {
     fn read_clipboard(&self) -> Option<String>;
     fn write_clipboard<S: Into<String>>(&self, content: S);
     fn close_window(&self);
+    fn reveal_in_explorer<P: AsRef<Path>>(&self, path: P);
 }
 
 impl TigerApp for tauri::App {
@@ -250,6 +385,10 @@ impl TigerApp for tauri::App {
         self.handle().texture_cache()
     }
 
+    fn thumbnail_cache(&self) -> thumbnail_cache::Handle {
+        self.handle().thumbnail_cache()
+    }
+
     fn paths(&self) -> paths::Handle {
         self.handle().paths()
     }
@@ -277,6 +416,10 @@ impl TigerApp for tauri::App {
     fn close_window(&self) {
         self.handle().close_window()
     }
+
+    fn reveal_in_explorer<P: AsRef<Path>>(&self, path: P) {
+        self.handle().reveal_in_explorer(path)
+    }
 }
 
 impl TigerApp for tauri::AppHandle {
@@ -290,6 +433,11 @@ impl TigerApp for tauri::AppHandle {
         texture_cache::Handle::clone(&cache)
     }
 
+    fn thumbnail_cache(&self) -> thumbnail_cache::Handle {
+        let cache = tauri::Manager::state::<thumbnail_cache::Handle>(self);
+        thumbnail_cache::Handle::clone(&cache)
+    }
+
     fn paths(&self) -> paths::Handle {
         let paths = tauri::Manager::state::<paths::Handle>(self);
         paths::Handle::clone(&paths)
@@ -344,4 +492,31 @@ impl TigerApp for tauri::AppHandle {
             error!("Could not access app window to close it");
         }
     }
+
+    fn reveal_in_explorer<P: AsRef<Path>>(&self, path: P) {
+        // For future improvements, see https://github.com/tauri-apps/tauri/issues/4062
+        let path = path.as_ref();
+
+        #[cfg(windows)]
+        let result = std::process::Command::new("explorer")
+            .args(["/select,", path.to_string_lossy().as_ref()]) // The comma after select is not a typo
+            .spawn();
+
+        #[cfg(target_os = "macos")]
+        let result = std::process::Command::new("open")
+            .args(["-R", path.to_string_lossy().as_ref()])
+            .spawn();
+
+        #[cfg(target_os = "linux")]
+        let result = std::process::Command::new("xdg-open")
+            .arg(path.parent().unwrap_or(path))
+            .spawn();
+
+        #[cfg(any(windows, target_os = "macos", target_os = "linux"))]
+        {
+            if let Err(e) = result {
+                error!("Failed to reveal `{}`: `{e}`", path.to_string_lossy());
+            }
+        }
+    }
 }
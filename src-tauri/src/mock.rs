@@ -8,7 +8,8 @@ use std::{
 use crate::{
     api::Api,
     dto,
-    features::{self, texture_cache},
+    features::{self, texture_cache, thumbnail_cache},
+    import::AtlasRegion,
     state::{self, State},
     utils::{
         handle,
@@ -21,11 +22,13 @@ use crate::{
 pub struct TigerAppMock {
     state: state::Handle,
     texture_cache: texture_cache::Handle,
+    thumbnail_cache: thumbnail_cache::Handle,
     paths: paths::Handle,
     client_state: handle::Handle<dto::State>,
     events: handle::Handle<Vec<(String, serde_json::Value)>>,
     clipboard: handle::Handle<Option<String>>,
     closed: handle::Handle<bool>,
+    revealed_paths: handle::Handle<Vec<PathBuf>>,
 }
 
 impl TigerAppMock {
@@ -41,22 +44,28 @@ impl TigerAppMock {
         let paths = Paths::test_outputs();
         std::fs::remove_file(&paths.log_file).ok();
         std::fs::remove_file(&paths.recent_documents_file).ok();
+        std::fs::remove_file(&paths.session_file).ok();
         Self {
             state: state::Handle::default(),
             texture_cache: texture_cache::Handle::default(),
+            thumbnail_cache: thumbnail_cache::Handle::default(),
             paths: handle::Handle::new(paths),
             client_state: handle::Handle::new(State::default().to_dto(dto::StateTrim::Full)),
             events: handle::Handle::default(),
             clipboard: handle::Handle::default(),
             closed: handle::Handle::default(),
+            revealed_paths: handle::Handle::default(),
         }
     }
 
     pub fn init(&self) {
         self.texture_cache.init(self.clone(), Self::PERIOD);
+        self.thumbnail_cache.init(self.clone(), Self::PERIOD);
         features::clipboard_analysis::init(self.clone(), Self::PERIOD);
         features::missing_textures::init(self.clone(), Self::PERIOD);
         features::recent_documents::init(self.clone());
+        features::save_on_focus_loss::init(self.clone(), Self::PERIOD);
+        features::session::init(self.clone());
         features::template_hot_reload::init(self.clone(), Self::PERIOD);
         features::texture_hot_reload::init(self.clone(), Self::PERIOD);
         self.replace_state();
@@ -74,6 +83,10 @@ impl TigerAppMock {
         self.events.lock().clone()
     }
 
+    pub fn revealed_paths(&self) -> Vec<PathBuf> {
+        self.revealed_paths.lock().clone()
+    }
+
     pub fn assert_eventually<F: Fn() -> bool>(&self, test: F) {
         let start = std::time::Instant::now();
         while std::time::Instant::now().duration_since(start) < Duration::from_secs(5) {
@@ -94,6 +107,10 @@ impl TigerAppMock {
 
 #[allow(dead_code)]
 impl TigerAppMock {
+    pub fn align_keyframe_offsets(&self, align: dto::Align) {
+        self.apply_patch(Api::align_keyframe_offsets(self, align).unwrap());
+    }
+
     pub fn begin_drag_and_drop_frame<P: Into<PathBuf>>(&self, frame: P) {
         self.apply_patch(Api::begin_drag_and_drop_frame(self, frame).unwrap());
     }
@@ -110,6 +127,10 @@ impl TigerAppMock {
         self.apply_patch(Api::copy(self).unwrap());
     }
 
+    pub fn copy_keyframe_hitboxes(&self) {
+        self.apply_patch(Api::copy_keyframe_hitboxes(self).unwrap());
+    }
+
     pub fn cut(&self) {
         self.apply_patch(Api::cut(self).unwrap());
     }
@@ -130,10 +151,22 @@ impl TigerAppMock {
         self.apply_patch(Api::delete_hitbox(self, name).unwrap());
     }
 
+    pub fn delete_hitbox_from_all_keyframes<S: Into<String>>(&self, name: S) {
+        self.apply_patch(Api::delete_hitbox_from_all_keyframes(self, name).unwrap());
+    }
+
+    pub fn discard_autosave(&self) {
+        self.apply_patch(Api::discard_autosave(self).unwrap());
+    }
+
     pub fn drop_frame_on_timeline(&self, direction: dto::Direction, index: usize) {
         self.apply_patch(Api::drop_frame_on_timeline(self, direction, index).unwrap());
     }
 
+    pub fn duplicate_selected_hitboxes(&self) {
+        self.apply_patch(Api::duplicate_selected_hitboxes(self).unwrap());
+    }
+
     pub fn edit_animation<S: Into<String>>(&self, name: S) {
         self.apply_patch(Api::edit_animation(self, name).unwrap());
     }
@@ -142,14 +175,112 @@ impl TigerAppMock {
         self.apply_patch(Api::export(self).await.unwrap());
     }
 
+    pub async fn export_and_reveal(&self) {
+        self.apply_patch(Api::export_and_reveal(self).await.unwrap());
+    }
+
+    pub fn export_command_history(&self, redact_paths: bool) -> Result<String, String> {
+        Api::export_command_history(self, redact_paths)
+    }
+
+    pub async fn export_contact_sheet<P: Into<PathBuf> + Send>(&self, path: P) {
+        self.apply_patch(Api::export_contact_sheet(self, path).await.unwrap());
+    }
+
+    pub async fn get_template_context_preview(&self) -> Result<String, String> {
+        Api::get_template_context_preview(self).await
+    }
+
+    pub fn find_frame_usages<P: AsRef<Path>>(&self, path: P) -> Vec<dto::FrameUsage> {
+        Api::find_frame_usages(self, path).unwrap()
+    }
+
+    pub fn fit_workbench_to_item(&self, viewport_size: (f32, f32)) {
+        self.apply_patch(Api::fit_workbench_to_item(self, viewport_size).unwrap());
+    }
+
+    pub fn flip_hitboxes_horizontally(&self) {
+        self.apply_patch(Api::flip_hitboxes_horizontally(self).unwrap());
+    }
+
     pub fn focus_document<P: AsRef<Path>>(&self, path: P) {
         self.apply_patch(Api::focus_document(self, path).unwrap());
     }
 
+    pub async fn import_aseprite<P: Into<PathBuf> + Send>(&self, path: P) {
+        self.apply_patch(Api::import_aseprite(self, path).await.unwrap());
+    }
+
+    pub fn get_current_keyframe_index(&self) -> Option<usize> {
+        Api::get_current_keyframe_index(self).unwrap()
+    }
+
+    pub fn get_degenerate_animations(&self) -> Vec<String> {
+        Api::get_degenerate_animations(self).unwrap()
+    }
+
+    pub fn get_animation_keyframes<S: Into<String>>(&self, name: S) -> Vec<dto::KeyframeSummary> {
+        Api::get_animation_keyframes(self, name).unwrap()
+    }
+
+    pub fn get_animation_thumbnail<S: Into<String>>(&self, name: S) -> Option<Vec<u8>> {
+        Api::get_animation_thumbnail(self, name).unwrap()
+    }
+
+    pub fn get_hitbox_rect<S: Into<String>>(&self, name: S) -> Option<((i32, i32), (u32, u32))> {
+        Api::get_hitbox_rect(self, name).unwrap()
+    }
+
+    pub fn get_keyframe_offset(&self) -> Option<(i32, i32)> {
+        Api::get_keyframe_offset(self).unwrap()
+    }
+
+    pub async fn get_packing_stats(&self) -> Result<dto::PackingStats, String> {
+        Api::get_packing_stats(self).await
+    }
+
+    pub fn get_timeline_clock(&self) -> u64 {
+        Api::get_timeline_clock(self).unwrap()
+    }
+
+    pub fn get_missing_keyframe_frames(&self) -> Vec<(String, dto::Direction, usize)> {
+        Api::get_missing_keyframe_frames(self).unwrap()
+    }
+
+    pub fn get_unsaved_documents(&self) -> Vec<PathBuf> {
+        Api::get_unsaved_documents(self).unwrap()
+    }
+
     pub fn import_frames<P: Into<PathBuf>>(&self, paths: Vec<P>) {
         self.apply_patch(Api::import_frames(self, paths).unwrap());
     }
 
+    pub async fn import_atlas_regions<P: Into<PathBuf> + Send>(
+        &self,
+        path: P,
+        regions: Vec<AtlasRegion>,
+    ) {
+        self.apply_patch(
+            Api::import_atlas_regions(self, path, regions)
+                .await
+                .unwrap(),
+        );
+    }
+
+    pub async fn import_sprite_strip<P: Into<PathBuf> + Send>(
+        &self,
+        path: P,
+        frame_width: u32,
+        frame_height: u32,
+        count: u32,
+    ) {
+        self.apply_patch(
+            Api::import_sprite_strip(self, path, frame_width, frame_height, count)
+                .await
+                .unwrap(),
+        );
+    }
+
     pub fn new_document<P: Into<PathBuf>>(&self, path: P) {
         self.apply_patch(Api::new_document(self, path).unwrap());
     }
@@ -162,10 +293,50 @@ impl TigerAppMock {
         self.apply_patch(Api::paste(self).unwrap());
     }
 
+    pub fn recover_autosave(&self) {
+        self.apply_patch(Api::recover_autosave(self).unwrap());
+    }
+
+    pub async fn rename_frame_file<P: Into<PathBuf> + Send, Q: Into<PathBuf> + Send>(
+        &self,
+        from: P,
+        to: Q,
+    ) {
+        self.apply_patch(Api::rename_frame_file(self, from, to).await.unwrap());
+    }
+
+    pub fn paste_after_selection(&self) {
+        self.apply_patch(Api::paste_after_selection(self).unwrap());
+    }
+
+    pub fn paste_keyframe_hitboxes(&self) {
+        self.apply_patch(Api::paste_keyframe_hitboxes(self).unwrap());
+    }
+
+    pub fn paste_properties(&self) {
+        self.apply_patch(Api::paste_properties(self).unwrap());
+    }
+
+    pub async fn reopen_last_closed_document(&self) {
+        self.apply_patch(Api::reopen_last_closed_document(self).await.unwrap());
+    }
+
+    pub fn reorder_animations<S: Into<String>>(&self, names: Vec<S>) {
+        self.apply_patch(Api::reorder_animations(self, names).unwrap());
+    }
+
+    pub fn request_exit(&self) {
+        self.apply_patch(Api::request_exit(self).unwrap());
+    }
+
     pub fn reset_timeline_zoom(&self) {
         self.apply_patch(Api::reset_timeline_zoom(self).unwrap());
     }
 
+    pub fn reset_view(&self) {
+        self.apply_patch(Api::reset_view(self).unwrap());
+    }
+
     pub fn reset_workbench_zoom(&self) {
         self.apply_patch(Api::reset_workbench_zoom(self).unwrap());
     }
@@ -192,6 +363,15 @@ impl TigerAppMock {
         self.apply_patch(Api::select_keyframe(self, direction, index, shift, ctrl).unwrap());
     }
 
+    pub fn set_autosave_interval(&self, interval_secs: u64) {
+        self.apply_patch(Api::set_autosave_interval(self, interval_secs).unwrap());
+    }
+
+    pub fn set_export_settings(&self, settings: dto::ExportSettings) -> Result<(), String> {
+        self.apply_patch(Api::set_export_settings(self, settings)?);
+        Ok(())
+    }
+
     pub fn set_export_template_file<P: Into<PathBuf>>(&self, path: P) {
         self.apply_patch(Api::set_export_template_file(self, path).unwrap());
     }
@@ -200,6 +380,10 @@ impl TigerAppMock {
         self.apply_patch(Api::set_hitbox_height(self, height).unwrap());
     }
 
+    pub fn set_hitbox_kind(&self, kind: dto::HitboxKind) {
+        self.apply_patch(Api::set_hitbox_kind(self, kind).unwrap());
+    }
+
     pub fn set_hitbox_position_x(&self, x: i32) {
         self.apply_patch(Api::set_hitbox_position_x(self, x).unwrap());
     }
@@ -212,6 +396,10 @@ impl TigerAppMock {
         self.apply_patch(Api::set_hitbox_width(self, width).unwrap());
     }
 
+    pub fn set_keyframe_colors(&self, colors: dto::KeyframeColors) {
+        self.apply_patch(Api::set_keyframe_colors(self, colors).unwrap());
+    }
+
     pub fn set_keyframe_duration(&self, duration_millis: u64) {
         self.apply_patch(Api::set_keyframe_duration(self, duration_millis).unwrap());
     }
@@ -220,6 +408,14 @@ impl TigerAppMock {
         self.apply_patch(Api::set_keyframe_offset_x(self, x).unwrap());
     }
 
+    pub fn set_restore_session_on_startup(&self, restore: bool) {
+        self.apply_patch(Api::set_restore_session_on_startup(self, restore).unwrap());
+    }
+
+    pub fn set_save_on_focus_loss(&self, save: bool) {
+        self.apply_patch(Api::set_save_on_focus_loss(self, save).unwrap());
+    }
+
     pub fn set_timeline_zoom_amount(&self, amount: f32) {
         self.apply_patch(Api::set_timeline_zoom_amount(self, amount).unwrap());
     }
@@ -232,10 +428,34 @@ impl TigerAppMock {
         self.apply_patch(Api::set_workbench_zoom_factor(self, zoom_factor).unwrap());
     }
 
+    pub fn subdivide_hitbox<S: Into<String>>(&self, name: S, columns: u32, rows: u32) {
+        self.apply_patch(Api::subdivide_hitbox(self, name, columns, rows).unwrap());
+    }
+
+    pub fn tick(&self, delta_time_millis: f64) {
+        self.apply_patch(Api::tick(self, delta_time_millis).unwrap());
+    }
+
+    pub fn toggle_playback_all_documents(&self) {
+        self.apply_patch(Api::toggle_playback_all_documents(self).unwrap());
+    }
+
+    pub fn toggle_precise_durations(&self) {
+        self.apply_patch(Api::toggle_precise_durations(self).unwrap());
+    }
+
     pub fn toggle_preserve_aspect_ratio(&self) {
         self.apply_patch(Api::toggle_preserve_aspect_ratio(self).unwrap());
     }
 
+    pub fn trim_animation_ends(&self) {
+        self.apply_patch(Api::trim_animation_ends(self).unwrap());
+    }
+
+    pub async fn was_export_changed(&self) -> Result<bool, String> {
+        Api::was_export_changed(self).await
+    }
+
     pub fn zoom_in_timeline(&self) {
         self.apply_patch(Api::zoom_in_timeline(self).unwrap());
     }
@@ -278,6 +498,10 @@ impl TigerApp for TigerAppMock {
         self.texture_cache.clone()
     }
 
+    fn thumbnail_cache(&self) -> thumbnail_cache::Handle {
+        self.thumbnail_cache.clone()
+    }
+
     fn paths(&self) -> paths::Handle {
         self.paths.clone()
     }
@@ -311,4 +535,8 @@ impl TigerApp for TigerAppMock {
     fn close_window(&self) {
         *self.closed.lock() = true;
     }
+
+    fn reveal_in_explorer<P: AsRef<Path>>(&self, path: P) {
+        self.revealed_paths.lock().push(path.as_ref().to_owned());
+    }
 }
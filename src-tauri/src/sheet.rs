@@ -5,7 +5,7 @@ use euclid::rect;
 use pathdiff::diff_paths;
 use regex::Regex;
 use serde::{ser::SerializeMap, Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::ops::Range;
@@ -22,6 +22,7 @@ pub(in crate::sheet) mod version1;
 pub(in crate::sheet) mod version2;
 pub(in crate::sheet) mod version3;
 pub(in crate::sheet) mod version4;
+pub(in crate::sheet) mod version5;
 
 #[derive(Serialize, Deserialize, PartialEq, Eq)]
 enum Version {
@@ -29,6 +30,7 @@ enum Version {
     Tiger2,
     Tiger3,
     Tiger4,
+    Tiger5,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -52,8 +54,23 @@ impl<P: AsRef<Path>> From<P> for Relative {
     }
 }
 
-const CURRENT_VERSION: Version = Version::Tiger4;
-pub use self::version4::*;
+const CURRENT_VERSION: Version = Version::Tiger5;
+
+// Duration of newly created keyframes
+pub const DEFAULT_KEYFRAME_DURATION_MILLIS: u64 = 100;
+
+// Name of the file format version used when writing sheets, e.g. `Tiger5`
+pub fn current_version_name() -> &'static str {
+    match CURRENT_VERSION {
+        Version::Tiger1 => "Tiger1",
+        Version::Tiger2 => "Tiger2",
+        Version::Tiger3 => "Tiger3",
+        Version::Tiger4 => "Tiger4",
+        Version::Tiger5 => "Tiger5",
+    }
+}
+
+pub use self::version5::*;
 
 #[derive(Error, Debug)]
 pub enum SheetError {
@@ -71,12 +88,20 @@ pub enum SheetError {
     HitboxNotFound(String),
     #[error("A hitbox with the name `{0}` already exists")]
     HitboxNameAlreadyExists(String),
+    #[error("Could not find an anchor named `{0}`")]
+    AnchorNotFound(String),
+    #[error("An anchor with the name `{0}` already exists")]
+    AnchorNameAlreadyExists(String),
     #[error("Error converting an absolute path to a relative path\nAbsolute path: `{0}`\nRelative path root: `{1}`")]
     AbsoluteToRelativePath(PathBuf, PathBuf),
     #[error("Animation is missing a keyframe at index `{0}`")]
     InvalidFrameIndex(usize),
     #[error("Expected a relative path but got: `{0}`")]
     RelativePathExpected(PathBuf),
+    #[error("Could not find a frame at `{0}`")]
+    FrameNotFound(PathBuf),
+    #[error("Export settings have no output target at index `{0}`")]
+    InvalidExportTargetIndex(usize),
 }
 
 impl From<SheetError> for String {
@@ -100,12 +125,35 @@ impl<P: Paths> Sheet<P> {
         self.animations.iter()
     }
 
+    // Animations with a custom order come first, followed by all others in alphabetical order
     pub fn sorted_animations(&self) -> Vec<(&String, &Animation<P>)> {
         let mut animations = self.animations.iter().collect::<Vec<_>>();
-        animations.sort_by_cached_key(|(n, _)| n.to_lowercase());
+        animations.sort_by_cached_key(|(n, _)| {
+            let position = self.animation_order.iter().position(|o| o == *n);
+            (position.unwrap_or(usize::MAX), n.to_lowercase())
+        });
         animations
     }
 
+    pub fn animation_order(&self) -> &[String] {
+        &self.animation_order
+    }
+
+    pub fn set_animation_order<T: AsRef<str>>(&mut self, names: &[T]) -> Result<(), SheetError> {
+        let mut order: Vec<String> = Vec::new();
+        for name in names {
+            let name = name.as_ref();
+            if !self.has_animation(name) {
+                return Err(SheetError::AnimationNotFound(name.to_owned()));
+            }
+            if !order.iter().any(|n| n == name) {
+                order.push(name.to_owned());
+            }
+        }
+        self.animation_order = order;
+        Ok(())
+    }
+
     pub fn has_frame<T: AsRef<Path>>(&self, path: T) -> bool {
         self.frames.iter().any(|f| f.source == path.as_ref())
     }
@@ -145,6 +193,53 @@ impl<P: Paths> Sheet<P> {
         }
     }
 
+    pub fn replace_frame_source<T: AsRef<Path>, U: AsRef<Path>>(&mut self, old: T, new: U) {
+        let (old, new) = (old.as_ref(), new.as_ref());
+        if self.has_frame(new) {
+            self.frames.retain(|f| f.source != old);
+        } else {
+            for frame in self.frames.iter_mut().filter(|f| f.source == old) {
+                frame.source = new.to_owned();
+            }
+        }
+        for (_, animation) in self.animations.iter_mut() {
+            for (_, sequence) in animation.sequences.iter_mut() {
+                for keyframe in sequence.keyframes.iter_mut() {
+                    if keyframe.frame == old {
+                        keyframe.frame = new.to_owned();
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn relocate_frames_root<T: AsRef<Path>, U: AsRef<Path>>(
+        &mut self,
+        old_root: T,
+        new_root: U,
+    ) {
+        let rebase = |path: &mut PathBuf| {
+            if let Ok(relative) = path.strip_prefix(old_root.as_ref()) {
+                *path = new_root.as_ref().join(relative);
+            }
+        };
+        for frame in self.frames.iter_mut() {
+            rebase(&mut frame.source);
+            if let Some(normal_source) = &mut frame.normal_source {
+                rebase(normal_source);
+            }
+        }
+        let mut sources = HashSet::new();
+        self.frames.retain(|f| sources.insert(f.source.clone()));
+        for (_, animation) in self.animations.iter_mut() {
+            for (_, sequence) in animation.sequences.iter_mut() {
+                for keyframe in sequence.keyframes.iter_mut() {
+                    rebase(&mut keyframe.frame);
+                }
+            }
+        }
+    }
+
     pub fn create_animation<T: AsRef<str>>(
         &mut self,
         proposed_name: T,
@@ -158,6 +253,24 @@ impl<P: Paths> Sheet<P> {
         self.frames.iter().find(|f| f.source == path.as_ref())
     }
 
+    pub fn set_frame_normal_map<T: AsRef<Path>, U: AsRef<Path>>(
+        &mut self,
+        frame: T,
+        normal_map: Option<U>,
+    ) -> Result<(), SheetError> {
+        let frame = self
+            .frames
+            .iter_mut()
+            .find(|f| f.source == frame.as_ref())
+            .ok_or_else(|| SheetError::FrameNotFound(frame.as_ref().to_owned()))?;
+        frame.normal_source = normal_map.map(|p| p.as_ref().to_owned());
+        Ok(())
+    }
+
+    pub fn has_normal_maps(&self) -> bool {
+        self.frames.iter().any(|f| f.normal_source.is_some())
+    }
+
     pub fn animation<T: AsRef<str>>(&self, name: T) -> Option<&Animation<P>> {
         self.animations.get(name.as_ref())
     }
@@ -193,6 +306,32 @@ impl<P: Paths> Sheet<P> {
             .ok_or_else(|| SheetError::AnimationNotFound(old_name.as_ref().to_owned()))?;
         self.animations
             .insert(new_name.as_ref().to_owned(), animation);
+        for name in self.animation_order.iter_mut() {
+            if name == old_name.as_ref() {
+                *name = new_name.as_ref().to_owned();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn swap_animation_names<T: AsRef<str>, U: AsRef<str>>(
+        &mut self,
+        name_a: T,
+        name_b: U,
+    ) -> Result<(), SheetError> {
+        let (name_a, name_b) = (name_a.as_ref(), name_b.as_ref());
+        for name in [name_a, name_b] {
+            if !self.has_animation(name) {
+                return Err(SheetError::AnimationNotFound(name.to_owned()));
+            }
+        }
+        if name_a == name_b {
+            return Ok(());
+        }
+        let animation_a = self.animations.remove(name_a).unwrap();
+        let animation_b = self.animations.remove(name_b).unwrap();
+        self.animations.insert(name_a.to_owned(), animation_b);
+        self.animations.insert(name_b.to_owned(), animation_a);
         Ok(())
     }
 
@@ -207,6 +346,46 @@ impl<P: Paths> Sheet<P> {
 
     pub fn delete_animation<T: AsRef<str>>(&mut self, name: T) {
         self.animations.remove(name.as_ref());
+        self.animation_order.retain(|n| n != name.as_ref());
+    }
+
+    pub fn frame_usages<T: AsRef<Path>>(&self, path: T) -> Vec<(String, Direction, usize)> {
+        let path = path.as_ref();
+        let mut usages = self
+            .animations
+            .iter()
+            .flat_map(|(name, animation)| {
+                animation
+                    .sequences
+                    .iter()
+                    .flat_map(move |(direction, sequence)| {
+                        sequence
+                            .keyframes
+                            .iter()
+                            .enumerate()
+                            .filter(move |(_, k)| k.frame == path)
+                            .map(move |(index, _)| (name.clone(), *direction, index))
+                    })
+            })
+            .collect::<Vec<_>>();
+        usages.sort();
+        usages
+    }
+
+    // Frames referenced by keyframes without being part of the sheet, which can happen when a
+    // sheet file is edited by hand
+    pub fn missing_frames(&self) -> Vec<PathBuf> {
+        let mut missing_frames = self
+            .animations
+            .values()
+            .flat_map(|animation| animation.sequences.values())
+            .flat_map(|sequence| sequence.keyframes.iter())
+            .map(|keyframe| keyframe.frame.clone())
+            .filter(|frame| !self.has_frame(frame))
+            .collect::<Vec<_>>();
+        missing_frames.sort();
+        missing_frames.dedup();
+        missing_frames
     }
 }
 
@@ -223,6 +402,7 @@ impl Sheet<Relative> {
                 .into_iter()
                 .map(|(n, a)| (n, a.with_absolute_paths(&self.paths.base)))
                 .collect(),
+            animation_order: self.animation_order,
             export_settings: self
                 .export_settings
                 .map(|s| s.with_absolute_paths(&self.paths.base)),
@@ -274,6 +454,7 @@ impl Sheet<Any> {
                 .into_iter()
                 .map(|(n, a)| a.with_relative_paths().map(|a| (n, a)))
                 .collect::<Result<_, _>>()?,
+            animation_order: self.animation_order,
             export_settings,
             paths: relative_to.as_ref().resolve().into(),
         })
@@ -321,6 +502,7 @@ impl Sheet<Absolute> {
                 .into_iter()
                 .map(|(n, a)| a.with_relative_paths(&relative_to).map(|a| (n, a)))
                 .collect::<Result<_, _>>()?,
+            animation_order: self.animation_order,
             export_settings,
             paths: relative_to.into(),
         })
@@ -331,6 +513,7 @@ impl<P: Paths> Frame<P> {
     pub fn new<T: AsRef<Path>>(path: T) -> Self {
         Self {
             source: path.as_ref().to_owned(),
+            normal_source: None,
             paths: std::marker::PhantomData,
         }
     }
@@ -338,12 +521,19 @@ impl<P: Paths> Frame<P> {
     pub fn source(&self) -> &Path {
         &self.source
     }
+
+    pub fn normal_source(&self) -> Option<&Path> {
+        self.normal_source.as_deref()
+    }
 }
 
 impl Frame<Relative> {
     pub fn with_absolute_paths<T: AsRef<Path>>(self, relative_to: T) -> Frame<Absolute> {
         Frame {
             source: relative_to.as_ref().join(self.source).resolve(),
+            normal_source: self
+                .normal_source
+                .map(|n| relative_to.as_ref().join(n).resolve()),
             paths: std::marker::PhantomData,
         }
     }
@@ -355,7 +545,11 @@ impl Frame<Absolute> {
         relative_to: T,
     ) -> Result<Frame<Relative>, SheetError> {
         Ok(Frame {
-            source: absolute_to_relative(self.source, relative_to)?,
+            source: absolute_to_relative(self.source, &relative_to)?,
+            normal_source: self
+                .normal_source
+                .map(|n| absolute_to_relative(n, &relative_to))
+                .transpose()?,
             paths: std::marker::PhantomData,
         })
     }
@@ -365,6 +559,7 @@ impl Frame<Any> {
     pub fn with_relative_paths(self) -> Result<Frame<Relative>, SheetError> {
         Ok(Frame {
             source: relative_or_err(self.source)?,
+            normal_source: self.normal_source.map(relative_or_err).transpose()?,
             paths: std::marker::PhantomData,
         })
     }
@@ -389,6 +584,8 @@ impl<P: Paths> Animation<P> {
         Self {
             sequences: Default::default(),
             is_looping: Default::default(),
+            start_delay_millis: Default::default(),
+            origin: Default::default(),
             key: Uuid::new_v4(),
         }
     }
@@ -401,6 +598,8 @@ impl<P: Paths> Animation<P> {
                 .map(|(d, s)| (*d, s.duplicate()))
                 .collect(),
             is_looping: self.is_looping,
+            start_delay_millis: self.start_delay_millis,
+            origin: self.origin,
             key: Uuid::new_v4(),
         }
     }
@@ -417,6 +616,51 @@ impl<P: Paths> Animation<P> {
         self.is_looping = new_is_looping;
     }
 
+    // Empty time before the first keyframe plays. Keyframe timings on `Sequence`
+    // ignore this delay, the functions below include it.
+    pub fn start_delay(&self) -> Duration {
+        Duration::from_millis(self.start_delay_millis)
+    }
+
+    pub fn set_start_delay(&mut self, new_start_delay: Duration) {
+        self.start_delay_millis = new_start_delay.as_millis() as u64;
+    }
+
+    // Pivot point of the animation, relative to the default origin of its keyframes
+    pub fn origin(&self) -> Vector2D<i32> {
+        self.origin.into()
+    }
+
+    pub fn set_origin(&mut self, new_origin: Vector2D<i32>) {
+        self.origin = new_origin.to_tuple();
+    }
+
+    pub fn keyframe_index_at(&self, direction: Direction, time: Duration) -> Option<usize> {
+        let time = time.checked_sub(self.start_delay())?;
+        self.sequence(direction)?.keyframe_index_at(time)
+    }
+
+    pub fn keyframe_time_ranges(&self, direction: Direction) -> Vec<Range<u64>> {
+        self.sequence(direction)
+            .map(|s| s.keyframe_time_ranges())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|r| (r.start + self.start_delay_millis)..(r.end + self.start_delay_millis))
+            .collect()
+    }
+
+    pub fn duration_millis(&self, direction: Direction) -> Option<u64> {
+        self.sequence(direction)?
+            .duration_millis()
+            .map(|d| d + self.start_delay_millis)
+    }
+
+    pub fn duration_micros(&self, direction: Direction) -> Option<u64> {
+        self.sequence(direction)?
+            .duration_micros()
+            .map(|d| d + self.start_delay_millis * 1_000)
+    }
+
     pub fn sequence(&self, direction: Direction) -> Option<&Sequence<P>> {
         self.sequences.get(&direction)
     }
@@ -436,14 +680,49 @@ impl<P: Paths> Animation<P> {
     pub fn direction_preset(&self) -> Option<DirectionPreset> {
         DirectionPreset::from_directions(self.sequences_iter().map(|(d, _s)| *d))
     }
+
+    pub fn bounding_box(&self, frame_sizes: &HashMap<PathBuf, Vector2D<u32>>) -> Option<Rect<i32>> {
+        self.sequences
+            .values()
+            .flat_map(|s| s.keyframes_iter())
+            .filter_map(|k| {
+                let size = frame_sizes.get(k.frame())?;
+                let top_left = k.offset() - (size.to_f32() / 2.0).floor().to_i32();
+                Some(Rect::new(top_left.to_point(), size.to_i32().to_size()))
+            })
+            .reduce(|a, b| a.union(&b))
+    }
 }
 
 impl<P: Paths + Default> Animation<P> {
+    // Sequences which are not part of the new preset are moved to the closest direction
+    // (by angle) which has no keyframes yet. Ties go to the direction listed first in the
+    // `Direction` enum. Sequences are only discarded when every direction is already in use.
     pub fn apply_direction_preset(&mut self, preset: DirectionPreset) {
         let directions = preset.directions();
-        self.sequences.retain(|d, _s| directions.contains(d));
-        for d in directions {
-            self.sequences.entry(d).or_default();
+        let (kept, removed): (BTreeMap<_, _>, BTreeMap<_, _>) = std::mem::take(&mut self.sequences)
+            .into_iter()
+            .partition(|(d, _s)| directions.contains(d));
+        self.sequences = kept;
+        for d in &directions {
+            self.sequences.entry(*d).or_default();
+        }
+        for (direction, sequence) in removed {
+            if sequence.num_keyframes() == 0 {
+                continue;
+            }
+            let target = directions
+                .iter()
+                .copied()
+                .filter(|d| {
+                    self.sequences
+                        .get(d)
+                        .map_or(true, |s| s.num_keyframes() == 0)
+                })
+                .min_by_key(|d| (direction.angular_distance(*d), *d));
+            if let Some(target) = target {
+                self.sequences.insert(target, sequence);
+            }
         }
     }
 }
@@ -457,6 +736,8 @@ impl Animation<Relative> {
                 .map(|(d, s)| (d, s.with_absolute_paths(&relative_to)))
                 .collect(),
             is_looping: self.is_looping,
+            start_delay_millis: self.start_delay_millis,
+            origin: self.origin,
             key: self.key,
         }
     }
@@ -474,6 +755,8 @@ impl Animation<Absolute> {
                 .map(|(d, s)| s.with_relative_paths(&relative_to).map(|s| (d, s)))
                 .collect::<Result<_, _>>()?,
             is_looping: self.is_looping,
+            start_delay_millis: self.start_delay_millis,
+            origin: self.origin,
             key: self.key,
         })
     }
@@ -488,11 +771,31 @@ impl Animation<Any> {
                 .map(|(d, s)| s.with_relative_paths().map(|s| (d, s)))
                 .collect::<Result<_, _>>()?,
             is_looping: self.is_looping,
+            start_delay_millis: self.start_delay_millis,
+            origin: self.origin,
             key: self.key,
         })
     }
 }
 
+impl Direction {
+    // Number of 45 degree steps between two directions
+    fn angular_distance(&self, other: Direction) -> u8 {
+        let steps = (*self as i8 - other as i8).rem_euclid(8) as u8;
+        steps.min(8 - steps)
+    }
+}
+
+impl TextureFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TextureFormat::Png => "png",
+            TextureFormat::WebP => "webp",
+            TextureFormat::Qoi => "qoi",
+        }
+    }
+}
+
 impl DirectionPreset {
     pub fn from_directions<T: Iterator<Item = Direction>>(directions: T) -> Option<Self> {
         let directions_set: HashSet<Direction> = directions.collect();
@@ -561,7 +864,7 @@ impl<P: Paths> Sequence<P> {
         }
         let mut cursor = Duration::new(0, 0);
         for (index, frame) in self.keyframes.iter().enumerate() {
-            cursor += Duration::from_millis(frame.duration_millis);
+            cursor += Duration::from_micros(frame.effective_duration_micros());
             if time < cursor {
                 return Some(index);
             }
@@ -586,13 +889,15 @@ impl<P: Paths> Sequence<P> {
             .collect()
     }
 
+    // Boundaries are rounded down from exact keyframe times, so rounding errors
+    // of sub-millisecond durations do not accumulate along the sequence.
     pub fn keyframe_time_ranges(&self) -> Vec<Range<u64>> {
-        let mut cursor = 0;
+        let mut cursor_micros = 0;
         self.keyframes_iter()
             .map(|f| {
-                let start = cursor;
-                cursor += f.duration_millis();
-                start..cursor
+                let start = cursor_micros / 1_000;
+                cursor_micros += f.effective_duration_micros();
+                start..(cursor_micros / 1_000)
             })
             .collect()
     }
@@ -616,6 +921,51 @@ impl<P: Paths> Sequence<P> {
         Ok(self.keyframes.remove(index))
     }
 
+    pub fn trim_keyframes<F: Fn(&Keyframe<P>) -> bool>(&mut self, should_trim: F) {
+        let start = self
+            .keyframes
+            .iter()
+            .position(|k| !should_trim(k))
+            .unwrap_or(self.keyframes.len());
+        let end = self
+            .keyframes
+            .iter()
+            .rposition(|k| !should_trim(k))
+            .map_or(start, |i| i + 1);
+        self.keyframes.truncate(end);
+        self.keyframes.drain(..start);
+    }
+
+    // Consecutive keyframes showing the same frame at the same offset, with the same hold
+    // and anchors, are combined into one, keeping the hitboxes and events of both.
+    pub fn merge_adjacent_duplicate_keyframes(&mut self) {
+        let mut merged_keyframes: Vec<Keyframe<P>> = Vec::with_capacity(self.keyframes.len());
+        for keyframe in self.keyframes.drain(..) {
+            match merged_keyframes.last_mut() {
+                Some(previous)
+                    if previous.frame == keyframe.frame
+                        && previous.offset == keyframe.offset
+                        && previous.hold == keyframe.hold
+                        && previous.anchors == keyframe.anchors =>
+                {
+                    previous.set_duration_micros(
+                        previous.duration_micros() + keyframe.duration_micros(),
+                    );
+                    for (name, hitbox) in keyframe.hitboxes {
+                        previous.hitboxes.entry(name).or_insert(hitbox);
+                    }
+                    for event in keyframe.events {
+                        if !previous.events.contains(&event) {
+                            previous.events.push(event);
+                        }
+                    }
+                }
+                _ => merged_keyframes.push(keyframe),
+            }
+        }
+        self.keyframes = merged_keyframes;
+    }
+
     pub fn keyframes_iter(&self) -> impl Iterator<Item = &Keyframe<P>> {
         self.keyframes.iter()
     }
@@ -625,14 +975,23 @@ impl<P: Paths> Sequence<P> {
     }
 
     pub fn duration(&self) -> Option<Duration> {
-        self.duration_millis().map(Duration::from_millis)
+        self.duration_micros().map(Duration::from_micros)
     }
 
     pub fn duration_millis(&self) -> Option<u64> {
+        self.duration_micros().map(|d| d / 1_000)
+    }
+
+    pub fn duration_micros(&self) -> Option<u64> {
         if self.keyframes.is_empty() {
             return None;
         }
-        Some(self.keyframes.iter().map(|f| f.duration_millis).sum())
+        Some(
+            self.keyframes
+                .iter()
+                .map(Keyframe::effective_duration_micros)
+                .sum(),
+        )
     }
 }
 
@@ -679,8 +1038,12 @@ impl<P: Paths> Keyframe<P> {
     pub fn new<T: AsRef<Path>>(frame: T) -> Self {
         Self {
             frame: frame.as_ref().to_owned(),
-            duration_millis: 100,
+            duration_millis: DEFAULT_KEYFRAME_DURATION_MILLIS,
+            duration_extra_micros: 0,
             offset: (0, 0),
+            events: Vec::new(),
+            hold: 1,
+            anchors: BTreeMap::new(),
             hitboxes: HashMap::new(),
             key: Uuid::new_v4(),
             paths: std::marker::PhantomData,
@@ -696,7 +1059,11 @@ impl<P: Paths> Keyframe<P> {
                 .map(|(n, h)| (n.clone(), h.duplicate()))
                 .collect(),
             duration_millis: self.duration_millis,
+            duration_extra_micros: self.duration_extra_micros,
             offset: self.offset,
+            events: self.events.clone(),
+            hold: self.hold,
+            anchors: self.anchors.clone(),
             key: Uuid::new_v4(),
             paths: std::marker::PhantomData,
         }
@@ -710,6 +1077,27 @@ impl<P: Paths> Keyframe<P> {
         self.duration_millis
     }
 
+    pub fn duration_micros(&self) -> u64 {
+        self.duration_millis * 1_000 + self.duration_extra_micros as u64
+    }
+
+    // Number of times the keyframe is held at playback and export time, on top of
+    // its base duration.
+    pub fn hold(&self) -> u8 {
+        self.hold
+    }
+
+    pub fn effective_duration_millis(&self) -> u64 {
+        self.effective_duration_micros() / 1_000
+    }
+
+    pub fn effective_duration_micros(&self) -> u64 {
+        self.duration_micros() * self.hold as u64
+    }
+
+    // Position of the frame's center relative to the animation origin, in pixels.
+    // The center of a frame with odd dimensions is rounded down (top-left), which
+    // is the convention used by the workbench, keyframe dragging and exports.
     pub fn offset(&self) -> Vector2D<i32> {
         self.offset.into()
     }
@@ -718,14 +1106,41 @@ impl<P: Paths> Keyframe<P> {
         self.key
     }
 
+    pub fn set_frame<T: AsRef<Path>>(&mut self, new_frame: T) {
+        self.frame = new_frame.as_ref().to_owned();
+    }
+
     pub fn set_duration_millis(&mut self, new_duration: u64) {
         self.duration_millis = new_duration;
+        self.duration_extra_micros = 0;
+    }
+
+    pub fn set_duration_micros(&mut self, new_duration: u64) {
+        self.duration_millis = new_duration / 1_000;
+        self.duration_extra_micros = (new_duration % 1_000) as u16;
+    }
+
+    pub fn set_hold(&mut self, new_hold: u8) {
+        self.hold = new_hold.max(1);
     }
 
     pub fn set_offset(&mut self, new_offset: Vector2D<i32>) {
         self.offset = new_offset.to_tuple();
     }
 
+    pub fn copy_properties_from(&mut self, other: &Keyframe<P>) {
+        self.duration_millis = other.duration_millis;
+        self.duration_extra_micros = other.duration_extra_micros;
+        self.hold = other.hold;
+        self.offset = other.offset;
+        self.hitboxes = other
+            .hitboxes
+            .iter()
+            .map(|(n, h)| (n.clone(), h.duplicate()))
+            .collect();
+        self.anchors = other.anchors.clone();
+    }
+
     pub fn hitboxes_iter(&self) -> impl Iterator<Item = (&String, &Hitbox)> {
         self.hitboxes.iter()
     }
@@ -744,6 +1159,10 @@ impl<P: Paths> Keyframe<P> {
         self.hitboxes.contains_key(name.as_ref())
     }
 
+    pub fn hitbox<T: AsRef<str>>(&self, name: T) -> Option<&Hitbox> {
+        self.hitboxes.get(name.as_ref())
+    }
+
     pub fn create_hitbox<T: AsRef<str>>(&mut self, proposed_name: T) -> (String, &mut Hitbox) {
         let name = generate_unique_name(proposed_name.as_ref(), |n| !self.has_hitbox(n));
         self.hitboxes.insert(name.clone(), Hitbox::new());
@@ -774,6 +1193,88 @@ impl<P: Paths> Keyframe<P> {
     pub fn delete_hitbox<T: AsRef<str>>(&mut self, name: T) {
         self.hitboxes.remove(name.as_ref());
     }
+
+    pub fn delete_all_hitboxes(&mut self) {
+        self.hitboxes.clear();
+    }
+
+    pub fn anchors_iter(&self) -> impl Iterator<Item = (&String, Vector2D<i32>)> {
+        self.anchors.iter().map(|(n, p)| (n, Vector2D::from(*p)))
+    }
+
+    pub fn anchor<T: AsRef<str>>(&self, name: T) -> Option<Vector2D<i32>> {
+        self.anchors.get(name.as_ref()).map(|p| Vector2D::from(*p))
+    }
+
+    pub fn has_anchor<T: AsRef<str>>(&self, name: T) -> bool {
+        self.anchors.contains_key(name.as_ref())
+    }
+
+    pub fn create_anchor<T: AsRef<str>>(
+        &mut self,
+        proposed_name: T,
+        position: Vector2D<i32>,
+    ) -> String {
+        let name = generate_unique_name(proposed_name.as_ref(), |n| !self.has_anchor(n));
+        self.anchors.insert(name.clone(), position.to_tuple());
+        name
+    }
+
+    pub fn set_anchor_position<T: AsRef<str>>(
+        &mut self,
+        name: T,
+        position: Vector2D<i32>,
+    ) -> Result<(), SheetError> {
+        let anchor = self
+            .anchors
+            .get_mut(name.as_ref())
+            .ok_or_else(|| SheetError::AnchorNotFound(name.as_ref().to_owned()))?;
+        *anchor = position.to_tuple();
+        Ok(())
+    }
+
+    pub fn rename_anchor<T: AsRef<str>, U: AsRef<str>>(
+        &mut self,
+        old_name: T,
+        new_name: U,
+    ) -> Result<(), SheetError> {
+        if old_name.as_ref() == new_name.as_ref() {
+            return Ok(());
+        }
+        if self.has_anchor(&new_name) {
+            return Err(SheetError::AnchorNameAlreadyExists(
+                new_name.as_ref().to_owned(),
+            ));
+        }
+        let anchor = self
+            .anchors
+            .remove(old_name.as_ref())
+            .ok_or_else(|| SheetError::AnchorNotFound(old_name.as_ref().to_owned()))?;
+        self.anchors.insert(new_name.as_ref().to_owned(), anchor);
+        Ok(())
+    }
+
+    pub fn delete_anchor<T: AsRef<str>>(&mut self, name: T) {
+        self.anchors.remove(name.as_ref());
+    }
+
+    pub fn events_iter(&self) -> impl Iterator<Item = &String> {
+        self.events.iter()
+    }
+
+    pub fn has_event<T: AsRef<str>>(&self, name: T) -> bool {
+        self.events.iter().any(|e| e == name.as_ref())
+    }
+
+    pub fn add_event<T: AsRef<str>>(&mut self, name: T) {
+        if !self.has_event(&name) {
+            self.events.push(name.as_ref().to_owned());
+        }
+    }
+
+    pub fn remove_event<T: AsRef<str>>(&mut self, name: T) {
+        self.events.retain(|e| e != name.as_ref());
+    }
 }
 
 impl Keyframe<Relative> {
@@ -782,7 +1283,11 @@ impl Keyframe<Relative> {
             frame: relative_to.as_ref().join(&self.frame).resolve(),
             hitboxes: self.hitboxes,
             duration_millis: self.duration_millis,
+            duration_extra_micros: self.duration_extra_micros,
             offset: self.offset,
+            events: self.events,
+            hold: self.hold,
+            anchors: self.anchors,
             key: self.key,
             paths: std::marker::PhantomData,
         }
@@ -798,7 +1303,11 @@ impl Keyframe<Absolute> {
             frame: absolute_to_relative(self.frame, relative_to)?,
             hitboxes: self.hitboxes,
             duration_millis: self.duration_millis,
+            duration_extra_micros: self.duration_extra_micros,
             offset: self.offset,
+            events: self.events,
+            hold: self.hold,
+            anchors: self.anchors,
             key: self.key,
             paths: std::marker::PhantomData,
         })
@@ -811,7 +1320,11 @@ impl Keyframe<Any> {
             frame: relative_or_err(self.frame)?,
             hitboxes: self.hitboxes,
             duration_millis: self.duration_millis,
+            duration_extra_micros: self.duration_extra_micros,
             offset: self.offset,
+            events: self.events,
+            hold: self.hold,
+            anchors: self.anchors,
             key: self.key,
             paths: std::marker::PhantomData,
         })
@@ -825,6 +1338,7 @@ impl Hitbox {
                 top_left: (-10, -10),
                 size: (20, 20),
             }),
+            kind: HitboxKind::default(),
             key: Uuid::new_v4(),
         }
     }
@@ -832,6 +1346,7 @@ impl Hitbox {
     pub fn duplicate(&self) -> Hitbox {
         Hitbox {
             geometry: self.geometry.clone(),
+            kind: self.kind,
             key: Uuid::new_v4(),
         }
     }
@@ -844,6 +1359,9 @@ impl Hitbox {
         }
     }
 
+    // Hitbox coordinates are relative to the sprite origin, like the workbench and the hitbox
+    // details panel display them. They already include the keyframe offset, which is why
+    // nudging a keyframe also moves its hitboxes.
     pub fn position(&self) -> Vector2D<i32> {
         match &self.geometry {
             Shape::Rectangle(r) => r.top_left.into(),
@@ -856,10 +1374,18 @@ impl Hitbox {
         }
     }
 
+    pub fn kind(&self) -> HitboxKind {
+        self.kind
+    }
+
     pub fn key(&self) -> Uuid {
         self.key
     }
 
+    pub fn set_kind(&mut self, kind: HitboxKind) {
+        self.kind = kind;
+    }
+
     pub fn set_position(&mut self, new_position: Vector2D<i32>) {
         match &mut self.geometry {
             Shape::Rectangle(r) => {
@@ -889,6 +1415,29 @@ impl Default for ExportSettings<Any> {
     }
 }
 
+impl<P: Paths> Default for TemplateExportSettings<P> {
+    fn default() -> Self {
+        Self {
+            template_file: Default::default(),
+            texture_file: Default::default(),
+            metadata_file: Default::default(),
+            metadata_paths_root: Default::default(),
+            format: Default::default(),
+            texture_format: Default::default(),
+            texture_scale: 1,
+            packing_algorithm: Default::default(),
+            packing_order: Default::default(),
+            path_style: Default::default(),
+            deduplicate_frames: false,
+            allow_rotation: false,
+            mip_safe_padding: 0,
+            write_manifest: false,
+            additional_targets: Vec::new(),
+            paths: std::marker::PhantomData,
+        }
+    }
+}
+
 impl ExportSettings<Relative> {
     pub fn with_absolute_paths<T: AsRef<Path>>(self, relative_to: T) -> ExportSettings<Absolute> {
         match self {
@@ -947,6 +1496,22 @@ impl<P: Paths> TemplateExportSettings<P> {
         self.texture_file.as_path()
     }
 
+    // Normal maps are written next to the texture file, e.g. `sheet.png` -> `sheet_normal.png`
+    pub fn normal_map_file(&self) -> PathBuf {
+        let stem = self.texture_file.file_stem().unwrap_or_default();
+        let stem = stem.to_string_lossy();
+        let file_name = match self.texture_file.extension() {
+            Some(extension) => format!("{stem}_normal.{}", extension.to_string_lossy()),
+            None => format!("{stem}_normal"),
+        };
+        self.texture_file.with_file_name(file_name)
+    }
+
+    // Manifests are written next to the texture file, e.g. `sheet.png` -> `sheet.manifest.json`
+    pub fn manifest_file(&self) -> PathBuf {
+        self.texture_file.with_extension("manifest.json")
+    }
+
     pub fn metadata_file(&self) -> &Path {
         self.metadata_file.as_path()
     }
@@ -954,6 +1519,62 @@ impl<P: Paths> TemplateExportSettings<P> {
     pub fn metadata_paths_root(&self) -> &Path {
         self.metadata_paths_root.as_path()
     }
+
+    pub fn format(&self) -> ExportFormat {
+        self.format
+    }
+
+    pub fn texture_format(&self) -> TextureFormat {
+        self.texture_format
+    }
+
+    pub fn texture_scale(&self) -> u32 {
+        self.texture_scale
+    }
+
+    pub fn packing_algorithm(&self) -> PackingAlgorithm {
+        self.packing_algorithm
+    }
+
+    pub fn packing_order(&self) -> PackingOrder {
+        self.packing_order
+    }
+
+    pub fn path_style(&self) -> PathStyle {
+        self.path_style
+    }
+
+    pub fn deduplicate_frames(&self) -> bool {
+        self.deduplicate_frames
+    }
+
+    pub fn allow_rotation(&self) -> bool {
+        self.allow_rotation
+    }
+
+    // Gutter between frames in pixels, rounded up to a power of two. Zero disables it.
+    pub fn mip_safe_padding(&self) -> u32 {
+        self.mip_safe_padding
+    }
+
+    pub fn write_manifest(&self) -> bool {
+        self.write_manifest
+    }
+
+    pub fn additional_targets(&self) -> &[ExportTarget<P>] {
+        &self.additional_targets
+    }
+
+    // Template and metadata files of every target, starting with the main one
+    pub fn targets(&self) -> Vec<(&Path, &Path)> {
+        std::iter::once((self.template_file(), self.metadata_file()))
+            .chain(
+                self.additional_targets
+                    .iter()
+                    .map(|t| (t.template_file(), t.metadata_file())),
+            )
+            .collect()
+    }
 }
 
 impl TemplateExportSettings<Absolute> {
@@ -966,6 +1587,21 @@ impl TemplateExportSettings<Absolute> {
             texture_file: absolute_to_relative(self.texture_file, &relative_to)?,
             metadata_file: absolute_to_relative(self.metadata_file, &relative_to)?,
             metadata_paths_root: absolute_to_relative(self.metadata_paths_root, &relative_to)?,
+            format: self.format,
+            texture_format: self.texture_format,
+            texture_scale: self.texture_scale,
+            packing_algorithm: self.packing_algorithm,
+            packing_order: self.packing_order,
+            path_style: self.path_style,
+            deduplicate_frames: self.deduplicate_frames,
+            allow_rotation: self.allow_rotation,
+            mip_safe_padding: self.mip_safe_padding,
+            write_manifest: self.write_manifest,
+            additional_targets: self
+                .additional_targets
+                .into_iter()
+                .map(|t| t.with_relative_paths(&relative_to))
+                .collect::<Result<_, _>>()?,
             paths: std::marker::PhantomData,
         })
     }
@@ -976,6 +1612,21 @@ impl TemplateExportSettings<Absolute> {
             texture_file: self.texture_file,
             metadata_file: self.metadata_file,
             metadata_paths_root: self.metadata_paths_root,
+            format: self.format,
+            texture_format: self.texture_format,
+            texture_scale: self.texture_scale,
+            packing_algorithm: self.packing_algorithm,
+            packing_order: self.packing_order,
+            path_style: self.path_style,
+            deduplicate_frames: self.deduplicate_frames,
+            allow_rotation: self.allow_rotation,
+            mip_safe_padding: self.mip_safe_padding,
+            write_manifest: self.write_manifest,
+            additional_targets: self
+                .additional_targets
+                .into_iter()
+                .map(|t| t.with_any_paths())
+                .collect(),
             paths: std::marker::PhantomData,
         }
     }
@@ -994,6 +1645,21 @@ impl TemplateExportSettings<Relative> {
                 .as_ref()
                 .join(&self.metadata_paths_root)
                 .resolve(),
+            format: self.format,
+            texture_format: self.texture_format,
+            texture_scale: self.texture_scale,
+            packing_algorithm: self.packing_algorithm,
+            packing_order: self.packing_order,
+            path_style: self.path_style,
+            deduplicate_frames: self.deduplicate_frames,
+            allow_rotation: self.allow_rotation,
+            mip_safe_padding: self.mip_safe_padding,
+            write_manifest: self.write_manifest,
+            additional_targets: self
+                .additional_targets
+                .iter()
+                .map(|t| t.with_absolute_paths(&relative_to))
+                .collect(),
             paths: std::marker::PhantomData,
         }
     }
@@ -1016,12 +1682,85 @@ impl TemplateExportSettings<Any> {
         self.metadata_paths_root = path.as_ref().to_owned();
     }
 
+    pub fn set_format(&mut self, format: ExportFormat) {
+        self.format = format;
+    }
+
+    pub fn set_texture_format(&mut self, format: TextureFormat) {
+        self.texture_format = format;
+    }
+
+    pub fn set_texture_scale(&mut self, scale: u32) {
+        self.texture_scale = scale;
+    }
+
+    pub fn set_packing_algorithm(&mut self, algorithm: PackingAlgorithm) {
+        self.packing_algorithm = algorithm;
+    }
+
+    pub fn set_packing_order(&mut self, order: PackingOrder) {
+        self.packing_order = order;
+    }
+
+    pub fn set_path_style(&mut self, style: PathStyle) {
+        self.path_style = style;
+    }
+
+    pub fn set_deduplicate_frames(&mut self, deduplicate: bool) {
+        self.deduplicate_frames = deduplicate;
+    }
+
+    pub fn set_allow_rotation(&mut self, allow_rotation: bool) {
+        self.allow_rotation = allow_rotation;
+    }
+
+    pub fn set_mip_safe_padding(&mut self, padding: u32) {
+        self.mip_safe_padding = padding;
+    }
+
+    pub fn set_write_manifest(&mut self, write_manifest: bool) {
+        self.write_manifest = write_manifest;
+    }
+
+    pub fn add_target(&mut self) {
+        self.additional_targets.push(ExportTarget::default());
+    }
+
+    pub fn remove_target(&mut self, index: usize) -> Result<(), SheetError> {
+        if index >= self.additional_targets.len() {
+            return Err(SheetError::InvalidExportTargetIndex(index));
+        }
+        self.additional_targets.remove(index);
+        Ok(())
+    }
+
+    pub fn target_mut(&mut self, index: usize) -> Result<&mut ExportTarget<Any>, SheetError> {
+        self.additional_targets
+            .get_mut(index)
+            .ok_or(SheetError::InvalidExportTargetIndex(index))
+    }
+
     pub fn with_absolute_paths(self) -> Result<TemplateExportSettings<Absolute>, SheetError> {
         Ok(TemplateExportSettings {
             template_file: absolute_or_err(self.template_file)?,
             texture_file: absolute_or_err(self.texture_file)?,
             metadata_file: absolute_or_err(self.metadata_file)?,
             metadata_paths_root: absolute_or_err(self.metadata_paths_root)?,
+            format: self.format,
+            texture_format: self.texture_format,
+            texture_scale: self.texture_scale,
+            packing_algorithm: self.packing_algorithm,
+            packing_order: self.packing_order,
+            path_style: self.path_style,
+            deduplicate_frames: self.deduplicate_frames,
+            allow_rotation: self.allow_rotation,
+            mip_safe_padding: self.mip_safe_padding,
+            write_manifest: self.write_manifest,
+            additional_targets: self
+                .additional_targets
+                .into_iter()
+                .map(|t| t.with_absolute_paths())
+                .collect::<Result<_, _>>()?,
             paths: std::marker::PhantomData,
         })
     }
@@ -1032,6 +1771,98 @@ impl TemplateExportSettings<Any> {
             texture_file: relative_or_err(self.texture_file)?,
             metadata_file: relative_or_err(self.metadata_file)?,
             metadata_paths_root: relative_or_err(self.metadata_paths_root)?,
+            format: self.format,
+            texture_format: self.texture_format,
+            texture_scale: self.texture_scale,
+            packing_algorithm: self.packing_algorithm,
+            packing_order: self.packing_order,
+            path_style: self.path_style,
+            deduplicate_frames: self.deduplicate_frames,
+            allow_rotation: self.allow_rotation,
+            mip_safe_padding: self.mip_safe_padding,
+            write_manifest: self.write_manifest,
+            additional_targets: self
+                .additional_targets
+                .into_iter()
+                .map(|t| t.with_relative_paths())
+                .collect::<Result<_, _>>()?,
+            paths: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<P: Paths> Default for ExportTarget<P> {
+    fn default() -> Self {
+        Self {
+            template_file: Default::default(),
+            metadata_file: Default::default(),
+            paths: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<P: Paths> ExportTarget<P> {
+    pub fn template_file(&self) -> &Path {
+        self.template_file.as_path()
+    }
+
+    pub fn metadata_file(&self) -> &Path {
+        self.metadata_file.as_path()
+    }
+}
+
+impl ExportTarget<Absolute> {
+    pub fn with_relative_paths<T: AsRef<Path>>(
+        self,
+        relative_to: T,
+    ) -> Result<ExportTarget<Relative>, SheetError> {
+        Ok(ExportTarget {
+            template_file: absolute_to_relative(self.template_file, &relative_to)?,
+            metadata_file: absolute_to_relative(self.metadata_file, &relative_to)?,
+            paths: std::marker::PhantomData,
+        })
+    }
+
+    pub fn with_any_paths(self) -> ExportTarget<Any> {
+        ExportTarget {
+            template_file: self.template_file,
+            metadata_file: self.metadata_file,
+            paths: std::marker::PhantomData,
+        }
+    }
+}
+
+impl ExportTarget<Relative> {
+    pub fn with_absolute_paths<T: AsRef<Path>>(&self, relative_to: T) -> ExportTarget<Absolute> {
+        ExportTarget {
+            template_file: relative_to.as_ref().join(&self.template_file).resolve(),
+            metadata_file: relative_to.as_ref().join(&self.metadata_file).resolve(),
+            paths: std::marker::PhantomData,
+        }
+    }
+}
+
+impl ExportTarget<Any> {
+    pub fn set_template_file<T: AsRef<Path>>(&mut self, path: T) {
+        self.template_file = path.as_ref().to_owned();
+    }
+
+    pub fn set_metadata_file<T: AsRef<Path>>(&mut self, path: T) {
+        self.metadata_file = path.as_ref().to_owned();
+    }
+
+    pub fn with_absolute_paths(self) -> Result<ExportTarget<Absolute>, SheetError> {
+        Ok(ExportTarget {
+            template_file: absolute_or_err(self.template_file)?,
+            metadata_file: absolute_or_err(self.metadata_file)?,
+            paths: std::marker::PhantomData,
+        })
+    }
+
+    pub fn with_relative_paths(self) -> Result<ExportTarget<Relative>, SheetError> {
+        Ok(ExportTarget {
+            template_file: relative_or_err(self.template_file)?,
+            metadata_file: relative_or_err(self.metadata_file)?,
             paths: std::marker::PhantomData,
         })
     }
@@ -1115,6 +1946,16 @@ fn portable_path<S: serde::Serializer>(value: &Path, serializer: S) -> Result<S:
     cleaned.serialize(serializer)
 }
 
+fn portable_optional_path<S: serde::Serializer>(
+    value: &Option<PathBuf>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(path) => portable_path(path, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -1221,6 +2062,171 @@ mod test {
         assert_eq!(original, copy);
     }
 
+    #[test]
+    fn can_upgrade_sheet_from_previous_version() {
+        let sheet = Sheet::<Any>::read("test-data/samurai.tiger").unwrap();
+        let animation = sheet.animation("idle").unwrap();
+        assert_eq!(animation.start_delay(), Duration::ZERO);
+        assert_eq!(animation.origin(), Vector2D::zero());
+        let keyframe = sheet.keyframe("idle", Direction::East, 0);
+        assert_eq!(keyframe.hold(), 1);
+        assert_eq!(keyframe.events_iter().count(), 0);
+        assert_eq!(keyframe.anchors_iter().count(), 0);
+        assert_eq!(
+            sheet.hitbox("idle", Direction::East, 0, "weak").kind(),
+            HitboxKind::Generic
+        );
+    }
+
+    #[test]
+    fn can_read_write_hitbox_kind_from_disk() {
+        let mut original = Sheet::<Any>::read("test-data/samurai.tiger")
+            .unwrap()
+            .with_relative_paths("test-data")
+            .unwrap()
+            .with_absolute_paths();
+        assert_eq!(
+            original.hitbox("idle", Direction::East, 0, "weak").kind(),
+            HitboxKind::Generic
+        );
+        original
+            .hitbox_mut("idle", Direction::East, 0, "weak")
+            .set_kind(HitboxKind::Hurtbox);
+
+        original
+            .clone()
+            .write("test-data/hitbox_kind.tiger")
+            .unwrap();
+        let copy = Sheet::<Any>::read("test-data/hitbox_kind.tiger")
+            .unwrap()
+            .with_relative_paths("test-data")
+            .unwrap()
+            .with_absolute_paths();
+        std::fs::remove_file("test-data/hitbox_kind.tiger").unwrap();
+        assert_eq!(
+            copy.hitbox("idle", Direction::East, 0, "weak").kind(),
+            HitboxKind::Hurtbox
+        );
+        assert_eq!(original, copy);
+    }
+
+    #[test]
+    fn can_read_write_animation_origin_from_disk() {
+        let mut original = Sheet::<Any>::read("test-data/samurai.tiger")
+            .unwrap()
+            .with_relative_paths("test-data")
+            .unwrap()
+            .with_absolute_paths();
+        let animation = original.animation_mut("idle").unwrap();
+        assert_eq!(animation.origin(), Vector2D::zero());
+        animation.set_origin(vec2(-3, 7));
+
+        original
+            .clone()
+            .write("test-data/animation_origin.tiger")
+            .unwrap();
+        let copy = Sheet::<Any>::read("test-data/animation_origin.tiger")
+            .unwrap()
+            .with_relative_paths("test-data")
+            .unwrap()
+            .with_absolute_paths();
+        std::fs::remove_file("test-data/animation_origin.tiger").unwrap();
+        assert_eq!(copy.animation("idle").unwrap().origin(), vec2(-3, 7));
+        assert_eq!(original, copy);
+    }
+
+    #[test]
+    fn can_read_write_precise_keyframe_duration_from_disk() {
+        std::fs::create_dir_all("test-output").unwrap();
+        let mut original = Sheet::<Any>::read("test-data/samurai.tiger")
+            .unwrap()
+            .with_relative_paths("test-data")
+            .unwrap()
+            .with_absolute_paths();
+        original
+            .keyframe_mut("idle", Direction::East, 0)
+            .set_duration_micros(16_667);
+
+        original
+            .clone()
+            .write("test-output/precise_duration.tiger")
+            .unwrap();
+        let copy = Sheet::<Any>::read("test-output/precise_duration.tiger")
+            .unwrap()
+            .with_relative_paths("test-output")
+            .unwrap()
+            .with_absolute_paths();
+        std::fs::remove_file("test-output/precise_duration.tiger").unwrap();
+        let keyframe = copy.keyframe("idle", Direction::East, 0);
+        assert_eq!(keyframe.duration_micros(), 16_667);
+        assert_eq!(keyframe.duration_millis(), 16);
+        assert_eq!(original, copy);
+    }
+
+    #[test]
+    fn precise_keyframe_durations_do_not_drift() {
+        let mut sequence = Sequence::<Any>::default();
+        for index in 0..60 {
+            let mut keyframe = Keyframe::new(Path::new("a.png"));
+            keyframe.set_duration_micros(16_667);
+            sequence.insert_keyframe(keyframe, index).unwrap();
+        }
+        assert_eq!(sequence.duration_micros(), Some(1_000_020));
+        assert_eq!(
+            sequence.keyframe_index_at(Duration::from_micros(16_667 * 30)),
+            Some(30)
+        );
+        assert_eq!(
+            sequence.keyframe_index_at(Duration::from_micros(16_667 * 30 - 1)),
+            Some(29)
+        );
+    }
+
+    #[test]
+    fn can_add_and_remove_keyframe_events() {
+        let mut keyframe = Keyframe::<Any>::new("frame.png");
+        keyframe.add_event("spawn_projectile");
+        keyframe.add_event("spawn_projectile");
+        keyframe.add_event("active");
+        assert_eq!(
+            keyframe.events_iter().collect::<Vec<_>>(),
+            vec!["spawn_projectile", "active"]
+        );
+        keyframe.remove_event("spawn_projectile");
+        assert!(!keyframe.has_event("spawn_projectile"));
+        assert!(keyframe.has_event("active"));
+    }
+
+    #[test]
+    fn can_compute_animation_bounding_box() {
+        let mut sheet = Sheet::<Any>::default();
+        sheet.add_test_animation(
+            "walk",
+            HashMap::from([
+                (Direction::East, vec!["small.png", "large.png"]),
+                (Direction::West, vec!["unknown.png"]),
+            ]),
+        );
+        sheet
+            .keyframe_mut("walk", Direction::East, 0)
+            .set_offset(vec2(10, -5));
+        sheet
+            .keyframe_mut("walk", Direction::East, 1)
+            .set_offset(vec2(-20, 0));
+
+        let frame_sizes = HashMap::from([
+            (PathBuf::from("small.png"), vec2(4, 6)),
+            (PathBuf::from("large.png"), vec2(11, 9)),
+        ]);
+
+        let animation = sheet.animation("walk").unwrap();
+        assert_eq!(
+            animation.bounding_box(&frame_sizes),
+            Some(rect(-25, -8, 37, 13))
+        );
+        assert_eq!(animation.bounding_box(&HashMap::new()), None);
+    }
+
     #[test]
     fn can_add_and_remove_sheet_frame() {
         let mut sheet = Sheet::<Any>::default();
@@ -1257,6 +2263,29 @@ mod test {
         );
     }
 
+    #[test]
+    fn can_find_frame_usages() {
+        let mut sheet = Sheet::<Any>::default();
+        sheet.add_test_animation(
+            "walk",
+            HashMap::from([(Direction::North, vec!["shared.png", "walk.png"])]),
+        );
+        sheet.add_test_animation(
+            "idle",
+            HashMap::from([(Direction::North, vec!["idle.png", "shared.png"])]),
+        );
+        sheet.add_frame("unused.png");
+
+        assert_eq!(
+            sheet.frame_usages("shared.png"),
+            vec![
+                ("idle".to_owned(), Direction::North, 1),
+                ("walk".to_owned(), Direction::North, 0),
+            ]
+        );
+        assert!(sheet.frame_usages("unused.png").is_empty());
+    }
+
     #[test]
     fn cannot_add_duplicate_sheet_frame() {
         let mut sheet = Sheet::<Any>::default();
@@ -1311,6 +2340,56 @@ mod test {
         );
     }
 
+    #[test]
+    fn can_relocate_frames_root() {
+        let mut sheet = Sheet::<Any>::default();
+        sheet.add_frames(&vec![
+            "art/old/a.png",
+            "art/old/sub/b.png",
+            "art/older/c.png",
+        ]);
+        sheet.add_test_animation(
+            "Animation",
+            HashMap::from([(Direction::North, vec!["art/old/a.png", "art/older/c.png"])]),
+        );
+        sheet.relocate_frames_root("art/old", "art/new");
+
+        assert!(sheet.has_frame("art/new/a.png"));
+        assert!(sheet.has_frame("art/new/sub/b.png"));
+        assert!(sheet.has_frame("art/older/c.png"));
+        assert!(!sheet.has_frame("art/old/a.png"));
+        assert!(!sheet.has_frame("art/old/sub/b.png"));
+        assert_eq!(
+            PathBuf::from("art/new/a.png"),
+            sheet.keyframe("Animation", Direction::North, 0).frame
+        );
+        assert_eq!(
+            PathBuf::from("art/older/c.png"),
+            sheet.keyframe("Animation", Direction::North, 1).frame
+        );
+    }
+
+    #[test]
+    fn relocating_frames_root_merges_existing_frames() {
+        let mut sheet = Sheet::<Any>::default();
+        sheet.add_frames(&vec!["art/old/a.png", "art/new/a.png", "art/old/b.png"]);
+        sheet.add_test_animation(
+            "Animation",
+            HashMap::from([(Direction::North, vec!["art/old/a.png", "art/new/a.png"])]),
+        );
+        sheet.relocate_frames_root("art/old", "art/new");
+
+        assert_eq!(sheet.frames_iter().count(), 2);
+        assert!(sheet.has_frame("art/new/a.png"));
+        assert!(sheet.has_frame("art/new/b.png"));
+        for index in 0..2 {
+            assert_eq!(
+                PathBuf::from("art/new/a.png"),
+                sheet.keyframe("Animation", Direction::North, index).frame
+            );
+        }
+    }
+
     #[test]
     fn can_add_and_remove_sheet_animation() {
         let mut sheet = Sheet::<Any>::default();
@@ -1355,6 +2434,34 @@ mod test {
         assert!(sheet.rename_animation(&old_name, "conflict").is_err());
     }
 
+    #[test]
+    fn can_reorder_sheet_animations() {
+        let mut sheet = Sheet::<Any>::default();
+        for name in ["a", "b", "c", "d"] {
+            sheet.create_animation(name);
+        }
+        let names = |sheet: &Sheet<Any>| {
+            sheet
+                .sorted_animations()
+                .into_iter()
+                .map(|(n, _)| n.clone())
+                .collect::<Vec<_>>()
+        };
+
+        sheet.set_animation_order(&["c", "a"]).unwrap();
+        assert_eq!(names(&sheet), vec!["c", "a", "b", "d"]);
+
+        sheet.rename_animation("a", "e").unwrap();
+        assert_eq!(names(&sheet), vec!["c", "e", "b", "d"]);
+
+        sheet.delete_animation("c");
+        assert_eq!(names(&sheet), vec!["e", "b", "d"]);
+        assert_eq!(sheet.animation_order(), &["e".to_owned()]);
+
+        assert!(sheet.set_animation_order(&["b", "missing"]).is_err());
+        assert_eq!(names(&sheet), vec!["e", "b", "d"]);
+    }
+
     #[test]
     fn can_read_write_animation_looping() {
         let mut animation = Animation::<Any>::new();
@@ -1384,6 +2491,61 @@ mod test {
         }
     }
 
+    #[test]
+    fn direction_preset_remaps_removed_sequences() {
+        let mut animation = Animation::<Any>::new();
+        animation.apply_direction_preset(DirectionPreset::FixedAngle);
+        animation
+            .sequence_mut(Direction::North)
+            .unwrap()
+            .insert_keyframe(Keyframe::new("walk_0"), 0)
+            .unwrap();
+
+        animation.apply_direction_preset(DirectionPreset::EightDirections);
+        assert_eq!(
+            animation.direction_preset(),
+            Some(DirectionPreset::EightDirections)
+        );
+        assert_eq!(
+            animation
+                .sequence(Direction::North)
+                .unwrap()
+                .num_keyframes(),
+            1
+        );
+        assert_eq!(
+            animation.sequence(Direction::East).unwrap().num_keyframes(),
+            0
+        );
+
+        animation.apply_direction_preset(DirectionPreset::Isometric);
+        assert_eq!(
+            animation.direction_preset(),
+            Some(DirectionPreset::Isometric)
+        );
+        assert_eq!(
+            animation
+                .sequence(Direction::NorthEast)
+                .unwrap()
+                .num_keyframes(),
+            1
+        );
+
+        animation.apply_direction_preset(DirectionPreset::LeftRight);
+        assert_eq!(
+            animation.direction_preset(),
+            Some(DirectionPreset::LeftRight)
+        );
+        assert_eq!(
+            animation.sequence(Direction::East).unwrap().num_keyframes(),
+            1
+        );
+        assert_eq!(
+            animation.sequence(Direction::West).unwrap().num_keyframes(),
+            0
+        );
+    }
+
     #[test]
     fn animation_can_recognize_direction_preset() {
         let mut animation = Animation::<Any>::new();
@@ -1406,6 +2568,39 @@ mod test {
         );
     }
 
+    #[test]
+    fn does_not_merge_duplicate_keyframes_with_different_holds() {
+        let mut sequence = Sequence::<Any>::default();
+        let keyframe_a = Keyframe::new(Path::new("a.png"));
+        let mut keyframe_b = Keyframe::new(Path::new("a.png"));
+        keyframe_b.set_hold(2);
+        sequence.insert_keyframe(keyframe_a, 0).unwrap();
+        sequence.insert_keyframe(keyframe_b, 1).unwrap();
+
+        sequence.merge_adjacent_duplicate_keyframes();
+        assert_eq!(sequence.num_keyframes(), 2);
+        assert_eq!(sequence.keyframe(1).unwrap().hold(), 2);
+    }
+
+    #[test]
+    fn does_not_merge_duplicate_keyframes_with_different_anchors() {
+        let mut sequence = Sequence::<Any>::default();
+        let mut keyframe_a = Keyframe::new(Path::new("a.png"));
+        let mut keyframe_b = Keyframe::new(Path::new("a.png"));
+        keyframe_a.create_anchor("hand", vec2(1, 2));
+        keyframe_b.create_anchor("hand", vec2(3, 4));
+        sequence.insert_keyframe(keyframe_a.clone(), 0).unwrap();
+        sequence.insert_keyframe(keyframe_b, 1).unwrap();
+        sequence.insert_keyframe(keyframe_a, 2).unwrap();
+
+        sequence.merge_adjacent_duplicate_keyframes();
+        assert_eq!(sequence.num_keyframes(), 3);
+        assert_eq!(
+            sequence.keyframe(1).unwrap().anchor("hand"),
+            Some(vec2(3, 4))
+        );
+    }
+
     #[test]
     fn can_add_and_remove_sequence_keyframe() {
         let mut sequence = Sequence::<Any>::default();
@@ -1462,6 +2657,28 @@ mod test {
         assert_eq!(sequence.duration(), Some(Duration::from_millis(400)));
     }
 
+    #[test]
+    fn keyframe_hold_multiplies_duration() {
+        let mut sequence = Sequence::<Any>::default();
+        let mut keyframe_a = Keyframe::new(Path::new("a.png"));
+        let keyframe_b = Keyframe::new(Path::new("b.png"));
+        keyframe_a.set_hold(2);
+        sequence.insert_keyframe(keyframe_a, 0).unwrap();
+        sequence.insert_keyframe(keyframe_b, 1).unwrap();
+
+        assert_eq!(sequence.keyframe(0).unwrap().duration_millis(), 100);
+        assert_eq!(sequence.duration_millis(), Some(300));
+        assert_eq!(sequence.keyframe_times(), vec![0, 200]);
+        assert_eq!(
+            sequence.keyframe_at(Duration::from_millis(150)).unwrap().0,
+            0
+        );
+        assert_eq!(
+            sequence.keyframe_at(Duration::from_millis(250)).unwrap().0,
+            1
+        );
+    }
+
     #[test]
     fn can_query_sequence_by_time_elapsed() {
         let mut sequence = Sequence::<Any>::default();
@@ -1593,6 +2810,21 @@ mod test {
             texture_file: PathBuf::from("a/b/c/sheet.png").resolve(),
             metadata_file: PathBuf::from("a/b/c/sheet.lua").resolve(),
             metadata_paths_root: PathBuf::from("a/b").resolve(),
+            format: ExportFormat::GodotSpriteFrames,
+            texture_format: TextureFormat::Qoi,
+            texture_scale: 2,
+            packing_algorithm: PackingAlgorithm::Shelf,
+            packing_order: PackingOrder::Sheet,
+            path_style: PathStyle::BasenameOnly,
+            deduplicate_frames: true,
+            allow_rotation: true,
+            mip_safe_padding: 4,
+            write_manifest: true,
+            additional_targets: vec![ExportTarget {
+                template_file: PathBuf::from("a/b/other.template").resolve(),
+                metadata_file: PathBuf::from("a/b/c/sheet.json").resolve(),
+                paths: std::marker::PhantomData,
+            }],
             paths: std::marker::PhantomData,
         }
         .with_absolute_paths()
@@ -1606,6 +2838,14 @@ mod test {
         assert_eq!(&relative.texture_file, Path::new("c/sheet.png"));
         assert_eq!(&relative.metadata_file, Path::new("c/sheet.lua"));
         assert_eq!(&relative.metadata_paths_root, Path::new(""));
+        assert_eq!(
+            relative.additional_targets()[0].template_file(),
+            Path::new("other.template")
+        );
+        assert_eq!(
+            relative.additional_targets()[0].metadata_file(),
+            Path::new("c/sheet.json")
+        );
 
         let roundtrip = relative.with_absolute_paths("a/b");
         assert_eq!(roundtrip, absolute);
@@ -1630,6 +2870,39 @@ mod test {
         let path = Path::new("metadata_paths_root");
         settings.set_metadata_paths_root(path);
         assert_eq!(settings.metadata_paths_root(), path);
+
+        settings.set_format(ExportFormat::GodotSpriteFrames);
+        assert_eq!(settings.format(), ExportFormat::GodotSpriteFrames);
+
+        settings.set_texture_format(TextureFormat::WebP);
+        assert_eq!(settings.texture_format(), TextureFormat::WebP);
+
+        settings.set_texture_scale(3);
+        assert_eq!(settings.texture_scale(), 3);
+
+        settings.set_packing_algorithm(PackingAlgorithm::Shelf);
+        assert_eq!(settings.packing_algorithm(), PackingAlgorithm::Shelf);
+
+        settings.set_packing_order(PackingOrder::Sheet);
+        assert_eq!(settings.packing_order(), PackingOrder::Sheet);
+        settings.set_path_style(PathStyle::BasenameOnly);
+        assert_eq!(settings.path_style(), PathStyle::BasenameOnly);
+        settings.set_deduplicate_frames(true);
+        assert!(settings.deduplicate_frames());
+        settings.set_allow_rotation(true);
+        assert!(settings.allow_rotation());
+        settings.set_mip_safe_padding(8);
+        assert_eq!(settings.mip_safe_padding(), 8);
+        settings.set_write_manifest(true);
+        assert!(settings.write_manifest());
+
+        settings.add_target();
+        let path = Path::new("target_metadata_file");
+        settings.target_mut(0).unwrap().set_metadata_file(path);
+        assert_eq!(settings.targets()[1].1, path);
+        assert!(settings.target_mut(1).is_err());
+        settings.remove_target(0).unwrap();
+        assert!(settings.additional_targets().is_empty());
     }
 
     #[test]
@@ -1,66 +1,38 @@
-use derivative::Derivative;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use std::io::Read;
 use std::path::PathBuf;
-use uuid::Uuid;
 
 use crate::sheet::version3 as previous_version;
-use crate::sheet::{ordered_map, ordered_slice, portable_path, Any, Paths, SheetError, Version};
+use crate::sheet::{SheetError, Version};
 
 const THIS_VERSION: Version = Version::Tiger4;
 
 #[derive(Serialize, Deserialize)]
 struct VersionedSheet {
-    sheet: Sheet<Any>,
+    sheet: Sheet,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
-pub struct Sheet<P: Paths> {
-    #[serde(serialize_with = "ordered_slice")]
-    pub(in crate::sheet) frames: Vec<Frame<P>>,
-    #[serde(serialize_with = "ordered_map")]
-    pub(in crate::sheet) animations: HashMap<String, Animation<P>>,
-    pub(in crate::sheet) export_settings: Option<ExportSettings<P>>,
-    #[serde(skip)]
-    pub(in crate::sheet) paths: P,
+pub struct Sheet {
+    pub(in crate::sheet) frames: Vec<Frame>,
+    pub(in crate::sheet) animations: HashMap<String, Animation>,
+    pub(in crate::sheet) export_settings: Option<ExportSettings>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-pub struct Frame<P: Paths> {
-    #[serde(serialize_with = "portable_path")]
+pub struct Frame {
     pub(in crate::sheet) source: PathBuf,
-    #[serde(skip)]
-    pub(in crate::sheet) paths: std::marker::PhantomData<P>,
 }
 
-#[derive(Derivative)]
-#[derivative(PartialEq)]
-#[derive(Clone, Debug, Eq, Serialize, Deserialize)]
-pub struct Animation<P: Paths> {
-    pub(in crate::sheet) sequences: BTreeMap<Direction, Sequence<P>>,
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Animation {
+    pub(in crate::sheet) sequences: BTreeMap<Direction, Sequence>,
     pub(in crate::sheet) is_looping: bool,
-    #[derivative(PartialEq = "ignore")]
-    #[serde(skip, default = "Uuid::new_v4")]
-    pub(in crate::sheet) key: Uuid,
-}
-
-#[derive(
-    Clone,
-    Copy,
-    Debug,
-    Default,
-    Eq,
-    Hash,
-    PartialEq,
-    PartialOrd,
-    Ord,
-    Serialize,
-    Deserialize,
-    enum_iterator::Sequence,
-)]
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Direction {
-    #[default]
     East,
     NorthEast,
     North,
@@ -71,46 +43,22 @@ pub enum Direction {
     SouthEast,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, enum_iterator::Sequence)]
-pub enum DirectionPreset {
-    FourDirections,
-    EightDirections,
-    LeftRight,
-    UpDown,
-    Isometric,
-    FixedAngle,
-}
-
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
-pub struct Sequence<P: Paths> {
-    pub(in crate::sheet) keyframes: Vec<Keyframe<P>>,
+pub struct Sequence {
+    pub(in crate::sheet) keyframes: Vec<Keyframe>,
 }
 
-#[derive(Derivative)]
-#[derivative(PartialEq)]
-#[derive(Clone, Debug, Eq, Serialize, Deserialize)]
-pub struct Keyframe<P: Paths> {
-    #[serde(serialize_with = "portable_path")]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe {
     pub(in crate::sheet) frame: PathBuf,
-    #[serde(serialize_with = "ordered_map")]
     pub(in crate::sheet) hitboxes: HashMap<String, Hitbox>,
     pub(in crate::sheet) duration_millis: u64,
     pub(in crate::sheet) offset: (i32, i32),
-    #[derivative(PartialEq = "ignore")]
-    #[serde(skip, default = "Uuid::new_v4")]
-    pub(in crate::sheet) key: Uuid,
-    #[serde(skip)]
-    pub(in crate::sheet) paths: std::marker::PhantomData<P>,
 }
 
-#[derive(Derivative)]
-#[derivative(PartialEq)]
-#[derive(Clone, Debug, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Hitbox {
     pub(in crate::sheet) geometry: Shape,
-    #[derivative(PartialEq = "ignore")]
-    #[serde(skip, default = "Uuid::new_v4")]
-    pub(in crate::sheet) key: Uuid,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -119,22 +67,16 @@ pub enum Shape {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
-pub enum ExportSettings<P: Paths> {
-    Template(TemplateExportSettings<P>),
+pub enum ExportSettings {
+    Template(TemplateExportSettings),
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
-pub struct TemplateExportSettings<P: Paths> {
-    #[serde(serialize_with = "portable_path")]
+pub struct TemplateExportSettings {
     pub(in crate::sheet) template_file: PathBuf,
-    #[serde(serialize_with = "portable_path")]
     pub(in crate::sheet) texture_file: PathBuf,
-    #[serde(serialize_with = "portable_path")]
     pub(in crate::sheet) metadata_file: PathBuf,
-    #[serde(serialize_with = "portable_path")]
     pub(in crate::sheet) metadata_paths_root: PathBuf,
-    #[serde(skip)]
-    pub(in crate::sheet) paths: std::marker::PhantomData<P>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -143,7 +85,7 @@ pub struct Rectangle {
     pub(in crate::sheet) size: (u32, u32),
 }
 
-pub(super) fn read_file<R: Read>(version: Version, reader: R) -> Result<Sheet<Any>, SheetError> {
+pub(super) fn read_file<R: Read>(version: Version, reader: R) -> Result<Sheet, SheetError> {
     match version {
         THIS_VERSION => {
             let deserialized: VersionedSheet = serde_json::from_reader(reader)?;
@@ -153,8 +95,8 @@ pub(super) fn read_file<R: Read>(version: Version, reader: R) -> Result<Sheet<An
     }
 }
 
-impl From<previous_version::Sheet> for Sheet<Any> {
-    fn from(old: previous_version::Sheet) -> Sheet<Any> {
+impl From<previous_version::Sheet> for Sheet {
+    fn from(old: previous_version::Sheet) -> Sheet {
         Sheet {
             frames: old.frames.into_iter().map(|o| o.into()).collect(),
             animations: old
@@ -163,40 +105,35 @@ impl From<previous_version::Sheet> for Sheet<Any> {
                 .map(|o| (o.name.to_owned(), o.into()))
                 .collect(),
             export_settings: old.export_settings.map(|o| o.into()),
-            paths: Default::default(),
         }
     }
 }
 
-impl From<previous_version::Animation> for Animation<Any> {
-    fn from(old: previous_version::Animation) -> Animation<Any> {
+impl From<previous_version::Animation> for Animation {
+    fn from(old: previous_version::Animation) -> Animation {
         Self {
             sequences: BTreeMap::from([(Direction::East, old.timeline.into())]),
             is_looping: old.is_looping,
-            key: Uuid::new_v4(),
         }
     }
 }
 
-impl From<Vec<previous_version::Keyframe>> for Sequence<Any> {
-    fn from(keyframes: Vec<previous_version::Keyframe>) -> Sequence<Any> {
+impl From<Vec<previous_version::Keyframe>> for Sequence {
+    fn from(keyframes: Vec<previous_version::Keyframe>) -> Sequence {
         Self {
             keyframes: keyframes.into_iter().map(|k| k.into()).collect(),
         }
     }
 }
 
-impl From<previous_version::Frame> for Frame<Any> {
+impl From<previous_version::Frame> for Frame {
     fn from(old: previous_version::Frame) -> Self {
-        Self {
-            source: old.source,
-            paths: std::marker::PhantomData,
-        }
+        Self { source: old.source }
     }
 }
 
-impl From<previous_version::Keyframe> for Keyframe<Any> {
-    fn from(old: previous_version::Keyframe) -> Keyframe<Any> {
+impl From<previous_version::Keyframe> for Keyframe {
+    fn from(old: previous_version::Keyframe) -> Keyframe {
         Self {
             frame: old.frame,
             duration_millis: old.duration_millis as u64,
@@ -206,8 +143,6 @@ impl From<previous_version::Keyframe> for Keyframe<Any> {
                 .into_iter()
                 .map(|o| (o.name.to_owned(), o.into()))
                 .collect(),
-            key: Uuid::new_v4(),
-            paths: std::marker::PhantomData,
         }
     }
 }
@@ -216,7 +151,6 @@ impl From<previous_version::Hitbox> for Hitbox {
     fn from(old: previous_version::Hitbox) -> Hitbox {
         Hitbox {
             geometry: old.geometry.into(),
-            key: Uuid::new_v4(),
         }
     }
 }
@@ -238,8 +172,8 @@ impl From<previous_version::Rectangle> for Rectangle {
     }
 }
 
-impl From<previous_version::ExportSettings> for ExportSettings<Any> {
-    fn from(old: previous_version::ExportSettings) -> ExportSettings<Any> {
+impl From<previous_version::ExportSettings> for ExportSettings {
+    fn from(old: previous_version::ExportSettings) -> ExportSettings {
         ExportSettings::Template(TemplateExportSettings {
             template_file: match old.format {
                 previous_version::ExportFormat::Template(p) => p,
@@ -247,7 +181,6 @@ impl From<previous_version::ExportSettings> for ExportSettings<Any> {
             texture_file: old.texture_destination,
             metadata_file: old.metadata_destination.clone(),
             metadata_paths_root: old.metadata_destination,
-            paths: std::marker::PhantomData,
         })
     }
 }
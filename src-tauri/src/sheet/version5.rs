@@ -0,0 +1,376 @@
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use crate::sheet::version4 as previous_version;
+use crate::sheet::{
+    ordered_map, ordered_slice, portable_optional_path, portable_path, Any, Paths, SheetError,
+    Version,
+};
+
+const THIS_VERSION: Version = Version::Tiger5;
+
+#[derive(Serialize, Deserialize)]
+struct VersionedSheet {
+    sheet: Sheet<Any>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Sheet<P: Paths> {
+    #[serde(serialize_with = "ordered_slice")]
+    pub(in crate::sheet) frames: Vec<Frame<P>>,
+    #[serde(serialize_with = "ordered_map")]
+    pub(in crate::sheet) animations: HashMap<String, Animation<P>>,
+    pub(in crate::sheet) animation_order: Vec<String>,
+    pub(in crate::sheet) export_settings: Option<ExportSettings<P>>,
+    #[serde(skip)]
+    pub(in crate::sheet) paths: P,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Frame<P: Paths> {
+    #[serde(serialize_with = "portable_path")]
+    pub(in crate::sheet) source: PathBuf,
+    #[serde(serialize_with = "portable_optional_path")]
+    pub(in crate::sheet) normal_source: Option<PathBuf>,
+    #[serde(skip)]
+    pub(in crate::sheet) paths: std::marker::PhantomData<P>,
+}
+
+#[derive(Derivative)]
+#[derivative(PartialEq)]
+#[derive(Clone, Debug, Eq, Serialize, Deserialize)]
+pub struct Animation<P: Paths> {
+    pub(in crate::sheet) sequences: BTreeMap<Direction, Sequence<P>>,
+    pub(in crate::sheet) is_looping: bool,
+    pub(in crate::sheet) start_delay_millis: u64,
+    pub(in crate::sheet) origin: (i32, i32),
+    #[derivative(PartialEq = "ignore")]
+    #[serde(skip, default = "Uuid::new_v4")]
+    pub(in crate::sheet) key: Uuid,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    enum_iterator::Sequence,
+)]
+pub enum Direction {
+    #[default]
+    East,
+    NorthEast,
+    North,
+    NorthWest,
+    West,
+    SouthWest,
+    South,
+    SouthEast,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, enum_iterator::Sequence)]
+pub enum DirectionPreset {
+    FourDirections,
+    EightDirections,
+    LeftRight,
+    UpDown,
+    Isometric,
+    FixedAngle,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Sequence<P: Paths> {
+    pub(in crate::sheet) keyframes: Vec<Keyframe<P>>,
+}
+
+#[derive(Derivative)]
+#[derivative(PartialEq)]
+#[derive(Clone, Debug, Eq, Serialize, Deserialize)]
+pub struct Keyframe<P: Paths> {
+    #[serde(serialize_with = "portable_path")]
+    pub(in crate::sheet) frame: PathBuf,
+    #[serde(serialize_with = "ordered_map")]
+    pub(in crate::sheet) hitboxes: HashMap<String, Hitbox>,
+    pub(in crate::sheet) duration_millis: u64,
+    // Sub-millisecond part of the duration
+    pub(in crate::sheet) duration_extra_micros: u16,
+    pub(in crate::sheet) offset: (i32, i32),
+    pub(in crate::sheet) events: Vec<String>,
+    pub(in crate::sheet) hold: u8,
+    pub(in crate::sheet) anchors: BTreeMap<String, (i32, i32)>,
+    #[derivative(PartialEq = "ignore")]
+    #[serde(skip, default = "Uuid::new_v4")]
+    pub(in crate::sheet) key: Uuid,
+    #[serde(skip)]
+    pub(in crate::sheet) paths: std::marker::PhantomData<P>,
+}
+
+#[derive(Derivative)]
+#[derivative(PartialEq)]
+#[derive(Clone, Debug, Eq, Serialize, Deserialize)]
+pub struct Hitbox {
+    pub(in crate::sheet) geometry: Shape,
+    pub(in crate::sheet) kind: HitboxKind,
+    #[derivative(PartialEq = "ignore")]
+    #[serde(skip, default = "Uuid::new_v4")]
+    pub(in crate::sheet) key: Uuid,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum HitboxKind {
+    #[default]
+    Generic,
+    Hurtbox,
+    Attack,
+    Grab,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Shape {
+    Rectangle(Rectangle),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ExportSettings<P: Paths> {
+    Template(TemplateExportSettings<P>),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TemplateExportSettings<P: Paths> {
+    #[serde(serialize_with = "portable_path")]
+    pub(in crate::sheet) template_file: PathBuf,
+    #[serde(serialize_with = "portable_path")]
+    pub(in crate::sheet) texture_file: PathBuf,
+    #[serde(serialize_with = "portable_path")]
+    pub(in crate::sheet) metadata_file: PathBuf,
+    #[serde(serialize_with = "portable_path")]
+    pub(in crate::sheet) metadata_paths_root: PathBuf,
+    pub(in crate::sheet) format: ExportFormat,
+    pub(in crate::sheet) texture_format: TextureFormat,
+    pub(in crate::sheet) texture_scale: u32,
+    pub(in crate::sheet) packing_algorithm: PackingAlgorithm,
+    pub(in crate::sheet) packing_order: PackingOrder,
+    pub(in crate::sheet) path_style: PathStyle,
+    pub(in crate::sheet) deduplicate_frames: bool,
+    pub(in crate::sheet) allow_rotation: bool,
+    pub(in crate::sheet) mip_safe_padding: u32,
+    pub(in crate::sheet) write_manifest: bool,
+    pub(in crate::sheet) additional_targets: Vec<ExportTarget<P>>,
+    #[serde(skip)]
+    pub(in crate::sheet) paths: std::marker::PhantomData<P>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExportTarget<P: Paths> {
+    #[serde(serialize_with = "portable_path")]
+    pub(in crate::sheet) template_file: PathBuf,
+    #[serde(serialize_with = "portable_path")]
+    pub(in crate::sheet) metadata_file: PathBuf,
+    #[serde(skip)]
+    pub(in crate::sheet) paths: std::marker::PhantomData<P>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    #[default]
+    Template,
+    GodotSpriteFrames,
+    Css,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TextureFormat {
+    #[default]
+    Png,
+    WebP,
+    Qoi,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PackingAlgorithm {
+    #[default]
+    MaxRects,
+    Shelf,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PackingOrder {
+    #[default]
+    Sorted,
+    Sheet,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PathStyle {
+    AbsolutePath,
+    #[default]
+    RelativeToRoot,
+    BasenameOnly,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Rectangle {
+    pub(in crate::sheet) top_left: (i32, i32),
+    pub(in crate::sheet) size: (u32, u32),
+}
+
+pub(super) fn read_file<R: Read>(version: Version, reader: R) -> Result<Sheet<Any>, SheetError> {
+    match version {
+        THIS_VERSION => {
+            let deserialized: VersionedSheet = serde_json::from_reader(reader)?;
+            Ok(deserialized.sheet)
+        }
+        _ => Ok(previous_version::read_file(version, reader)?.into()),
+    }
+}
+
+impl From<previous_version::Sheet> for Sheet<Any> {
+    fn from(old: previous_version::Sheet) -> Sheet<Any> {
+        Sheet {
+            frames: old.frames.into_iter().map(|o| o.into()).collect(),
+            animations: old
+                .animations
+                .into_iter()
+                .map(|(name, animation)| (name, animation.into()))
+                .collect(),
+            animation_order: Vec::new(),
+            export_settings: old.export_settings.map(|o| o.into()),
+            paths: Default::default(),
+        }
+    }
+}
+
+impl From<previous_version::Animation> for Animation<Any> {
+    fn from(old: previous_version::Animation) -> Animation<Any> {
+        Self {
+            sequences: old
+                .sequences
+                .into_iter()
+                .map(|(direction, sequence)| (direction.into(), sequence.into()))
+                .collect(),
+            is_looping: old.is_looping,
+            start_delay_millis: 0,
+            origin: (0, 0),
+            key: Uuid::new_v4(),
+        }
+    }
+}
+
+impl From<previous_version::Direction> for Direction {
+    fn from(old: previous_version::Direction) -> Direction {
+        match old {
+            previous_version::Direction::East => Direction::East,
+            previous_version::Direction::NorthEast => Direction::NorthEast,
+            previous_version::Direction::North => Direction::North,
+            previous_version::Direction::NorthWest => Direction::NorthWest,
+            previous_version::Direction::West => Direction::West,
+            previous_version::Direction::SouthWest => Direction::SouthWest,
+            previous_version::Direction::South => Direction::South,
+            previous_version::Direction::SouthEast => Direction::SouthEast,
+        }
+    }
+}
+
+impl From<previous_version::Sequence> for Sequence<Any> {
+    fn from(old: previous_version::Sequence) -> Sequence<Any> {
+        Self {
+            keyframes: old.keyframes.into_iter().map(|k| k.into()).collect(),
+        }
+    }
+}
+
+impl From<previous_version::Frame> for Frame<Any> {
+    fn from(old: previous_version::Frame) -> Self {
+        Self {
+            source: old.source,
+            normal_source: None,
+            paths: std::marker::PhantomData,
+        }
+    }
+}
+
+impl From<previous_version::Keyframe> for Keyframe<Any> {
+    fn from(old: previous_version::Keyframe) -> Keyframe<Any> {
+        Self {
+            frame: old.frame,
+            duration_millis: old.duration_millis,
+            duration_extra_micros: 0,
+            offset: old.offset,
+            events: Vec::new(),
+            hold: 1,
+            anchors: BTreeMap::new(),
+            hitboxes: old
+                .hitboxes
+                .into_iter()
+                .map(|(name, hitbox)| (name, hitbox.into()))
+                .collect(),
+            key: Uuid::new_v4(),
+            paths: std::marker::PhantomData,
+        }
+    }
+}
+
+impl From<previous_version::Hitbox> for Hitbox {
+    fn from(old: previous_version::Hitbox) -> Hitbox {
+        Hitbox {
+            geometry: old.geometry.into(),
+            kind: HitboxKind::default(),
+            key: Uuid::new_v4(),
+        }
+    }
+}
+
+impl From<previous_version::Shape> for Shape {
+    fn from(old: previous_version::Shape) -> Shape {
+        match old {
+            previous_version::Shape::Rectangle(r) => Shape::Rectangle(r.into()),
+        }
+    }
+}
+
+impl From<previous_version::Rectangle> for Rectangle {
+    fn from(old: previous_version::Rectangle) -> Rectangle {
+        Rectangle {
+            top_left: old.top_left,
+            size: old.size,
+        }
+    }
+}
+
+impl From<previous_version::ExportSettings> for ExportSettings<Any> {
+    fn from(old: previous_version::ExportSettings) -> ExportSettings<Any> {
+        match old {
+            previous_version::ExportSettings::Template(settings) => {
+                ExportSettings::Template(TemplateExportSettings {
+                    template_file: settings.template_file,
+                    texture_file: settings.texture_file,
+                    metadata_file: settings.metadata_file,
+                    metadata_paths_root: settings.metadata_paths_root,
+                    format: ExportFormat::default(),
+                    texture_format: TextureFormat::default(),
+                    texture_scale: 1,
+                    packing_algorithm: PackingAlgorithm::default(),
+                    packing_order: PackingOrder::default(),
+                    path_style: PathStyle::default(),
+                    deduplicate_frames: false,
+                    allow_rotation: false,
+                    mip_safe_padding: 0,
+                    write_manifest: false,
+                    additional_targets: Vec::new(),
+                    paths: std::marker::PhantomData,
+                })
+            }
+        }
+    }
+}
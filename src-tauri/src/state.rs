@@ -1,9 +1,12 @@
+use serde::{Deserialize, Serialize};
 use squeak::{Delegate, Observable};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use sugar_path::SugarPath;
 use thiserror::Error;
 
 use crate::document::{ClipboardManifest, Document, DocumentError};
+use crate::theme::KeyframeColors;
 use crate::utils::handle;
 
 #[derive(Error, Debug)]
@@ -21,9 +24,42 @@ pub struct State {
     documents: Vec<Document>,
     current_document: Option<PathBuf>,
     recent_documents: Observable<'static, Vec<PathBuf>>,
+    recently_closed_documents: Vec<PathBuf>,
+    session: Observable<'static, Session>,
     clipboard_manifest: Option<ClipboardManifest>,
     errors: Vec<UserFacingError>,
     exit_requested: bool,
+    documents_losing_focus: Vec<PathBuf>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Session {
+    pub restore_on_startup: bool,
+    #[serde(default)]
+    pub save_on_focus_loss: bool,
+    #[serde(default)]
+    pub keyframe_colors: KeyframeColors,
+    #[serde(default = "default_autosave_interval_secs")]
+    pub autosave_interval_secs: u64,
+    pub documents: Vec<PathBuf>,
+    pub current_document: Option<PathBuf>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            restore_on_startup: false,
+            save_on_focus_loss: false,
+            keyframe_colors: KeyframeColors::default(),
+            autosave_interval_secs: default_autosave_interval_secs(),
+            documents: Vec::new(),
+            current_document: None,
+        }
+    }
+}
+
+fn default_autosave_interval_secs() -> u64 {
+    60
 }
 
 #[derive(Debug)]
@@ -67,10 +103,24 @@ impl State {
     pub fn focus_document<T: AsRef<Path>>(&mut self, path: T) -> Result<(), AppError> {
         self.document(&path)
             .ok_or_else(|| AppError::DocumentNotFound(path.as_ref().to_owned()))?;
+        if self.current_document.as_deref() != Some(path.as_ref()) {
+            let unsaved_document = self.current_document_mut().and_then(|document| {
+                document.cancel_transient();
+                (!document.is_saved()).then(|| document.path().to_owned())
+            });
+            if self.session.save_on_focus_loss {
+                self.documents_losing_focus.extend(unsaved_document);
+            }
+        }
         self.current_document = Some(path.as_ref().to_owned());
         Ok(())
     }
 
+    // Documents which were unsaved when focus moved away from them, to be saved in the background
+    pub fn take_documents_losing_focus(&mut self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.documents_losing_focus)
+    }
+
     pub fn current_document(&self) -> Option<&Document> {
         match &self.current_document {
             None => None,
@@ -114,7 +164,8 @@ impl State {
             .iter()
             .position(|d| d.path() == path.as_ref())
         {
-            self.documents.remove(index);
+            let document = self.documents.remove(index);
+            self.add_recently_closed_document(document.path());
             self.current_document = if self.documents.is_empty() {
                 None
             } else {
@@ -142,6 +193,7 @@ impl State {
 
     pub fn request_exit(&mut self) {
         self.exit_requested = true;
+        self.record_session();
         for document in &mut self.documents {
             document.request_close();
         }
@@ -186,6 +238,18 @@ impl State {
         });
     }
 
+    fn add_recently_closed_document<T: AsRef<Path>>(&mut self, path: T) {
+        let path = path.as_ref().to_owned();
+        self.recently_closed_documents.retain(|p| *p != path);
+        self.recently_closed_documents.insert(0, path);
+        self.recently_closed_documents.truncate(10);
+    }
+
+    pub fn take_last_closed_document(&mut self) -> Option<PathBuf> {
+        (!self.recently_closed_documents.is_empty())
+            .then(|| self.recently_closed_documents.remove(0))
+    }
+
     pub fn recent_documents(&self) -> impl Iterator<Item = &Path> {
         self.recent_documents.iter().map(|d| d.as_path())
     }
@@ -200,6 +264,61 @@ impl State {
         self.recent_documents.delegate()
     }
 
+    fn record_session(&mut self) {
+        let documents = self
+            .documents
+            .iter()
+            .map(|d| d.path().resolve())
+            .collect::<Vec<_>>();
+        let current_document = self.current_document.as_ref().map(|p| p.resolve());
+        self.session.mutate(|s| {
+            s.documents = documents;
+            s.current_document = current_document;
+        });
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    pub fn set_session(&mut self, session: Session) {
+        self.session.mutate(|s| {
+            *s = session;
+        });
+    }
+
+    pub fn set_restore_session_on_startup(&mut self, restore: bool) {
+        self.session.mutate(|s| {
+            s.restore_on_startup = restore;
+        });
+    }
+
+    pub fn set_save_on_focus_loss(&mut self, save: bool) {
+        self.session.mutate(|s| {
+            s.save_on_focus_loss = save;
+        });
+    }
+
+    pub fn set_autosave_interval(&mut self, interval: Duration) {
+        self.session.mutate(|s| {
+            s.autosave_interval_secs = interval.as_secs().max(1);
+        });
+    }
+
+    pub fn autosave_interval(&self) -> Duration {
+        Duration::from_secs(self.session.autosave_interval_secs)
+    }
+
+    pub fn set_keyframe_colors(&mut self, colors: KeyframeColors) {
+        self.session.mutate(|s| {
+            s.keyframe_colors = colors;
+        });
+    }
+
+    pub fn session_delegate(&self) -> &Delegate<'static, Session> {
+        self.session.delegate()
+    }
+
     pub fn set_clipboard_manifest(&mut self, new_manifest: Option<ClipboardManifest>) {
         self.clipboard_manifest = new_manifest;
     }
@@ -212,10 +331,15 @@ impl State {
 #[cfg(test)]
 mod test {
 
+    use euclid::vec2;
     use std::path::PathBuf;
+    use std::time::Duration;
 
     use super::*;
+    use crate::document::Command;
+    use crate::dto::StateTrim;
     use crate::mock::TigerAppMock;
+    use crate::TigerApp;
 
     #[tokio::test]
     async fn can_open_and_close_documents() {
@@ -235,6 +359,40 @@ mod test {
         assert_eq!(app.client_state().documents[0].name, "samurai.tiger");
     }
 
+    #[tokio::test]
+    async fn can_reopen_last_closed_document() {
+        let app = TigerAppMock::new();
+        app.open_documents(vec!["test-data/samurai.tiger", "test-data/flame.tiger"])
+            .await;
+
+        app.close_document("test-data/flame.tiger");
+        assert_eq!(app.client_state().documents.len(), 1);
+
+        app.reopen_last_closed_document().await;
+        assert_eq!(app.client_state().documents.len(), 2);
+        assert_eq!(app.client_state().documents[1].name, "flame.tiger");
+        assert_eq!(
+            app.client_state().current_document_path,
+            Some("test-data/flame.tiger".into())
+        );
+
+        app.reopen_last_closed_document().await;
+        assert_eq!(app.client_state().documents.len(), 2);
+    }
+
+    #[test]
+    fn limits_list_of_recently_closed_documents() {
+        let mut state = State::default();
+        for i in 0..20 {
+            state.add_recently_closed_document(format!("doc_{i}"));
+        }
+        assert_eq!(state.recently_closed_documents.len(), 10);
+        assert_eq!(
+            state.take_last_closed_document(),
+            Some(PathBuf::from("doc_19"))
+        );
+    }
+
     #[tokio::test]
     async fn open_and_close_updates_focused_document() {
         let app = TigerAppMock::new();
@@ -270,6 +428,107 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn keeps_track_of_unsaved_documents() {
+        let app = TigerAppMock::new();
+        app.open_documents(vec!["test-data/samurai.tiger", "test-data/flame.tiger"])
+            .await;
+        assert!(!app.client_state().documents[0].has_unsaved_changes);
+        assert!(app.get_unsaved_documents().is_empty());
+
+        app.focus_document("test-data/samurai.tiger");
+        app.create_animation();
+        assert!(app.client_state().documents[0].has_unsaved_changes);
+        assert!(!app.client_state().documents[1].has_unsaved_changes);
+        assert_eq!(
+            app.get_unsaved_documents(),
+            vec![PathBuf::from("test-data/samurai.tiger")]
+        );
+
+        app.patch_state(StateTrim::Full, |state| {
+            let document = state.current_document_mut().unwrap();
+            document.mark_as_saved(document.version());
+        });
+        assert!(!app.client_state().documents[0].has_unsaved_changes);
+        assert!(app.get_unsaved_documents().is_empty());
+    }
+
+    #[tokio::test]
+    async fn global_playback_advances_all_documents() {
+        let app = TigerAppMock::new();
+        app.open_documents(vec!["test-data/samurai.tiger", "test-data/flame.tiger"])
+            .await;
+        app.focus_document("test-data/samurai.tiger");
+        app.edit_animation("idle");
+        app.focus_document("test-data/flame.tiger");
+        app.edit_animation("idle");
+
+        app.toggle_playback_all_documents();
+        assert!(app
+            .client_state()
+            .documents
+            .iter()
+            .all(|d| d.timeline_is_playing));
+
+        app.tick(10.0);
+        {
+            let state_handle = app.state();
+            let state = state_handle.lock();
+            assert!(state
+                .documents_iter()
+                .all(|d| d.timeline_clock() == Duration::from_millis(10)));
+        }
+
+        app.toggle_playback_all_documents();
+        assert!(app
+            .client_state()
+            .documents
+            .iter()
+            .all(|d| !d.timeline_is_playing));
+    }
+
+    #[test]
+    fn focusing_another_document_reverts_in_progress_drags() {
+        let mut state = State::default();
+        state.open_document(Document::open("test-data/samurai.tiger").unwrap());
+        state.open_document(Document::open("test-data/flame.tiger").unwrap());
+        state.focus_document("test-data/samurai.tiger").unwrap();
+
+        let hitbox_position = |state: &State| {
+            let document = state.current_document().unwrap();
+            let (_, keyframe) = document.workbench_keyframe().unwrap();
+            keyframe
+                .hitboxes_iter()
+                .find(|(name, _)| name.as_str() == "weak")
+                .map(|(_, hitbox)| hitbox.position())
+                .unwrap()
+        };
+
+        let document = state.current_document_mut().unwrap();
+        document
+            .process_command(Command::EditAnimation("idle".to_owned()))
+            .unwrap();
+        let initial_position = hitbox_position(&state);
+
+        let document = state.current_document_mut().unwrap();
+        document
+            .process_command(Command::BeginNudgeHitbox("weak".to_owned()))
+            .unwrap();
+        document
+            .process_command(Command::UpdateNudgeHitbox(vec2(50, 100), true))
+            .unwrap();
+        assert_ne!(hitbox_position(&state), initial_position);
+
+        state.focus_document("test-data/flame.tiger").unwrap();
+        state.focus_document("test-data/samurai.tiger").unwrap();
+        assert_eq!(hitbox_position(&state), initial_position);
+        assert!(state
+            .current_document()
+            .unwrap()
+            .hitboxes_being_nudged()
+            .is_empty());
+    }
+
     #[test]
     fn keeps_track_of_recently_opened_documents() {
         let mut state = State::default();
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+// Minimum contrast ratio recommended by WCAG for small text
+const MIN_TEXT_CONTRAST_RATIO: f32 = 4.5;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    const BLACK: Rgb = Rgb(0, 0, 0);
+    const WHITE: Rgb = Rgb(255, 255, 255);
+
+    // As defined in https://www.w3.org/TR/WCAG21/#dfn-relative-luminance
+    fn relative_luminance(self) -> f32 {
+        let linearize = |channel: u8| {
+            let c = channel as f32 / 255.0;
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linearize(self.0) + 0.7152 * linearize(self.1) + 0.0722 * linearize(self.2)
+    }
+
+    pub fn contrast_ratio(self, other: Rgb) -> f32 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+}
+
+impl From<Rgb> for (u8, u8, u8) {
+    fn from(color: Rgb) -> Self {
+        (color.0, color.1, color.2)
+    }
+}
+
+impl From<(u8, u8, u8)> for Rgb {
+    fn from(color: (u8, u8, u8)) -> Self {
+        Rgb(color.0, color.1, color.2)
+    }
+}
+
+// Colors of keyframe bars in the timeline. Text colors are only used when they remain readable
+// against their fill, see `readable_text_color`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct KeyframeColors {
+    pub fill: Rgb,
+    pub outline: Rgb,
+    pub text: Rgb,
+    pub selected_fill: Rgb,
+    pub selected_outline: Rgb,
+    pub selected_text: Rgb,
+}
+
+impl Default for KeyframeColors {
+    fn default() -> Self {
+        Self {
+            fill: Rgb(0x19, 0x19, 0x21),
+            outline: Rgb(0xEA, 0x58, 0x0C),
+            text: Rgb(0xFE, 0xD7, 0xAA),
+            selected_fill: Rgb(0x18, 0x18, 0x1B),
+            selected_outline: Rgb(0x25, 0x63, 0xEB),
+            selected_text: Rgb(0xDB, 0xEA, 0xFE),
+        }
+    }
+}
+
+impl KeyframeColors {
+    pub fn readable_text(&self) -> Rgb {
+        readable_text_color(self.fill, self.text)
+    }
+
+    pub fn readable_selected_text(&self) -> Rgb {
+        readable_text_color(self.selected_fill, self.selected_text)
+    }
+}
+
+// Keeps the preferred text color when it contrasts enough with the fill, and otherwise picks
+// whichever of black or white is easier to read.
+pub fn readable_text_color(fill: Rgb, preferred: Rgb) -> Rgb {
+    if preferred.contrast_ratio(fill) >= MIN_TEXT_CONTRAST_RATIO {
+        preferred
+    } else if Rgb::BLACK.contrast_ratio(fill) > Rgb::WHITE.contrast_ratio(fill) {
+        Rgb::BLACK
+    } else {
+        Rgb::WHITE
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn default_keyframe_colors_are_readable() {
+        let colors = KeyframeColors::default();
+        assert_eq!(colors.readable_text(), colors.text);
+        assert_eq!(colors.readable_selected_text(), colors.selected_text);
+    }
+
+    #[test]
+    fn picks_contrasting_text_color() {
+        let light_fill = Rgb(0xFD, 0xE6, 0x8A);
+        let dark_fill = Rgb(0x1E, 0x1E, 0x26);
+        let light_text = Rgb(0xFE, 0xF3, 0xC7);
+        let dark_text = Rgb(0x27, 0x27, 0x33);
+
+        assert_eq!(readable_text_color(light_fill, dark_text), dark_text);
+        assert_eq!(readable_text_color(light_fill, light_text), Rgb::BLACK);
+        assert_eq!(readable_text_color(dark_fill, light_text), light_text);
+        assert_eq!(readable_text_color(dark_fill, dark_text), Rgb::WHITE);
+    }
+
+    #[test]
+    fn computes_contrast_ratio() {
+        assert!((Rgb::BLACK.contrast_ratio(Rgb::WHITE) - 21.0).abs() < 0.01);
+        assert_eq!(
+            Rgb::BLACK.contrast_ratio(Rgb::WHITE),
+            Rgb::WHITE.contrast_ratio(Rgb::BLACK)
+        );
+        let grey = Rgb(0x80, 0x80, 0x80);
+        assert_eq!(grey.contrast_ratio(grey), 1.0);
+    }
+}
@@ -8,6 +8,7 @@ pub type Handle = handle::Handle<Paths>;
 pub struct Paths {
     pub log_file: PathBuf,
     pub recent_documents_file: PathBuf,
+    pub session_file: PathBuf,
 }
 
 impl Paths {
@@ -18,6 +19,7 @@ impl Paths {
         Self {
             log_file: data_local_dir.join("tiger.log"),
             recent_documents_file: data_local_dir.join("recent-documents.json"),
+            session_file: data_local_dir.join("session.json"),
         }
     }
 
@@ -35,6 +37,7 @@ impl Paths {
         let hash = s.finish();
         Paths {
             recent_documents_file: format!("test-output/recent_documents-{hash}.json").into(),
+            session_file: format!("test-output/session-{hash}.json").into(),
             log_file: format!("test-output/log-{hash}.log").into(),
         }
     }
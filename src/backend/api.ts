@@ -3,13 +3,29 @@ import {
   save as saveFileDialog,
 } from "@tauri-apps/api/dialog";
 import {
+  Align,
+  AtlasRegion,
   BrowseDirection,
+  Case,
   Direction,
   DirectionPreset,
+  ExportFormat,
+  ExportSettings,
+  FrameUsage,
+  GuideOrientation,
+  HitboxKind,
+  KeyframeColors,
+  KeyframeSummary,
   ListMode,
   NudgeDirection,
+  PackingAlgorithm,
+  PackingOrder,
+  PackingStats,
+  PanButton,
+  PathStyle,
   Patch,
   ResizeAxis,
+  TextureFormat,
 } from "@/backend/dto";
 import { useStateStore } from "@/stores/state";
 import { invoke } from "@tauri-apps/api";
@@ -87,6 +103,11 @@ export async function closeDocument(path: string): Promise<void> {
   appStore.patch(await invoke("close_document", { path: path }));
 }
 
+export async function reopenLastClosedDocument(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("reopen_last_closed_document"));
+}
+
 export async function closeCurrentDocument(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("close_current_document"));
@@ -97,6 +118,47 @@ export async function closeAllDocuments(): Promise<void> {
   appStore.patch(await invoke("close_all_documents"));
 }
 
+export async function setRestoreSessionOnStartup(
+  restore: boolean
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_restore_session_on_startup", { restore: restore })
+  );
+}
+
+export async function setSaveOnFocusLoss(save: boolean): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_save_on_focus_loss", { save: save }));
+}
+
+export async function setAutosaveInterval(intervalSecs: number): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_autosave_interval", { intervalSecs: intervalSecs })
+  );
+}
+
+export async function recoverAutosave(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("recover_autosave"));
+}
+
+export async function discardAutosave(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("discard_autosave"));
+}
+
+export async function setKeyframeColors(colors: KeyframeColors): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_keyframe_colors", { colors: colors }));
+}
+
+export async function resetKeyframeColors(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("reset_keyframe_colors"));
+}
+
 export async function requestExit(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("request_exit"));
@@ -142,6 +204,11 @@ export async function redo(): Promise<void> {
   appStore.patch(await invoke("redo"));
 }
 
+export async function clearHistory(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("clear_history"));
+}
+
 export async function cut(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("cut"));
@@ -157,6 +224,26 @@ export async function paste(): Promise<void> {
   appStore.patch(await invoke("paste"));
 }
 
+export async function pasteAfterSelection(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("paste_after_selection"));
+}
+
+export async function pasteProperties(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("paste_properties"));
+}
+
+export async function copyKeyframeHitboxes(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("copy_keyframe_hitboxes"));
+}
+
+export async function pasteKeyframeHitboxes(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("paste_keyframe_hitboxes"));
+}
+
 export async function setFramesListMode(listMode: ListMode): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("set_frames_list_mode", { listMode: listMode }));
@@ -191,6 +278,104 @@ export async function setHitboxesListOffset(offset: number): Promise<void> {
   appStore.patch(await invoke("set_hitboxes_list_offset", { offset: offset }));
 }
 
+export async function importAseprite() {
+  const file = await openFileDialog({
+    filters: [{ name: "Aseprite Sheets", extensions: ["json"] }],
+  });
+  if (typeof file !== "string") {
+    return;
+  }
+  const appStore = useStateStore();
+  appStore.patch(await invoke("import_aseprite", { path: file }));
+}
+
+export async function findFrameUsages(path: string): Promise<FrameUsage[]> {
+  return await invoke("find_frame_usages", { path: path });
+}
+
+export async function getAnimationThumbnail(
+  name: string
+): Promise<Uint8Array | null> {
+  const bytes: number[] | null = await invoke("get_animation_thumbnail", {
+    name: name,
+  });
+  return bytes ? new Uint8Array(bytes) : null;
+}
+
+export async function getCurrentKeyframeIndex(): Promise<number | null> {
+  return await invoke("get_current_keyframe_index");
+}
+
+export async function getDegenerateAnimations(): Promise<string[]> {
+  return await invoke("get_degenerate_animations");
+}
+
+export async function getMissingKeyframeFrames(): Promise<
+  [string, Direction, number][]
+> {
+  return await invoke("get_missing_keyframe_frames");
+}
+
+export async function getUnsavedDocuments(): Promise<string[]> {
+  return await invoke("get_unsaved_documents");
+}
+
+export async function getAnimationKeyframes(
+  name: string
+): Promise<KeyframeSummary[]> {
+  return await invoke("get_animation_keyframes", { name: name });
+}
+
+export async function getHitboxRect(
+  name: string
+): Promise<[[number, number], [number, number]] | null> {
+  return await invoke("get_hitbox_rect", { name });
+}
+
+export async function getKeyframeOffset(): Promise<[number, number] | null> {
+  return await invoke("get_keyframe_offset");
+}
+
+export async function getPackingStats(): Promise<PackingStats> {
+  return await invoke("get_packing_stats");
+}
+
+export async function getTimelineClock(): Promise<number> {
+  return await invoke("get_timeline_clock");
+}
+
+export async function importSpriteStrip(
+  frameWidth: number,
+  frameHeight: number,
+  count: number
+): Promise<void> {
+  const file = await openFileDialog({
+    filters: [{ name: "Image Files", extensions: ["png", "bmp"] }],
+  });
+  if (typeof file !== "string") {
+    return;
+  }
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("import_sprite_strip", {
+      path: file,
+      frameWidth: frameWidth,
+      frameHeight: frameHeight,
+      count: count,
+    })
+  );
+}
+
+export async function importAtlasRegions(
+  path: string,
+  regions: AtlasRegion[]
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("import_atlas_regions", { path: path, regions: regions })
+  );
+}
+
 export async function importFrames() {
   const files = await openFileDialog({
     filters: [{ name: "Image Files", extensions: ["png", "bmp"] }],
@@ -208,6 +393,23 @@ export async function importFrames() {
   }
 }
 
+export async function importFramesIntoAnimation() {
+  const files = await openFileDialog({
+    filters: [{ name: "Image Files", extensions: ["png", "bmp"] }],
+    multiple: true,
+  });
+  const appStore = useStateStore();
+  let patch: Patch | null = null;
+  if (typeof files === "string") {
+    patch = await invoke("import_frames_into_animation", { paths: [files] });
+  } else if (files) {
+    patch = await invoke("import_frames_into_animation", { paths: files });
+  }
+  if (patch) {
+    appStore.patch(patch);
+  }
+}
+
 export async function beginRelocateFrames(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("begin_relocate_frames"));
@@ -228,11 +430,67 @@ export async function endRelocateFrames(): Promise<void> {
   appStore.patch(await invoke("end_relocate_frames"));
 }
 
+export async function relocateFramesRoot(
+  oldRoot: string,
+  newRoot: string
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("relocate_frames_root", {
+      oldRoot: oldRoot,
+      newRoot: newRoot,
+    })
+  );
+}
+
+export async function replaceFrameSource(old: string): Promise<void> {
+  const file = await openFileDialog({
+    filters: [{ name: "Image Files", extensions: ["png", "bmp"] }],
+  });
+  if (typeof file !== "string") {
+    return;
+  }
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("replace_frame_source", { old: old, new: file })
+  );
+}
+
+export async function setFrameNormalMap(frame: string): Promise<void> {
+  const file = await openFileDialog({
+    filters: [{ name: "Image Files", extensions: ["png", "bmp"] }],
+  });
+  if (typeof file !== "string") {
+    return;
+  }
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_frame_normal_map", { frame: frame, normalMap: file })
+  );
+}
+
+export async function clearFrameNormalMap(frame: string): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_frame_normal_map", { frame: frame, normalMap: null })
+  );
+}
+
+export async function renameFrameFile(from: string, to: string): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("rename_frame_file", { from: from, to: to }));
+}
+
 export async function deleteFrame(path: string): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("delete_frame", { path: path }));
 }
 
+export async function repairSheet(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("repair_sheet"));
+}
+
 export async function deleteSelectedFrames(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("delete_selected_frames"));
@@ -269,6 +527,21 @@ export async function endRenameAnimation(newName: String): Promise<void> {
   appStore.patch(await invoke("end_rename_animation", { newName: newName }));
 }
 
+export async function normalizeAnimationNames(nameCase: Case): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("normalize_animation_names", { case: nameCase })
+  );
+}
+
+export async function swapAnimationNames(
+  nameA: string,
+  nameB: string
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("swap_animation_names", { nameA, nameB }));
+}
+
 export async function endRenameHitbox(newName: String): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("end_rename_hitbox", { newName: newName }));
@@ -284,6 +557,31 @@ export async function selectAll(): Promise<void> {
   appStore.patch(await invoke("select_all"));
 }
 
+export async function selectNextAnimation(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("select_next_animation"));
+}
+
+export async function selectPreviousAnimation(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("select_previous_animation"));
+}
+
+export async function invertHitboxSelection(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("invert_hitbox_selection"));
+}
+
+export async function selectKeyframesInRange(
+  startMillis: number,
+  endMillis: number
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("select_keyframes_in_range", { startMillis, endMillis })
+  );
+}
+
 export async function nudgeSelection(
   direction: NudgeDirection,
   largeNudge: boolean
@@ -397,6 +695,15 @@ export async function centerWorkbench(): Promise<void> {
   appStore.patch(await invoke("center_workbench"));
 }
 
+export async function fitWorkbenchToItem(
+  viewportSize: [number, number]
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("fit_workbench_to_item", { viewportSize: viewportSize })
+  );
+}
+
 export async function zoomInWorkbench(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("zoom_in_workbench"));
@@ -439,6 +746,66 @@ export async function resetWorkbenchZoom(): Promise<void> {
   appStore.patch(await invoke("reset_workbench_zoom"));
 }
 
+export async function resetView(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("reset_view"));
+}
+
+export async function addWorkbenchGuide(
+  orientation: GuideOrientation,
+  position: number
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("add_workbench_guide", {
+      orientation: orientation,
+      position: position,
+    })
+  );
+}
+
+export async function removeWorkbenchGuide(index: number): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("remove_workbench_guide", { index: index }));
+}
+
+export async function moveWorkbenchGuide(
+  index: number,
+  position: number
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("move_workbench_guide", { index: index, position: position })
+  );
+}
+
+export async function clearWorkbenchGuides(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("clear_workbench_guides"));
+}
+
+export async function setPanButton(panButton: PanButton): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_pan_button", { panButton: panButton }));
+}
+
+export async function setGridSpacing(spacing: number): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_grid_spacing", { spacing: spacing }));
+}
+
+export async function setGridSubdivisions(subdivisions: number): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_grid_subdivisions", { subdivisions: subdivisions })
+  );
+}
+
+export async function setOriginOffset(offset: [number, number]): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_origin_offset", { offset: offset }));
+}
+
 export async function enableSpriteDarkening(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("enable_sprite_darkening"));
@@ -469,6 +836,16 @@ export async function showHitboxes(): Promise<void> {
   appStore.patch(await invoke("show_hitboxes"));
 }
 
+export async function toggleHitboxVisibility(name: string): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("toggle_hitbox_visibility", { name: name }));
+}
+
+export async function showAllHitboxes(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("show_all_hitboxes"));
+}
+
 export async function hideOrigin(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("hide_origin"));
@@ -484,21 +861,45 @@ export async function createAnimation(): Promise<void> {
   appStore.patch(await invoke("create_animation"));
 }
 
+export async function createAnimationFromSelectedFrames(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("create_animation_from_selected_frames"));
+}
+
 export async function editAnimation(name: string): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("edit_animation", { name: name }));
 }
 
+export async function reorderAnimations(names: string[]): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("reorder_animations", { names: names }));
+}
+
 export async function deleteAnimation(name: string): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("delete_animation", { name: name }));
 }
 
+export async function deleteAnimationsNotMatching(
+  query: string
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("delete_animations_not_matching", { query: query })
+  );
+}
+
 export async function deleteSelectedAnimations(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("delete_selected_animations"));
 }
 
+export async function setAnimationFrame(path: string): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_animation_frame", { path: path }));
+}
+
 export async function tick(deltaTimeMillis: number): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("tick", { deltaTimeMillis: deltaTimeMillis }));
@@ -514,6 +915,11 @@ export async function pause(): Promise<void> {
   appStore.patch(await invoke("pause"));
 }
 
+export async function togglePlaybackAllDocuments(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("toggle_playback_all_documents"));
+}
+
 export async function scrubTimeline(timeMillis: number): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("scrub_timeline", { timeMillis: timeMillis }));
@@ -539,6 +945,11 @@ export async function jumpToNextFrame(): Promise<void> {
   appStore.patch(await invoke("jump_to_next_frame"));
 }
 
+export async function jumpToKeyframe(index: number): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("jump_to_keyframe", { index: index }));
+}
+
 export async function setSnapKeyframeDurations(snap: boolean): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("set_snap_keyframe_durations", { snap: snap }));
@@ -573,6 +984,17 @@ export async function setKeyframeSnappingBaseDuration(
   );
 }
 
+export async function setMinKeyframeDuration(
+  durationMillis: number
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_min_keyframe_duration", {
+      durationMillis: durationMillis,
+    })
+  );
+}
+
 export async function zoomInTimeline(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("zoom_in_timeline"));
@@ -614,6 +1036,16 @@ export async function setTimelineOffset(offsetMillis: number): Promise<void> {
   );
 }
 
+export async function setFollowPlayhead(follow: boolean): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_follow_playhead", { follow: follow }));
+}
+
+export async function setTimelineViewportWidth(width: number): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_timeline_viewport_width", { width: width }));
+}
+
 export async function panTimeline(delta: number): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("pan_timeline", { delta: delta }));
@@ -626,6 +1058,51 @@ export async function setAnimationLooping(isLooping: boolean): Promise<void> {
   );
 }
 
+export async function setAnimationStartDelay(
+  startDelayMillis: number
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_animation_start_delay", {
+      startDelayMillis: startDelayMillis,
+    })
+  );
+}
+
+export async function setAnimationOrigin(
+  origin: [number, number]
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_animation_origin", { origin: origin }));
+}
+
+export async function nudgeAnimationOrigin(
+  direction: NudgeDirection,
+  largeNudge: boolean
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("nudge_animation_origin", {
+      direction: direction,
+      largeNudge: largeNudge,
+    })
+  );
+}
+
+export async function extractCommonOffset(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("extract_common_offset"));
+}
+
+export async function setSelectedAnimationsLooping(
+  isLooping: boolean
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_selected_animations_looping", { isLooping: isLooping })
+  );
+}
+
 export async function applyDirectionPreset(
   preset: DirectionPreset
 ): Promise<void> {
@@ -666,6 +1143,51 @@ export async function deleteSelectedKeyframes(): Promise<void> {
   appStore.patch(await invoke("delete_selected_keyframes"));
 }
 
+export async function moveKeyframesEarlier(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("move_keyframes_earlier"));
+}
+
+export async function moveKeyframesLater(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("move_keyframes_later"));
+}
+
+export async function trimAnimationEnds(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("trim_animation_ends"));
+}
+
+export async function mergeAdjacentDuplicateKeyframes(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("merge_adjacent_duplicate_keyframes"));
+}
+
+export async function resetKeyframeDuration(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("reset_keyframe_duration"));
+}
+
+export async function resetKeyframeOffsets(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("reset_keyframe_offsets"));
+}
+
+export async function translateSelectedKeyframes(
+  dx: number,
+  dy: number
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("translate_selected_keyframes", { dx: dx, dy: dy })
+  );
+}
+
+export async function alignKeyframeOffsets(align: Align): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("align_keyframe_offsets", { align: align }));
+}
+
 export async function setKeyframeDuration(
   durationMillis: number
 ): Promise<void> {
@@ -675,6 +1197,46 @@ export async function setKeyframeDuration(
   );
 }
 
+export async function setKeyframeDurationInFrames(
+  frames: number,
+  fps: number
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_keyframe_duration_in_frames", {
+      frames: frames,
+      fps: fps,
+    })
+  );
+}
+
+export async function setKeyframeHold(hold: number): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_keyframe_hold", { hold: hold }));
+}
+
+export async function quantizeKeyframeDurations(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("quantize_keyframe_durations"));
+}
+
+export async function setUniformKeyframeDuration(
+  durationMillis: number
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_uniform_keyframe_duration", {
+      durationMillis: durationMillis,
+    })
+  );
+}
+
+export async function getKeyframeDurationsInFrames(
+  fps: number
+): Promise<Record<Direction, number[]>> {
+  return await invoke("get_keyframe_durations_in_frames", { fps: fps });
+}
+
 export async function setKeyframeOffsetX(x: number): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("set_keyframe_offset_x", { x: x }));
@@ -685,6 +1247,16 @@ export async function setKeyframeOffsetY(y: number): Promise<void> {
   appStore.patch(await invoke("set_keyframe_offset_y", { y: y }));
 }
 
+export async function addKeyframeEvent(name: string): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("add_keyframe_event", { name: name }));
+}
+
+export async function removeKeyframeEvent(name: string): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("remove_keyframe_event", { name: name }));
+}
+
 export async function beginDragAndDropKeyframe(
   direction: Direction,
   index: number
@@ -786,11 +1358,65 @@ export async function deleteHitbox(name: string): Promise<void> {
   appStore.patch(await invoke("delete_hitbox", { name: name }));
 }
 
+export async function matchHitboxSizes(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("match_hitbox_sizes"));
+}
+
+export async function flipHitboxesHorizontally(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("flip_hitboxes_horizontally"));
+}
+
+export async function deleteHitboxFromAllKeyframes(
+  name: string
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("delete_hitbox_from_all_keyframes", { name: name })
+  );
+}
+
+export async function deleteAllHitboxesOnKeyframe(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("delete_all_hitboxes_on_keyframe"));
+}
+
 export async function deleteSelectedHitboxes(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("delete_selected_hitboxes"));
 }
 
+export async function duplicateSelectedHitboxes(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("duplicate_selected_hitboxes"));
+}
+
+export async function subdivideHitbox(
+  name: string,
+  columns: number,
+  rows: number
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("subdivide_hitbox", {
+      name: name,
+      columns: columns,
+      rows: rows,
+    })
+  );
+}
+
+export async function prefixHitboxNames(prefix: string): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("prefix_hitbox_names", { prefix: prefix }));
+}
+
+export async function suffixHitboxNames(suffix: string): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("suffix_hitbox_names", { suffix: suffix }));
+}
+
 export async function lockHitboxes(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("lock_hitboxes"));
@@ -821,11 +1447,30 @@ export async function setHitboxHeight(height: number): Promise<void> {
   appStore.patch(await invoke("set_hitbox_height", { height: height }));
 }
 
+export async function setHitboxKind(kind: HitboxKind): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_hitbox_kind", { kind: kind }));
+}
+
+export async function togglePreciseDurations(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("toggle_precise_durations"));
+}
+
 export async function togglePreserveAspectRatio(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("toggle_preserve_aspect_ratio"));
 }
 
+export async function setDefaultPreserveAspectRatio(
+  preserve: boolean
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_default_preserve_aspect_ratio", { preserve: preserve })
+  );
+}
+
 export async function beginNudgeHitbox(name: string): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("begin_nudge_hitbox", { name: name }));
@@ -849,6 +1494,61 @@ export async function endNudgeHitbox(): Promise<void> {
   appStore.patch(await invoke("end_nudge_hitbox"));
 }
 
+export async function createAnchor(
+  position: [number, number] | null
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("create_anchor", { position: position }));
+}
+
+export async function setAnchorPosition(
+  name: string,
+  position: [number, number]
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_anchor_position", { name: name, position: position })
+  );
+}
+
+export async function renameAnchor(
+  oldName: string,
+  newName: string
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("rename_anchor", { oldName: oldName, newName: newName })
+  );
+}
+
+export async function deleteAnchor(name: string): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("delete_anchor", { name: name }));
+}
+
+export async function beginNudgeAnchor(name: string): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("begin_nudge_anchor", { name: name }));
+}
+
+export async function updateNudgeAnchor(
+  displacement: [number, number],
+  bothAxis: boolean
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("update_nudge_anchor", {
+      displacement: displacement,
+      bothAxis: bothAxis,
+    })
+  );
+}
+
+export async function endNudgeAnchor(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("end_nudge_anchor"));
+}
+
 export async function beginResizeHitbox(
   name: string,
   axis: ResizeAxis
@@ -882,6 +1582,31 @@ export async function doExport(): Promise<void> {
   appStore.patch(await invoke("export"));
 }
 
+export async function exportAndReveal(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("export_and_reveal"));
+}
+
+export async function exportContactSheet(): Promise<void> {
+  const path = await saveFileDialog({
+    filters: [{ name: "Image Files", extensions: ["png"] }],
+  });
+  if (typeof path === "string") {
+    const appStore = useStateStore();
+    appStore.patch(await invoke("export_contact_sheet", { path: path }));
+  }
+}
+
+export async function exportCommandHistory(
+  redactPaths: boolean
+): Promise<string> {
+  return await invoke("export_command_history", { redactPaths: redactPaths });
+}
+
+export async function getTemplateContextPreview(): Promise<string> {
+  return await invoke("get_template_context_preview");
+}
+
 export async function beginExportAs(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("begin_export_as"));
@@ -911,6 +1636,119 @@ export async function setExportMetadataPathsRoot(
   );
 }
 
+export async function setExportFormat(format: ExportFormat): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_export_format", { format: format }));
+}
+
+export async function setExportTextureFormat(
+  format: TextureFormat
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_export_texture_format", { format: format }));
+}
+
+export async function setExportTextureScale(scale: number): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_export_texture_scale", { scale: scale }));
+}
+
+export async function setPackingAlgorithm(
+  algorithm: PackingAlgorithm
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_packing_algorithm", { algorithm: algorithm })
+  );
+}
+
+export async function setPackingOrder(order: PackingOrder): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_packing_order", { order: order }));
+}
+
+export async function setExportPathStyle(style: PathStyle): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_export_path_style", { style: style }));
+}
+
+export async function setExportDeduplicateFrames(
+  deduplicate: boolean
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_export_deduplicate_frames", { deduplicate: deduplicate })
+  );
+}
+
+export async function setExportAllowRotation(
+  allowRotation: boolean
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_export_allow_rotation", { allowRotation: allowRotation })
+  );
+}
+
+export async function setExportMipSafePadding(padding: number): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_export_mip_safe_padding", { padding: padding }));
+}
+
+export async function setExportSettings(settings: ExportSettings): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("set_export_settings", { settings: settings }));
+}
+
+export async function setExportWriteManifest(
+  writeManifest: boolean
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_export_write_manifest", { writeManifest: writeManifest })
+  );
+}
+
+export async function wasExportChanged(): Promise<boolean> {
+  return await invoke("was_export_changed");
+}
+
+export async function addExportTarget(): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("add_export_target"));
+}
+
+export async function removeExportTarget(index: number): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(await invoke("remove_export_target", { index: index }));
+}
+
+export async function setExportTargetTemplateFile(
+  index: number,
+  file: string
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_export_target_template_file", {
+      index: index,
+      file: file,
+    })
+  );
+}
+
+export async function setExportTargetMetadataFile(
+  index: number,
+  file: string
+): Promise<void> {
+  const appStore = useStateStore();
+  appStore.patch(
+    await invoke("set_export_target_metadata_file", {
+      index: index,
+      file: file,
+    })
+  );
+}
+
 export async function cancelExportAs(): Promise<void> {
   const appStore = useStateStore();
   appStore.patch(await invoke("cancel_export_as"));
@@ -6,11 +6,28 @@ export type State = {
   documents: Document[];
   currentDocumentPath: string | null;
   recentDocumentPaths: RecentDocument[];
+  restoreSessionOnStartup: boolean;
+  saveOnFocusLoss: boolean;
+  autosaveIntervalSecs: number;
+  keyframeColors: KeyframeColors;
   clipboardManifest: ClipboardManifest | null;
   isReleaseBuild: boolean;
   error: UserFacingError | null;
 };
 
+export type Rgb = [number, number, number];
+
+export type KeyframeColors = {
+  fill: Rgb;
+  outline: Rgb;
+  text: Rgb;
+  selectedFill: Rgb;
+  selectedOutline: Rgb;
+  selectedText: Rgb;
+  readableText?: Rgb;
+  readableSelectedText?: Rgb;
+};
+
 export type RecentDocument = {
   path: string;
   name: string;
@@ -33,6 +50,7 @@ export type Document = {
   path: string;
   name: string;
   hasUnsavedChanges: boolean;
+  hasRecoverableAutosave: boolean;
   undoEffect: string | null;
   redoEffect: string | null;
   wasCloseRequested: boolean;
@@ -46,7 +64,15 @@ export type Document = {
   lastInteractedAnimation: string | null;
   lastInteractedFrame: string | null;
   lastInteractedHitbox: string | null;
+  gridSpacing: number;
+  gridSubdivisions: number;
+  originOffset: [number, number];
+  panButton: PanButton;
+  workbenchGridLineSpacing: number;
+  workbenchGuides: WorkbenchGuide[];
   workbenchOffset: [number, number];
+  workbenchPanButtons: MouseButton[];
+  workbenchSpacePanButtons: MouseButton[];
   workbenchZoom: number;
   currentAnimationName: string | null;
   currentSequenceDirection: Direction | null;
@@ -56,16 +82,20 @@ export type Document = {
   timelineOffsetMillis: number;
   timelineZoomFactor: number;
   timelineZoomAmount: number;
+  followPlayhead: boolean;
   snapKeyframeDurations: boolean;
   snapKeyframesToOtherKeyframes: boolean;
   snapKeyframesToMultiplesOfDuration: boolean;
   keyframeSnappingBaseDurationMillis: number;
+  minKeyframeDurationMillis: number;
   darkenSprites: boolean;
   hideSprite: boolean;
   hideHitboxes: boolean;
   hideOrigin: boolean;
   lockHitboxes: boolean;
   preserveAspectRatio: boolean;
+  defaultPreserveAspectRatio: boolean;
+  preciseDurations: boolean;
   isDraggingKeyframeDuration: boolean;
   animationBeingRenamed: string | null;
   hitboxBeingRenamed: string | null;
@@ -86,9 +116,11 @@ export type Sheet = {
 export type Frame = {
   path: string;
   name: string;
+  ambiguousName: boolean;
   selected: boolean;
   filteredOut: boolean;
   missingOnDisk: boolean;
+  normalMap: string | null;
 };
 
 export type Animation = {
@@ -98,6 +130,8 @@ export type Animation = {
   sequences: Record<Direction, Sequence>;
   directionPreset: DirectionPreset | null;
   isLooping: boolean;
+  startDelayMillis: number;
+  origin: [number, number];
 };
 
 export enum Direction {
@@ -131,24 +165,86 @@ export type Keyframe = {
   selected: boolean;
   startTimeMillis: number;
   durationMillis: number;
+  durationMicros: number;
+  hold: number;
   offset: [number, number];
+  events: string[];
   hitboxes: Hitbox[];
+  anchors: Anchor[];
+  missingOnDisk: boolean;
+  thumbnailSize: number | null;
   key: string;
 };
 
+export type Anchor = {
+  name: string;
+  position: [number, number];
+};
+
 export type Hitbox = {
   name: string;
   selected: boolean;
+  hidden: boolean;
+  kind: HitboxKind;
   topLeft: [number, number];
   size: [number, number];
   key: string;
 };
 
+export enum HitboxKind {
+  Generic = "Generic",
+  Hurtbox = "Hurtbox",
+  Attack = "Attack",
+  Grab = "Grab",
+}
+
+export type FrameUsage = {
+  animationName: string;
+  direction: Direction;
+  keyframeIndex: number;
+};
+
+export type KeyframeSummary = {
+  direction: Direction;
+  framePath: string;
+  durationMillis: number;
+  offset: [number, number];
+  hitboxCount: number;
+};
+
+export type PackingStats = {
+  usedArea: number;
+  atlasArea: number;
+  efficiency: number;
+};
+
+export type WorkbenchGuide = {
+  orientation: GuideOrientation;
+  position: number;
+  offset: number;
+};
+
+export enum GuideOrientation {
+  Horizontal = "Horizontal",
+  Vertical = "Vertical",
+}
+
 export enum ListMode {
   Linear = "Linear",
   Grid4xN = "Grid4xN",
 }
 
+export enum MouseButton {
+  Left = "Left",
+  Middle = "Middle",
+  Right = "Right",
+}
+
+export enum PanButton {
+  Right = "Right",
+  Middle = "Middle",
+}
+
 export enum ResizeAxis {
   N = "N",
   S = "S",
@@ -167,6 +263,20 @@ export enum NudgeDirection {
   Right = "Right",
 }
 
+export enum Align {
+  Left = "Left",
+  Center = "Center",
+  Right = "Right",
+  Top = "Top",
+  Bottom = "Bottom",
+}
+
+export enum Case {
+  Snake = "Snake",
+  Kebab = "Kebab",
+  Camel = "Camel",
+}
+
 export enum BrowseDirection {
   Up = "Up",
   Down = "Down",
@@ -179,6 +289,58 @@ export type ExportSettings = {
   textureFile: string;
   metadataFile: string;
   metadataPathsRoot: string;
+  format: ExportFormat;
+  textureFormat: TextureFormat;
+  textureScale: number;
+  packingAlgorithm: PackingAlgorithm;
+  packingOrder: PackingOrder;
+  pathStyle: PathStyle;
+  deduplicateFrames: boolean;
+  allowRotation: boolean;
+  mipSafePadding: number;
+  writeManifest: boolean;
+  additionalTargets: ExportTarget[];
+};
+
+export type ExportTarget = {
+  templateFile: string;
+  metadataFile: string;
+};
+
+export enum ExportFormat {
+  Template = "Template",
+  GodotSpriteFrames = "GodotSpriteFrames",
+  Css = "Css",
+}
+
+export enum TextureFormat {
+  Png = "Png",
+  WebP = "WebP",
+  Qoi = "Qoi",
+}
+
+export enum PackingAlgorithm {
+  MaxRects = "MaxRects",
+  Shelf = "Shelf",
+}
+
+export enum PackingOrder {
+  Sorted = "Sorted",
+  Sheet = "Sheet",
+}
+
+export enum PathStyle {
+  AbsolutePath = "AbsolutePath",
+  RelativeToRoot = "RelativeToRoot",
+  BasenameOnly = "BasenameOnly",
+}
+
+export type AtlasRegion = {
+  name: string;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
 };
 
 export type TemplateError = {
@@ -190,6 +352,9 @@ export type ExportSettingsError =
   | "ExpectedDirectory"
   | "ExpectedFile"
   | "FileNotFound"
+  | "InvalidTextureScale"
+  | "InvalidTextureExtension"
+  | "UnsupportedRotation"
   | TemplateError;
 
 export type ExportSettingsValidation = {
@@ -198,6 +363,14 @@ export type ExportSettingsValidation = {
   textureFileError: ExportSettingsError | null;
   metadataFileError: ExportSettingsError | null;
   metadataPathsRootError: ExportSettingsError | null;
+  textureScaleError: ExportSettingsError | null;
+  allowRotationError: ExportSettingsError | null;
+  additionalTargets: ExportTargetValidation[];
+};
+
+export type ExportTargetValidation = {
+  templateFileError: ExportSettingsError | null;
+  metadataFileError: ExportSettingsError | null;
 };
 
 export type TextureInvalidationEvent = {
@@ -29,6 +29,8 @@ export const useStateStore = defineStore("state", {
     activeModalId(): string | null {
       if (this.error != null) {
         return this.error.key;
+      } else if (this.currentDocument?.hasRecoverableAutosave) {
+        return "recovering_" + this.currentDocument.path;
       } else if (this.currentDocument?.wasCloseRequested) {
         return "closing_" + this.currentDocument.path;
       } else if (!!this.currentDocument?.framesBeingRelocated) {
@@ -15,19 +15,28 @@ import {
   cut,
   deleteSelection,
   doExport,
+  duplicateSelectedHitboxes,
+  moveKeyframesEarlier,
+  moveKeyframesLater,
   newDocument,
+  nudgeAnimationOrigin,
   nudgeSelection,
   openDocuments,
   paste,
+  pasteAfterSelection,
   pause,
   play,
   redo,
+  reopenLastClosedDocument,
   resetTimelineZoom,
   resetWorkbenchZoom,
   save,
   saveAll,
   saveAs,
   selectAll,
+  selectNextAnimation,
+  selectPreviousAnimation,
+  togglePlaybackAllDocuments,
   undo,
   zoomInTimeline,
   zoomInWorkbench,
@@ -36,6 +45,7 @@ import {
 } from "@/backend/api";
 import { BrowseDirection, NudgeDirection } from "@/backend/dto";
 import { useStateStore } from "@/stores/state";
+import { pressSpace, releaseSpace } from "@/utils/panning";
 
 function onKeyDown(event: KeyboardEvent) {
   const isActiveElementKeyboardFriendly =
@@ -70,6 +80,8 @@ function onKeyDown(event: KeyboardEvent) {
       closeCurrentDocument();
     } else if (event.key == "W") {
       closeAllDocuments();
+    } else if (event.key == "T") {
+      reopenLastClosedDocument();
     } else if (event.key == "z") {
       event.preventDefault();
       undo();
@@ -82,6 +94,8 @@ function onKeyDown(event: KeyboardEvent) {
       copy();
     } else if (event.key == "v") {
       paste();
+    } else if (event.key == "V") {
+      pasteAfterSelection();
     } else if (event.key == " ") {
       centerWorkbench();
     } else if (event.key == "+" || event.key == "=") {
@@ -104,6 +118,17 @@ function onKeyDown(event: KeyboardEvent) {
       }
     } else if (event.key == "a") {
       selectAll();
+    } else if (event.key == "d") {
+      event.preventDefault();
+      duplicateSelectedHitboxes();
+    } else if (event.key == "ArrowUp" && event.altKey) {
+      nudgeAnimationOrigin(NudgeDirection.Up, event.shiftKey);
+    } else if (event.key == "ArrowDown" && event.altKey) {
+      nudgeAnimationOrigin(NudgeDirection.Down, event.shiftKey);
+    } else if (event.key == "ArrowLeft" && event.altKey) {
+      nudgeAnimationOrigin(NudgeDirection.Left, event.shiftKey);
+    } else if (event.key == "ArrowRight" && event.altKey) {
+      nudgeAnimationOrigin(NudgeDirection.Right, event.shiftKey);
     } else if (event.key == "ArrowUp") {
       nudgeSelection(NudgeDirection.Up, event.shiftKey);
     } else if (event.key == "ArrowDown") {
@@ -117,10 +142,8 @@ function onKeyDown(event: KeyboardEvent) {
     if (event.key == " ") {
       if (!isActiveElementKeyboardFriendly) {
         event.preventDefault();
-        if (state.currentDocument?.timelineIsPlaying) {
-          pause();
-        } else {
-          play();
+        if (!event.repeat) {
+          pressSpace();
         }
       }
     } else if (event.key == "Delete") {
@@ -131,12 +154,24 @@ function onKeyDown(event: KeyboardEvent) {
     } else if (event.key == "ArrowDown") {
       event.preventDefault();
       browseSelection(BrowseDirection.Down, event.shiftKey);
+    } else if (event.key == "ArrowLeft" && event.altKey) {
+      event.preventDefault();
+      moveKeyframesEarlier();
+    } else if (event.key == "ArrowRight" && event.altKey) {
+      event.preventDefault();
+      moveKeyframesLater();
     } else if (event.key == "ArrowLeft") {
       event.preventDefault();
       browseSelection(BrowseDirection.Left, event.shiftKey);
     } else if (event.key == "ArrowRight") {
       event.preventDefault();
       browseSelection(BrowseDirection.Right, event.shiftKey);
+    } else if (event.key == "PageUp") {
+      event.preventDefault();
+      selectPreviousAnimation();
+    } else if (event.key == "PageDown") {
+      event.preventDefault();
+      selectNextAnimation();
     } else if (event.key == "Home") {
       browseToStart(event.shiftKey);
     } else if (event.key == "End") {
@@ -161,10 +196,30 @@ function onKeyDown(event: KeyboardEvent) {
   }
 }
 
+// Playback toggles when space is released, so that holding it can be used for panning.
+// Holding shift toggles playback of every open document at once.
+function onKeyUp(event: KeyboardEvent) {
+  if (event.key != " ") {
+    return;
+  }
+  const state = useStateStore();
+  if (releaseSpace()) {
+    if (event.shiftKey) {
+      togglePlaybackAllDocuments();
+    } else if (state.currentDocument?.timelineIsPlaying) {
+      pause();
+    } else {
+      play();
+    }
+  }
+}
+
 export function registerKeyboardShortcuts() {
   window.addEventListener("keydown", onKeyDown);
+  window.addEventListener("keyup", onKeyUp);
 }
 
 export function unregisterKeyboardShortcuts() {
   window.removeEventListener("keydown", onKeyDown);
+  window.removeEventListener("keyup", onKeyUp);
 }
@@ -0,0 +1,36 @@
+import { ref } from "vue";
+import { MouseButton } from "@/backend/dto";
+import type { DragButton } from "@/components/basic/DragArea.vue";
+
+// Holding space lets the workbench pan with the left mouse button
+export const isSpaceHeld = ref(false);
+let didPanWhileSpaceHeld = false;
+
+export function pressSpace() {
+  isSpaceHeld.value = true;
+  didPanWhileSpaceHeld = false;
+}
+
+// Returns true if space was pressed and released without panning
+export function releaseSpace(): boolean {
+  const wasHeld = isSpaceHeld.value;
+  isSpaceHeld.value = false;
+  return wasHeld && !didPanWhileSpaceHeld;
+}
+
+export function notifyPanning() {
+  if (isSpaceHeld.value) {
+    didPanWhileSpaceHeld = true;
+  }
+}
+
+export function toDragButton(button: MouseButton): DragButton {
+  switch (button) {
+    case MouseButton.Left:
+      return "left";
+    case MouseButton.Middle:
+      return "middle";
+    case MouseButton.Right:
+      return "right";
+  }
+}